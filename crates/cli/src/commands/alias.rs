@@ -8,13 +8,13 @@ use serde::Serialize;
 
 use crate::exit_code::ExitCode;
 use crate::output::{Formatter, OutputConfig};
-use rc_core::{Alias, AliasManager};
+use rc_core::{Alias, AliasManager, RetryConfig, TimeoutConfig};
 
 /// Alias subcommands for managing storage service connections
 #[derive(Subcommand, Debug)]
 pub enum AliasCommands {
     /// Add or update an alias
-    Set(SetArgs),
+    Set(Box<SetArgs>),
 
     /// List all configured aliases
     List(ListArgs),
@@ -50,9 +50,33 @@ pub struct SetArgs {
     #[arg(long, default_value = "auto")]
     pub bucket_lookup: String,
 
+    /// Base domain for virtual-hosted buckets (e.g., s3.internal.corp)
+    #[arg(long)]
+    pub bucket_domain: Option<String>,
+
     /// Allow insecure TLS connections
     #[arg(long, default_value = "false")]
     pub insecure: bool,
+
+    /// Maximum attempts per request, including the first (default: 3)
+    #[arg(long)]
+    pub retry_attempts: Option<u32>,
+
+    /// Initial retry backoff in milliseconds (default: 100)
+    #[arg(long)]
+    pub retry_initial_backoff_ms: Option<u64>,
+
+    /// Maximum retry backoff in milliseconds (default: 10000)
+    #[arg(long)]
+    pub retry_max_backoff_ms: Option<u64>,
+
+    /// Connection timeout in milliseconds (default: 5000)
+    #[arg(long)]
+    pub connect_timeout_ms: Option<u64>,
+
+    /// Read timeout in milliseconds (default: 30000)
+    #[arg(long)]
+    pub read_timeout_ms: Option<u64>,
 }
 
 /// Arguments for the `alias list` command
@@ -116,7 +140,7 @@ pub async fn execute(cmd: AliasCommands, output_config: OutputConfig) -> ExitCod
     };
 
     match cmd {
-        AliasCommands::Set(args) => execute_set(args, &alias_manager, &formatter).await,
+        AliasCommands::Set(args) => execute_set(*args, &alias_manager, &formatter).await,
         AliasCommands::List(args) => execute_list(args, &alias_manager, &formatter).await,
         AliasCommands::Remove(args) => execute_remove(args, &alias_manager, &formatter).await,
     }
@@ -146,6 +170,21 @@ async fn execute_set(args: SetArgs, manager: &AliasManager, formatter: &Formatte
         return ExitCode::UsageError;
     }
 
+    if args.bucket_domain.is_some() && args.bucket_lookup == "path" {
+        formatter.error("--bucket-domain cannot be used with --bucket-lookup path");
+        return ExitCode::UsageError;
+    }
+
+    // Keep retry and timeout settings of an existing alias unless overridden
+    let existing = manager.get(&args.name).ok();
+    let (retry, timeout) = match apply_network_settings(&args, existing.as_ref()) {
+        Ok(settings) => settings,
+        Err(e) => {
+            formatter.error(&e);
+            return ExitCode::UsageError;
+        }
+    };
+
     // Create alias
     let mut alias = Alias::new(
         &args.name,
@@ -156,7 +195,10 @@ async fn execute_set(args: SetArgs, manager: &AliasManager, formatter: &Formatte
     alias.region = args.region;
     alias.signature = args.signature;
     alias.bucket_lookup = args.bucket_lookup;
+    alias.bucket_domain = args.bucket_domain;
     alias.insecure = args.insecure;
+    alias.retry = retry;
+    alias.timeout = timeout;
 
     // Save alias
     match manager.set(alias) {
@@ -181,6 +223,55 @@ async fn execute_set(args: SetArgs, manager: &AliasManager, formatter: &Formatte
     }
 }
 
+/// Merge the retry and timeout flags over the settings of an existing alias
+fn apply_network_settings(
+    args: &SetArgs,
+    existing: Option<&Alias>,
+) -> Result<(Option<RetryConfig>, Option<TimeoutConfig>), String> {
+    let mut retry = existing.and_then(|a| a.retry.clone());
+    if args.retry_attempts.is_some()
+        || args.retry_initial_backoff_ms.is_some()
+        || args.retry_max_backoff_ms.is_some()
+    {
+        let mut config = retry.unwrap_or_default();
+        if let Some(attempts) = args.retry_attempts {
+            config.max_attempts = attempts;
+        }
+        if let Some(ms) = args.retry_initial_backoff_ms {
+            config.initial_backoff_ms = ms;
+        }
+        if let Some(ms) = args.retry_max_backoff_ms {
+            config.max_backoff_ms = ms;
+        }
+
+        if config.max_attempts == 0 {
+            return Err("Retry attempts must be at least 1".to_string());
+        }
+        if config.initial_backoff_ms > config.max_backoff_ms {
+            return Err("Initial retry backoff cannot exceed the maximum backoff".to_string());
+        }
+        retry = Some(config);
+    }
+
+    let mut timeout = existing.and_then(|a| a.timeout.clone());
+    if args.connect_timeout_ms.is_some() || args.read_timeout_ms.is_some() {
+        let mut config = timeout.unwrap_or_default();
+        if let Some(ms) = args.connect_timeout_ms {
+            config.connect_ms = ms;
+        }
+        if let Some(ms) = args.read_timeout_ms {
+            config.read_ms = ms;
+        }
+
+        if config.connect_ms == 0 || config.read_ms == 0 {
+            return Err("Timeouts must be greater than 0".to_string());
+        }
+        timeout = Some(config);
+    }
+
+    Ok((retry, timeout))
+}
+
 async fn execute_list(args: ListArgs, manager: &AliasManager, formatter: &Formatter) -> ExitCode {
     match manager.list() {
         Ok(aliases) => {
@@ -265,7 +356,13 @@ mod tests {
             region: "us-east-1".to_string(),
             signature: "v4".to_string(),
             bucket_lookup: "auto".to_string(),
+            bucket_domain: None,
             insecure: false,
+            retry_attempts: None,
+            retry_initial_backoff_ms: None,
+            retry_max_backoff_ms: None,
+            connect_timeout_ms: None,
+            read_timeout_ms: None,
         };
 
         assert_eq!(args.region, "us-east-1");
@@ -283,4 +380,51 @@ mod tests {
         assert_eq!(info.endpoint, "http://localhost:9000");
         assert_eq!(info.region, "us-east-1");
     }
+
+    #[test]
+    fn test_apply_network_settings() {
+        let mut args = SetArgs {
+            name: "test".to_string(),
+            endpoint: "http://localhost:9000".to_string(),
+            access_key: "accesskey".to_string(),
+            secret_key: "secretkey".to_string(),
+            region: "us-east-1".to_string(),
+            signature: "v4".to_string(),
+            bucket_lookup: "auto".to_string(),
+            bucket_domain: None,
+            insecure: false,
+            retry_attempts: None,
+            retry_initial_backoff_ms: None,
+            retry_max_backoff_ms: None,
+            connect_timeout_ms: None,
+            read_timeout_ms: Some(120000),
+        };
+
+        // Unset values fall back to the defaults
+        let (retry, timeout) = apply_network_settings(&args, None).unwrap();
+        assert!(retry.is_none());
+        let timeout = timeout.unwrap();
+        assert_eq!(timeout.connect_ms, 5000);
+        assert_eq!(timeout.read_ms, 120000);
+
+        // Settings of an existing alias are kept
+        let mut existing = Alias::new("test", "http://localhost:9000", "key", "secret");
+        existing.retry = Some(RetryConfig {
+            max_attempts: 7,
+            ..RetryConfig::default()
+        });
+        args.read_timeout_ms = None;
+        args.retry_max_backoff_ms = Some(60000);
+        let (retry, timeout) = apply_network_settings(&args, Some(&existing)).unwrap();
+        let retry = retry.unwrap();
+        assert_eq!(retry.max_attempts, 7);
+        assert_eq!(retry.max_backoff_ms, 60000);
+        assert!(timeout.is_none());
+
+        args.retry_attempts = Some(0);
+        assert!(apply_network_settings(&args, None).is_err());
+        args.retry_attempts = None;
+        args.retry_initial_backoff_ms = Some(90000);
+        assert!(apply_network_settings(&args, None).is_err());
+    }
 }
@@ -3,9 +3,9 @@
 //! Outputs the entire content of an object to stdout.
 
 use clap::Args;
-use rc_core::{AliasManager, ObjectStore as _, RemotePath};
+use rc_core::{AliasManager, ObjectStore as _, RemotePath, rewind};
 use rc_s3::S3Client;
-use std::io::{self, Write};
+use tokio::io::AsyncWriteExt;
 
 use crate::exit_code::ExitCode;
 use crate::output::{Formatter, OutputConfig};
@@ -20,12 +20,12 @@ pub struct CatArgs {
     #[arg(long)]
     pub enc_key: Option<String>,
 
-    /// Rewind to a specific time
+    /// Read the version current at a past time (duration like 2d, or RFC 3339)
     #[arg(long)]
     pub rewind: Option<String>,
 
     /// Specific version ID to retrieve
-    #[arg(long)]
+    #[arg(long, conflicts_with = "rewind")]
     pub version_id: Option<String>,
 }
 
@@ -70,11 +70,48 @@ pub async fn execute(args: CatArgs, output_config: OutputConfig) -> ExitCode {
 
     let path = RemotePath::new(&alias_name, &bucket, &key);
 
-    // Get object content
-    match client.get_object(&path).await {
-        Ok(data) => {
+    // Resolve the version to read, if any
+    let at = match args
+        .rewind
+        .as_deref()
+        .map(|r| rewind::parse_rewind(r, jiff::Timestamp::now()))
+        .transpose()
+    {
+        Ok(at) => at,
+        Err(e) => {
+            formatter.error(&e.to_string());
+            return ExitCode::UsageError;
+        }
+    };
+
+    let version_id =
+        match rewind::select_version(&client, &path, args.version_id.as_deref(), at).await {
+            Ok(v) => v,
+            Err(e @ rc_core::Error::NotFound(_)) => {
+                formatter.error(&e.to_string());
+                return ExitCode::NotFound;
+            }
+            Err(e) => {
+                formatter.error(&format!("Failed to resolve version: {e}"));
+                return ExitCode::NetworkError;
+            }
+        };
+
+    // Stream object content
+    let result = match &version_id {
+        Some(v) => client.get_object_version_stream(&path, v).await,
+        None => client.get_object_stream(&path).await,
+    };
+
+    match result {
+        Ok(mut stream) => {
             // Write directly to stdout (not through formatter to preserve binary data)
-            if let Err(e) = io::stdout().write_all(&data) {
+            let mut stdout = tokio::io::stdout();
+            if let Err(e) = tokio::io::copy(&mut stream.body, &mut stdout).await {
+                formatter.error(&format!("Failed to stream object to stdout: {e}"));
+                return ExitCode::GeneralError;
+            }
+            if let Err(e) = stdout.flush().await {
                 formatter.error(&format!("Failed to write to stdout: {e}"));
                 return ExitCode::GeneralError;
             }
@@ -0,0 +1,125 @@
+//! Helpers shared by commands that act on every object under a prefix
+//!
+//! Commands such as `retention`, `legalhold` and `sql` resolve an alias to a
+//! client, check that the backend supports the feature they need, and then
+//! expand a prefix into the keys to operate on.
+
+use rc_core::{AliasManager, ListOptions, ObjectStore as _, RemotePath};
+use rc_s3::S3Client;
+
+use crate::exit_code::ExitCode;
+use crate::output::Formatter;
+
+/// Map a core error to the matching exit code
+pub(crate) fn exit_code_from_error(error: &rc_core::Error) -> ExitCode {
+    ExitCode::from_i32(error.exit_code()).unwrap_or(ExitCode::GeneralError)
+}
+
+/// Create a client for `alias_name` and check that `bucket` supports `feature`
+///
+/// `feature` is a name accepted by [`S3Client::probe_capability`] and
+/// `description` is how the feature is named in the error message. The probe
+/// is skipped when `force` is set.
+pub(crate) async fn feature_client(
+    alias_name: &str,
+    bucket: &str,
+    feature: &str,
+    description: &str,
+    force: bool,
+    formatter: &Formatter,
+) -> Result<S3Client, ExitCode> {
+    let alias_manager = match AliasManager::new() {
+        Ok(manager) => manager,
+        Err(error) => {
+            formatter.error(&format!("Failed to load aliases: {error}"));
+            return Err(ExitCode::GeneralError);
+        }
+    };
+
+    let alias = match alias_manager.get(alias_name) {
+        Ok(alias) => alias,
+        Err(_) => {
+            formatter.error(&format!("Alias '{alias_name}' not found"));
+            return Err(ExitCode::NotFound);
+        }
+    };
+
+    let client = match S3Client::new(alias).await {
+        Ok(client) => client,
+        Err(error) => {
+            formatter.error(&format!("Failed to create S3 client: {error}"));
+            return Err(ExitCode::NetworkError);
+        }
+    };
+
+    if !force {
+        match client.probe_capability(bucket, feature).await {
+            Ok(true) => {}
+            Ok(false) => {
+                formatter.error(&format!(
+                    "Backend does not support {description}. Use --force to attempt anyway."
+                ));
+                return Err(ExitCode::UnsupportedFeature);
+            }
+            Err(error) => {
+                formatter.error(&format!("Failed to detect capabilities: {error}"));
+                return Err(exit_code_from_error(&error));
+            }
+        }
+    }
+
+    Ok(client)
+}
+
+/// List the keys of every object under `prefix`, one page at a time
+///
+/// Warns when nothing matches, since callers then have no work to do.
+pub(crate) async fn list_object_keys(
+    client: &S3Client,
+    alias_name: &str,
+    bucket: &str,
+    prefix: &str,
+    formatter: &Formatter,
+) -> Result<Vec<String>, ExitCode> {
+    let path = RemotePath::new(alias_name, bucket, prefix);
+    let mut keys = Vec::new();
+    let mut continuation_token: Option<String> = None;
+
+    loop {
+        let options = ListOptions {
+            recursive: true,
+            max_keys: Some(1000),
+            continuation_token: continuation_token.take(),
+            ..Default::default()
+        };
+
+        match client.list_objects(&path, options).await {
+            Ok(result) => {
+                keys.extend(
+                    result
+                        .items
+                        .into_iter()
+                        .filter(|item| !item.is_dir)
+                        .map(|item| item.key),
+                );
+
+                if !result.truncated || result.continuation_token.is_none() {
+                    break;
+                }
+                continuation_token = result.continuation_token;
+            }
+            Err(error) => {
+                formatter.error(&format!("Failed to list objects: {error}"));
+                return Err(exit_code_from_error(&error));
+            }
+        }
+    }
+
+    if keys.is_empty() {
+        formatter.warning(&format!(
+            "No objects found matching prefix: {alias_name}/{bucket}/{prefix}"
+        ));
+    }
+
+    Ok(keys)
+}
@@ -0,0 +1,161 @@
+//! Config management commands
+//!
+//! Read and edit the default settings stored in config.toml. Defaults apply
+//! to every command unless overridden by an environment variable or flag.
+
+use clap::Subcommand;
+use serde::Serialize;
+
+use crate::exit_code::ExitCode;
+use crate::output::{Formatter, OutputConfig};
+use rc_core::{ConfigManager, Defaults};
+
+/// Config subcommands for managing default settings
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommands {
+    /// Show the value of a default setting
+    Get(GetArgs),
+
+    /// Change a default setting
+    Set(SetArgs),
+
+    /// List all default settings
+    List,
+}
+
+/// Arguments for the `config get` command
+#[derive(clap::Args, Debug)]
+pub struct GetArgs {
+    /// Setting name: output, color or progress
+    pub key: String,
+}
+
+/// Arguments for the `config set` command
+#[derive(clap::Args, Debug)]
+pub struct SetArgs {
+    /// Setting name: output, color or progress
+    pub key: String,
+
+    /// New value (output: human|json, color: auto|always|never, progress: true|false)
+    pub value: String,
+}
+
+/// JSON output for a single setting
+#[derive(Serialize)]
+struct SettingOutput {
+    key: String,
+    value: String,
+}
+
+/// JSON output for config list
+#[derive(Serialize)]
+struct ConfigListOutput {
+    defaults: Vec<SettingOutput>,
+}
+
+/// Execute a config subcommand
+pub async fn execute(cmd: ConfigCommands, output_config: OutputConfig) -> ExitCode {
+    let formatter = Formatter::new(output_config);
+    let manager = match ConfigManager::new() {
+        Ok(manager) => manager,
+        Err(e) => {
+            formatter.error(&format!("Failed to locate config: {e}"));
+            return ExitCode::GeneralError;
+        }
+    };
+
+    let mut config = match manager.load() {
+        Ok(config) => config,
+        Err(e) => {
+            formatter.error(&format!("Failed to load config: {e}"));
+            return ExitCode::GeneralError;
+        }
+    };
+
+    match cmd {
+        ConfigCommands::Get(args) => match config.defaults.get(&args.key) {
+            Ok(value) => {
+                if formatter.is_json() {
+                    formatter.json(&SettingOutput {
+                        key: args.key,
+                        value,
+                    });
+                } else {
+                    formatter.println(&value);
+                }
+                ExitCode::Success
+            }
+            Err(e) => {
+                formatter.error(&e.to_string());
+                ExitCode::UsageError
+            }
+        },
+        ConfigCommands::Set(args) => {
+            if let Err(e) = config.defaults.set(&args.key, &args.value) {
+                formatter.error(&e.to_string());
+                return ExitCode::UsageError;
+            }
+
+            if let Err(e) = manager.save(&config) {
+                formatter.error(&format!("Failed to save config: {e}"));
+                return ExitCode::GeneralError;
+            }
+
+            // Report the normalized value that was stored
+            let value = config.defaults.get(&args.key).unwrap_or(args.value);
+            if formatter.is_json() {
+                formatter.json(&SettingOutput {
+                    key: args.key,
+                    value,
+                });
+            } else {
+                let styled_key = formatter.style_name(&args.key);
+                formatter.success(&format!("Set {styled_key} = {value}"));
+            }
+            ExitCode::Success
+        }
+        ConfigCommands::List => {
+            let settings = settings(&config.defaults);
+            if formatter.is_json() {
+                formatter.json(&ConfigListOutput { defaults: settings });
+            } else {
+                for setting in settings {
+                    let styled_key = formatter.style_name(&format!("{:<10}", setting.key));
+                    formatter.println(&format!("{styled_key} {}", setting.value));
+                }
+            }
+            ExitCode::Success
+        }
+    }
+}
+
+/// All default settings in key order
+fn settings(defaults: &Defaults) -> Vec<SettingOutput> {
+    Defaults::KEYS
+        .iter()
+        .filter_map(|key| {
+            defaults.get(key).ok().map(|value| SettingOutput {
+                key: key.to_string(),
+                value,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_lists_every_key() {
+        let settings = settings(&Defaults::default());
+        let pairs: Vec<(&str, &str)> = settings
+            .iter()
+            .map(|s| (s.key.as_str(), s.value.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![("output", "human"), ("color", "auto"), ("progress", "true")]
+        );
+    }
+}
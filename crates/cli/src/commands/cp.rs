@@ -3,13 +3,16 @@
 //! Copies objects between local filesystem and S3, or between S3 locations.
 
 use clap::Args;
-use rc_core::{AliasManager, ObjectStore as _, ParsedPath, RemotePath, parse_path};
-use rc_s3::S3Client;
+use rc_core::{
+    AliasManager, ConfigManager, ObjectStore as _, ObjectStream, ParsedPath, RemotePath,
+    parse_path, rewind,
+};
+use rc_s3::{MultipartConfig, S3Client, download, multipart, transfer};
 use serde::Serialize;
 use std::path::Path;
 
 use crate::exit_code::ExitCode;
-use crate::output::{Formatter, OutputConfig};
+use crate::output::{Formatter, OutputConfig, ProgressBar};
 
 /// Copy objects
 #[derive(Args, Debug)]
@@ -47,6 +50,14 @@ pub struct CpArgs {
     /// Content type for uploaded files
     #[arg(long)]
     pub content_type: Option<String>,
+
+    /// Copy objects as they were at a past time (duration like 2d, or RFC 3339)
+    #[arg(long)]
+    pub rewind: Option<String>,
+
+    /// Copy a specific version of the source object
+    #[arg(long, conflicts_with_all = ["rewind", "recursive"])]
+    pub version_id: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -81,6 +92,25 @@ pub async fn execute(args: CpArgs, output_config: OutputConfig) -> ExitCode {
         }
     };
 
+    // Point-in-time copies read older versions of a remote source
+    let at = match args
+        .rewind
+        .as_deref()
+        .map(|r| rewind::parse_rewind(r, jiff::Timestamp::now()))
+        .transpose()
+    {
+        Ok(at) => at,
+        Err(e) => {
+            formatter.error(&e.to_string());
+            return ExitCode::UsageError;
+        }
+    };
+
+    if matches!(source, ParsedPath::Local(_)) && (at.is_some() || args.version_id.is_some()) {
+        formatter.error("--rewind and --version-id require a remote source");
+        return ExitCode::UsageError;
+    }
+
     // Determine copy direction
     match (&source, &target) {
         (ParsedPath::Local(src), ParsedPath::Remote(dst)) => {
@@ -89,11 +119,11 @@ pub async fn execute(args: CpArgs, output_config: OutputConfig) -> ExitCode {
         }
         (ParsedPath::Remote(src), ParsedPath::Local(dst)) => {
             // S3 to Local
-            copy_s3_to_local(src, dst, &args, &formatter).await
+            copy_s3_to_local(src, dst, at, &args, &formatter).await
         }
         (ParsedPath::Remote(src), ParsedPath::Remote(dst)) => {
             // S3 to S3
-            copy_s3_to_s3(src, dst, &args, &formatter).await
+            copy_s3_to_s3(src, dst, at, &args, &formatter).await
         }
         (ParsedPath::Local(_), ParsedPath::Local(_)) => {
             formatter.error("Cannot copy between two local paths. Use system cp command.");
@@ -181,25 +211,57 @@ async fn upload_file(
         return ExitCode::Success;
     }
 
-    // Read file content
-    let data = match std::fs::read(src) {
-        Ok(d) => d,
+    // Stat the file; its content is streamed from disk during upload
+    let size = match std::fs::metadata(src) {
+        Ok(m) => m.len() as i64,
         Err(e) => {
             formatter.error(&format!("Failed to read {src_display}: {e}"));
             return ExitCode::GeneralError;
         }
     };
 
-    let size = data.len() as i64;
-
     // Determine content type
     let guessed_type: Option<String> = mime_guess::from_path(src)
         .first()
         .map(|m| m.essence_str().to_string());
     let content_type = args.content_type.as_deref().or(guessed_type.as_deref());
 
-    // Upload
-    match client.put_object(&target, data, content_type).await {
+    // Upload, switching to concurrent multipart for large files
+    let mut multipart_config = MultipartConfig::default();
+    if let Ok(manager) = ConfigManager::new() {
+        multipart_config = multipart_config.state_dir(manager.state_dir());
+    }
+
+    let result = if multipart_config.use_multipart(size as u64) {
+        let progress = ProgressBar::new(formatter.config().clone(), size as u64);
+        let on_part = |part_len| progress.inc(part_len);
+        let upload = multipart::upload_file(
+            client,
+            &target,
+            src,
+            content_type,
+            &multipart_config,
+            &on_part,
+        );
+
+        // Completed parts are persisted, so an interrupted upload resumes on the next run
+        let result = tokio::select! {
+            result = upload => result,
+            _ = tokio::signal::ctrl_c() => {
+                progress.finish_and_clear();
+                formatter.warning(&format!(
+                    "Upload of {src_display} interrupted. Run the same command again to resume."
+                ));
+                return ExitCode::Interrupted;
+            }
+        };
+        progress.finish_and_clear();
+        result
+    } else {
+        client.put_object_file(&target, src, content_type).await
+    };
+
+    match result {
         Ok(info) => {
             if formatter.is_json() {
                 let output = CpOutput {
@@ -232,28 +294,10 @@ async fn upload_directory(
     args: &CpArgs,
     formatter: &Formatter,
 ) -> ExitCode {
-    use std::fs;
-
     let mut success_count = 0;
     let mut error_count = 0;
 
     // Walk directory
-    fn walk_dir(dir: &Path, base: &Path) -> std::io::Result<Vec<(std::path::PathBuf, String)>> {
-        let mut files = Vec::new();
-        for entry in fs::read_dir(dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.is_file() {
-                let relative = path.strip_prefix(base).unwrap_or(&path);
-                let relative_str = relative.to_string_lossy().to_string();
-                files.push((path, relative_str));
-            } else if path.is_dir() {
-                files.extend(walk_dir(&path, base)?);
-            }
-        }
-        Ok(files)
-    }
-
     let files = match walk_dir(src, src) {
         Ok(f) => f,
         Err(e) => {
@@ -299,9 +343,30 @@ async fn upload_directory(
     }
 }
 
+/// Recursively list files under `dir` with their paths relative to `base`
+pub(crate) fn walk_dir(
+    dir: &Path,
+    base: &Path,
+) -> std::io::Result<Vec<(std::path::PathBuf, String)>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_file() {
+            let relative = path.strip_prefix(base).unwrap_or(&path);
+            let relative_str = relative.to_string_lossy().to_string();
+            files.push((path, relative_str));
+        } else if path.is_dir() {
+            files.extend(walk_dir(&path, base)?);
+        }
+    }
+    Ok(files)
+}
+
 async fn copy_s3_to_local(
     src: &RemotePath,
     dst: &Path,
+    at: Option<jiff::Timestamp>,
     args: &CpArgs,
     formatter: &Formatter,
 ) -> ExitCode {
@@ -335,10 +400,35 @@ async fn copy_s3_to_local(
 
     if is_prefix || args.recursive {
         // Download multiple objects
-        download_prefix(&client, src, dst, args, formatter).await
+        download_prefix(&client, src, dst, at, args, formatter).await
     } else {
         // Download single object
-        download_file(&client, src, dst, args, formatter).await
+        let version_id = match select_version(&client, src, at, args, formatter).await {
+            Ok(v) => v,
+            Err(code) => return code,
+        };
+        download_file(&client, src, dst, version_id.as_deref(), args, formatter).await
+    }
+}
+
+/// Resolve the source version selected by `--version-id` or `--rewind`
+async fn select_version(
+    client: &S3Client,
+    src: &RemotePath,
+    at: Option<jiff::Timestamp>,
+    args: &CpArgs,
+    formatter: &Formatter,
+) -> Result<Option<String>, ExitCode> {
+    match rewind::select_version(client, src, args.version_id.as_deref(), at).await {
+        Ok(v) => Ok(v),
+        Err(e @ rc_core::Error::NotFound(_)) => {
+            formatter.error(&e.to_string());
+            Err(ExitCode::NotFound)
+        }
+        Err(e) => {
+            formatter.error(&format!("Failed to resolve version: {e}"));
+            Err(ExitCode::NetworkError)
+        }
     }
 }
 
@@ -346,6 +436,7 @@ async fn download_file(
     client: &S3Client,
     src: &RemotePath,
     dst: &Path,
+    version_id: Option<&str>,
     args: &CpArgs,
     formatter: &Formatter,
 ) -> ExitCode {
@@ -385,15 +476,50 @@ async fn download_file(
         return ExitCode::GeneralError;
     }
 
-    // Download object
-    match client.get_object(src).await {
-        Ok(data) => {
-            let size = data.len() as i64;
-
-            if let Err(e) = std::fs::write(&dst_path, &data) {
-                formatter.error(&format!("Failed to write {dst_display}: {e}"));
-                return ExitCode::GeneralError;
+    // Download object, splitting large objects into concurrent ranged GETs
+    let multipart_config = MultipartConfig::default();
+    let result = match version_id {
+        // Ranged GETs always target the current version, so older versions are streamed whole
+        Some(version_id) => {
+            let stream = client.get_object_version_stream(src, version_id).await;
+            save_stream(stream, &dst_path).await
+        }
+        None => match client.head_object(src).await {
+            Ok(info) if multipart_config.use_multipart(info.size_bytes.unwrap_or(0) as u64) => {
+                let total = info.size_bytes.unwrap_or(0) as u64;
+                let progress = ProgressBar::new(formatter.config().clone(), total);
+                let on_part = |part_len| progress.inc(part_len);
+                let download = download::download_file(
+                    client,
+                    src,
+                    &info,
+                    &dst_path,
+                    &multipart_config,
+                    &on_part,
+                );
+
+                // Completed ranges are recorded, so an interrupted download resumes on the next run
+                let result = tokio::select! {
+                    result = download => result,
+                    _ = tokio::signal::ctrl_c() => {
+                        progress.finish_and_clear();
+                        formatter.warning(&format!(
+                            "Download of {src_display} interrupted. Run the same command again to resume."
+                        ));
+                        return ExitCode::Interrupted;
+                    }
+                };
+                progress.finish_and_clear();
+                result
             }
+            Ok(_) => save_stream(client.get_object_stream(src).await, &dst_path).await,
+            Err(e) => Err(e),
+        },
+    };
+
+    match result {
+        Ok(size) => {
+            let size = size as i64;
 
             if formatter.is_json() {
                 let output = CpOutput {
@@ -413,6 +539,10 @@ async fn download_file(
             }
             ExitCode::Success
         }
+        Err(e @ (rc_core::Error::Io(_) | rc_core::Error::General(_))) => {
+            formatter.error(&format!("Failed to download {src_display}: {e}"));
+            ExitCode::GeneralError
+        }
         Err(e) => {
             let err_str = e.to_string();
             if err_str.contains("NotFound") || err_str.contains("NoSuchKey") {
@@ -426,61 +556,68 @@ async fn download_file(
     }
 }
 
+/// Save a fetched object stream to a local file, returning the number of bytes written
+async fn save_stream(stream: rc_core::Result<ObjectStream>, path: &Path) -> rc_core::Result<u64> {
+    let mut stream = stream?;
+    write_stream_to_file(&mut stream.body, path)
+        .await
+        .map_err(|e| rc_core::Error::General(format!("Failed to write {}: {e}", path.display())))
+}
+
+/// Stream an object body into a local file, returning the number of bytes written
+pub(crate) async fn write_stream_to_file(
+    body: &mut rc_core::ByteReader,
+    path: &Path,
+) -> std::io::Result<u64> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut file = tokio::fs::File::create(path).await?;
+    let written = tokio::io::copy(body, &mut file).await?;
+    file.flush().await?;
+    Ok(written)
+}
+
 async fn download_prefix(
     client: &S3Client,
     src: &RemotePath,
     dst: &Path,
+    at: Option<jiff::Timestamp>,
     args: &CpArgs,
     formatter: &Formatter,
 ) -> ExitCode {
-    use rc_core::ListOptions;
+    let entries = match list_download_entries(client, src, at).await {
+        Ok(entries) => entries,
+        Err(e) => {
+            formatter.error(&format!("Failed to list objects: {e}"));
+            return ExitCode::NetworkError;
+        }
+    };
 
     let mut success_count = 0;
     let mut error_count = 0;
-    let mut continuation_token: Option<String> = None;
 
-    loop {
-        let options = ListOptions {
-            recursive: true,
-            max_keys: Some(1000),
-            continuation_token: continuation_token.clone(),
-            ..Default::default()
-        };
-
-        match client.list_objects(src, options).await {
-            Ok(result) => {
-                for item in result.items {
-                    if item.is_dir {
-                        continue;
-                    }
+    for (key, version_id) in entries {
+        // Calculate relative path from prefix
+        let relative_key = key.strip_prefix(&src.key).unwrap_or(&key);
+        let dst_path = dst.join(relative_key.replace('/', std::path::MAIN_SEPARATOR_STR));
+
+        let obj_src = RemotePath::new(&src.alias, &src.bucket, &key);
+        let result = download_file(
+            client,
+            &obj_src,
+            &dst_path,
+            version_id.as_deref(),
+            args,
+            formatter,
+        )
+        .await;
 
-                    // Calculate relative path from prefix
-                    let relative_key = item.key.strip_prefix(&src.key).unwrap_or(&item.key);
-                    let dst_path =
-                        dst.join(relative_key.replace('/', std::path::MAIN_SEPARATOR_STR));
-
-                    let obj_src = RemotePath::new(&src.alias, &src.bucket, &item.key);
-                    let result = download_file(client, &obj_src, &dst_path, args, formatter).await;
-
-                    if result == ExitCode::Success {
-                        success_count += 1;
-                    } else {
-                        error_count += 1;
-                        if !args.continue_on_error {
-                            return result;
-                        }
-                    }
-                }
-
-                if result.truncated {
-                    continuation_token = result.continuation_token;
-                } else {
-                    break;
-                }
-            }
-            Err(e) => {
-                formatter.error(&format!("Failed to list objects: {e}"));
-                return ExitCode::NetworkError;
+        if result == ExitCode::Success {
+            success_count += 1;
+        } else {
+            error_count += 1;
+            if !args.continue_on_error {
+                return result;
             }
         }
     }
@@ -501,9 +638,59 @@ async fn download_prefix(
     }
 }
 
+/// List the keys to download under `src`, with the version to fetch for each
+///
+/// With a rewind time, each key maps to the version that was current then;
+/// otherwise the current objects are listed.
+async fn list_download_entries(
+    client: &S3Client,
+    src: &RemotePath,
+    at: Option<jiff::Timestamp>,
+) -> rc_core::Result<Vec<(String, Option<String>)>> {
+    use rc_core::ListOptions;
+
+    if let Some(at) = at {
+        let versions = rewind::resolve_versions(client, src, at).await?;
+        return Ok(versions
+            .into_iter()
+            .map(|v| (v.key, Some(v.version_id)))
+            .collect());
+    }
+
+    let mut entries = Vec::new();
+    let mut continuation_token: Option<String> = None;
+
+    loop {
+        let options = ListOptions {
+            recursive: true,
+            max_keys: Some(1000),
+            continuation_token: continuation_token.take(),
+            ..Default::default()
+        };
+
+        let result = client.list_objects(src, options).await?;
+        entries.extend(
+            result
+                .items
+                .into_iter()
+                .filter(|item| !item.is_dir)
+                .map(|item| (item.key, None)),
+        );
+
+        if result.truncated {
+            continuation_token = result.continuation_token;
+        } else {
+            break;
+        }
+    }
+
+    Ok(entries)
+}
+
 async fn copy_s3_to_s3(
     src: &RemotePath,
     dst: &RemotePath,
+    at: Option<jiff::Timestamp>,
     args: &CpArgs,
     formatter: &Formatter,
 ) -> ExitCode {
@@ -516,12 +703,6 @@ async fn copy_s3_to_s3(
         }
     };
 
-    // For now, only support same-alias copies (server-side copy)
-    if src.alias != dst.alias {
-        formatter.error("Cross-alias S3-to-S3 copy not yet supported. Use download + upload.");
-        return ExitCode::UnsupportedFeature;
-    }
-
     let alias = match alias_manager.get(&src.alias) {
         Ok(a) => a,
         Err(_) => {
@@ -538,6 +719,27 @@ async fn copy_s3_to_s3(
         }
     };
 
+    // Different aliases need a separate destination client; data is streamed between them
+    let dst_client = if src.alias == dst.alias {
+        None
+    } else {
+        let alias = match alias_manager.get(&dst.alias) {
+            Ok(a) => a,
+            Err(_) => {
+                formatter.error(&format!("Alias '{}' not found", dst.alias));
+                return ExitCode::NotFound;
+            }
+        };
+
+        match S3Client::new(alias).await {
+            Ok(c) => Some(c),
+            Err(e) => {
+                formatter.error(&format!("Failed to create S3 client: {e}"));
+                return ExitCode::NetworkError;
+            }
+        }
+    };
+
     let src_display = format!("{}/{}/{}", src.alias, src.bucket, src.key);
     let dst_display = format!("{}/{}/{}", dst.alias, dst.bucket, dst.key);
 
@@ -548,7 +750,28 @@ async fn copy_s3_to_s3(
         return ExitCode::Success;
     }
 
-    match client.copy_object(src, dst).await {
+    let version_id = match select_version(&client, src, at, args, formatter).await {
+        Ok(v) => v,
+        Err(code) => return code,
+    };
+
+    let result = match (&dst_client, version_id) {
+        (None, Some(version_id)) => client.copy_object_version(src, &version_id, dst).await,
+        (None, None) => client.copy_object(src, dst).await,
+        (Some(dst_client), version_id) => {
+            copy_across_clients(
+                &client,
+                src,
+                version_id.as_deref(),
+                dst_client,
+                dst,
+                formatter,
+            )
+            .await
+        }
+    };
+
+    match result {
         Ok(info) => {
             if formatter.is_json() {
                 let output = CpOutput {
@@ -580,6 +803,36 @@ async fn copy_s3_to_s3(
     }
 }
 
+/// Copy an object, or one of its versions, between two clients by streaming
+/// it through this process
+pub(crate) async fn copy_across_clients(
+    src_client: &S3Client,
+    src: &RemotePath,
+    version_id: Option<&str>,
+    dst_client: &S3Client,
+    dst: &RemotePath,
+    formatter: &Formatter,
+) -> rc_core::Result<rc_core::ObjectInfo> {
+    let (info, attributes) = src_client
+        .head_object_with_attributes(src, version_id)
+        .await?;
+    let size = info.size_bytes.unwrap_or(0).max(0) as u64;
+    let source = transfer::CopySource {
+        client: src_client,
+        path: src,
+        version_id,
+        info: &info,
+        attributes: &attributes,
+    };
+
+    let multipart_config = MultipartConfig::default();
+    let progress = ProgressBar::new(formatter.config().clone(), size);
+    let on_part = |part_len| progress.inc(part_len);
+    let result = transfer::copy_object(&source, dst_client, dst, &multipart_config, &on_part).await;
+    progress.finish_and_clear();
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -651,6 +904,8 @@ mod tests {
             dry_run: false,
             storage_class: None,
             content_type: None,
+            rewind: None,
+            version_id: None,
         };
         assert!(args.overwrite);
         assert!(!args.recursive);
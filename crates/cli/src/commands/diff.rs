@@ -3,12 +3,14 @@
 //! Shows differences between two S3 paths or between local and remote.
 
 use clap::Args;
-use rc_core::{AliasManager, ListOptions, ObjectStore as _, ParsedPath, RemotePath, parse_path};
-use rc_s3::S3Client;
+use rc_core::{AliasManager, ParsedPath, parse_path};
+use rc_s3::{S3Client, etag};
 use serde::Serialize;
 use std::collections::HashMap;
-use std::path::Path;
 
+use crate::commands::mirror::{
+    FileInfo, apply_local_checksums, is_newer, list_local_map, list_objects_map,
+};
 use crate::exit_code::ExitCode;
 use crate::output::{Formatter, OutputConfig};
 
@@ -28,6 +30,10 @@ pub struct DiffArgs {
     /// Show only differences (default: show all)
     #[arg(long)]
     pub diff_only: bool,
+
+    /// Compare content checksums (MD5 / multipart ETag) instead of size only
+    #[arg(long)]
+    pub checksum: bool,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -70,11 +76,11 @@ struct DiffSummary {
     total: usize,
 }
 
-#[derive(Debug, Clone)]
-struct FileInfo {
-    size: Option<i64>,
-    modified: Option<String>,
-    etag: Option<String>,
+/// Whether each side of a comparison is a local path
+#[derive(Debug, Clone, Copy, Default)]
+struct LocalSides {
+    first: bool,
+    second: bool,
 }
 
 /// Execute the diff command
@@ -82,16 +88,8 @@ pub async fn execute(args: DiffArgs, output_config: OutputConfig) -> ExitCode {
     let formatter = Formatter::new(output_config);
 
     // Parse both paths
-    let first_parsed = parse_path(&args.first);
-    let second_parsed = parse_path(&args.second);
-
-    // Both must be remote for now (local support can be added later)
-    let (first_path, second_path) = match (&first_parsed, &second_parsed) {
-        (Ok(ParsedPath::Remote(f)), Ok(ParsedPath::Remote(s))) => (f.clone(), s.clone()),
-        (Ok(ParsedPath::Local(_)), _) | (_, Ok(ParsedPath::Local(_))) => {
-            formatter.error("Local paths are not yet supported in diff command");
-            return ExitCode::UsageError;
-        }
+    let (first_parsed, second_parsed) = match (parse_path(&args.first), parse_path(&args.second)) {
+        (Ok(f), Ok(s)) => (f, s),
         (Err(e), _) => {
             formatter.error(&format!("Invalid first path: {e}"));
             return ExitCode::UsageError;
@@ -102,68 +100,71 @@ pub async fn execute(args: DiffArgs, output_config: OutputConfig) -> ExitCode {
         }
     };
 
-    // Load aliases
-    let alias_manager = match AliasManager::new() {
-        Ok(am) => am,
-        Err(e) => {
-            formatter.error(&format!("Failed to load aliases: {e}"));
-            return ExitCode::GeneralError;
-        }
-    };
-
-    // Create clients for both paths
-    let first_alias = match alias_manager.get(&first_path.alias) {
-        Ok(a) => a,
-        Err(_) => {
-            formatter.error(&format!("Alias '{}' not found", first_path.alias));
-            return ExitCode::NotFound;
-        }
-    };
-
-    let second_alias = match alias_manager.get(&second_path.alias) {
-        Ok(a) => a,
-        Err(_) => {
-            formatter.error(&format!("Alias '{}' not found", second_path.alias));
-            return ExitCode::NotFound;
-        }
-    };
-
-    let first_client = match S3Client::new(first_alias).await {
-        Ok(c) => c,
-        Err(e) => {
-            formatter.error(&format!("Failed to create client for first path: {e}"));
-            return ExitCode::NetworkError;
-        }
-    };
-
-    let second_client = match S3Client::new(second_alias).await {
-        Ok(c) => c,
-        Err(e) => {
-            formatter.error(&format!("Failed to create client for second path: {e}"));
-            return ExitCode::NetworkError;
+    // Aliases are only needed when at least one side is remote
+    let needs_aliases = matches!(first_parsed, ParsedPath::Remote(_))
+        || matches!(second_parsed, ParsedPath::Remote(_));
+    let alias_manager = if needs_aliases {
+        match AliasManager::new() {
+            Ok(am) => Some(am),
+            Err(e) => {
+                formatter.error(&format!("Failed to load aliases: {e}"));
+                return ExitCode::GeneralError;
+            }
         }
+    } else {
+        None
     };
 
     // List objects from both paths
-    let first_objects = match list_objects_map(&first_client, &first_path, args.recursive).await {
+    let mut first_objects = match list_side(
+        &first_parsed,
+        alias_manager.as_ref(),
+        args.recursive,
+        "first",
+        &formatter,
+    )
+    .await
+    {
         Ok(o) => o,
-        Err(e) => {
-            formatter.error(&format!("Failed to list first path: {e}"));
-            return ExitCode::NetworkError;
-        }
+        Err(code) => return code,
     };
 
-    let second_objects = match list_objects_map(&second_client, &second_path, args.recursive).await
+    let mut second_objects = match list_side(
+        &second_parsed,
+        alias_manager.as_ref(),
+        args.recursive,
+        "second",
+        &formatter,
+    )
+    .await
     {
         Ok(o) => o,
-        Err(e) => {
-            formatter.error(&format!("Failed to list second path: {e}"));
-            return ExitCode::NetworkError;
-        }
+        Err(code) => return code,
     };
 
+    // Hash local files in the form of the other side's ETags
+    if args.checksum {
+        let mut result = Ok(());
+        if let ParsedPath::Local(root) = &first_parsed {
+            result = apply_local_checksums(root, &mut first_objects, &second_objects).await;
+        }
+        if result.is_ok()
+            && let ParsedPath::Local(root) = &second_parsed
+        {
+            result = apply_local_checksums(root, &mut second_objects, &first_objects).await;
+        }
+        if let Err(e) = result {
+            formatter.error(&format!("Failed to compute checksums: {e}"));
+            return ExitCode::GeneralError;
+        }
+    }
+
     // Compare objects
-    let entries = compare_objects(&first_objects, &second_objects, args.diff_only);
+    let sides = LocalSides {
+        first: matches!(first_parsed, ParsedPath::Local(_)),
+        second: matches!(second_parsed, ParsedPath::Local(_)),
+    };
+    let entries = compare_objects(&first_objects, &second_objects, sides, args.diff_only);
 
     // Calculate summary
     let mut summary = DiffSummary {
@@ -235,73 +236,85 @@ pub async fn execute(args: DiffArgs, output_config: OutputConfig) -> ExitCode {
     }
 }
 
-async fn list_objects_map(
-    client: &S3Client,
-    path: &RemotePath,
+/// List one side of the comparison, keyed by relative path
+async fn list_side(
+    parsed: &ParsedPath,
+    alias_manager: Option<&AliasManager>,
     recursive: bool,
-) -> Result<HashMap<String, FileInfo>, rc_core::Error> {
-    let mut objects = HashMap::new();
-    let mut continuation_token: Option<String> = None;
-    let base_prefix = &path.key;
-
-    loop {
-        let options = ListOptions {
-            recursive,
-            max_keys: Some(1000),
-            continuation_token: continuation_token.clone(),
-            ..Default::default()
-        };
-
-        let result = client.list_objects(path, options).await?;
-
-        for item in result.items {
-            if item.is_dir {
-                continue;
+    side: &str,
+    formatter: &Formatter,
+) -> Result<HashMap<String, FileInfo>, ExitCode> {
+    let path = match parsed {
+        ParsedPath::Local(path) => {
+            if !path.exists() {
+                formatter.error(&format!("Path not found: {}", path.display()));
+                return Err(ExitCode::NotFound);
             }
+            return list_local_map(path, recursive).map_err(|e| {
+                formatter.error(&format!("Failed to list {side} path: {e}"));
+                ExitCode::GeneralError
+            });
+        }
+        ParsedPath::Remote(path) => path,
+    };
 
-            // Get relative key (remove base prefix)
-            let relative_key = item.key.strip_prefix(base_prefix).unwrap_or(&item.key);
-            let relative_key = relative_key.trim_start_matches('/').to_string();
-
-            if relative_key.is_empty() {
-                // Single object case
-                let filename = Path::new(&item.key)
-                    .file_name()
-                    .map(|s| s.to_string_lossy().to_string())
-                    .unwrap_or(item.key.clone());
-                objects.insert(
-                    filename,
-                    FileInfo {
-                        size: item.size_bytes,
-                        modified: item.last_modified.map(|t| t.to_string()),
-                        etag: item.etag,
-                    },
-                );
-            } else {
-                objects.insert(
-                    relative_key,
-                    FileInfo {
-                        size: item.size_bytes,
-                        modified: item.last_modified.map(|t| t.to_string()),
-                        etag: item.etag,
-                    },
-                );
-            }
+    let Some(alias_manager) = alias_manager else {
+        formatter.error("Failed to load aliases");
+        return Err(ExitCode::GeneralError);
+    };
+
+    let alias = match alias_manager.get(&path.alias) {
+        Ok(a) => a,
+        Err(_) => {
+            formatter.error(&format!("Alias '{}' not found", path.alias));
+            return Err(ExitCode::NotFound);
         }
+    };
 
-        if result.truncated {
-            continuation_token = result.continuation_token;
-        } else {
-            break;
+    let client = match S3Client::new(alias).await {
+        Ok(c) => c,
+        Err(e) => {
+            formatter.error(&format!("Failed to create client for {side} path: {e}"));
+            return Err(ExitCode::NetworkError);
         }
+    };
+
+    list_objects_map(&client, path, recursive)
+        .await
+        .map_err(|e| {
+            formatter.error(&format!("Failed to list {side} path: {e}"));
+            ExitCode::NetworkError
+        })
+}
+
+/// Whether a pair of entries present on both sides have the same content
+///
+/// Sizes must match. When both sides carry an ETag (remote objects, or local
+/// files hashed with `--checksum`) the ETags decide. Otherwise a local file
+/// counts as changed when it was modified after the entry it is compared
+/// with, and a remote object without an ETag only matches another one
+/// without an ETag.
+fn is_same(first: &FileInfo, second: &FileInfo, sides: LocalSides) -> bool {
+    if first.size != second.size {
+        return false;
     }
 
-    Ok(objects)
+    match (&first.etag, &second.etag) {
+        (Some(first_etag), Some(second_etag)) => etag::etags_match(first_etag, second_etag),
+        _ if sides.first || sides.second => {
+            let first_changed = sides.first && is_newer(first.modified, second.modified);
+            let second_changed = sides.second && is_newer(second.modified, first.modified);
+            !first_changed && !second_changed
+        }
+        (Some(_), None) => false,
+        (None, _) => true,
+    }
 }
 
 fn compare_objects(
     first: &HashMap<String, FileInfo>,
     second: &HashMap<String, FileInfo>,
+    sides: LocalSides,
     diff_only: bool,
 ) -> Vec<DiffEntry> {
     let mut entries = Vec::new();
@@ -309,11 +322,7 @@ fn compare_objects(
     // Check objects in first
     for (key, first_info) in first {
         if let Some(second_info) = second.get(key) {
-            // Object exists in both
-            let is_same = first_info.size == second_info.size
-                && (first_info.etag == second_info.etag || first_info.etag.is_none());
-
-            let status = if is_same {
+            let status = if is_same(first_info, second_info, sides) {
                 DiffStatus::Same
             } else {
                 DiffStatus::Different
@@ -325,8 +334,8 @@ fn compare_objects(
                     status,
                     first_size: first_info.size,
                     second_size: second_info.size,
-                    first_modified: first_info.modified.clone(),
-                    second_modified: second_info.modified.clone(),
+                    first_modified: first_info.modified.map(|t| t.to_string()),
+                    second_modified: second_info.modified.map(|t| t.to_string()),
                 });
             }
         } else {
@@ -336,7 +345,7 @@ fn compare_objects(
                 status: DiffStatus::OnlyFirst,
                 first_size: first_info.size,
                 second_size: None,
-                first_modified: first_info.modified.clone(),
+                first_modified: first_info.modified.map(|t| t.to_string()),
                 second_modified: None,
             });
         }
@@ -351,7 +360,7 @@ fn compare_objects(
                 first_size: None,
                 second_size: second_info.size,
                 first_modified: None,
-                second_modified: second_info.modified.clone(),
+                second_modified: second_info.modified.map(|t| t.to_string()),
             });
         }
     }
@@ -391,7 +400,7 @@ mod tests {
             },
         );
 
-        let entries = compare_objects(&first, &second, false);
+        let entries = compare_objects(&first, &second, LocalSides::default(), false);
         assert_eq!(entries.len(), 1);
         assert_eq!(entries[0].status, DiffStatus::Same);
     }
@@ -418,7 +427,7 @@ mod tests {
             },
         );
 
-        let entries = compare_objects(&first, &second, false);
+        let entries = compare_objects(&first, &second, LocalSides::default(), false);
         assert_eq!(entries.len(), 1);
         assert_eq!(entries[0].status, DiffStatus::Different);
     }
@@ -437,7 +446,7 @@ mod tests {
 
         let second = HashMap::new();
 
-        let entries = compare_objects(&first, &second, false);
+        let entries = compare_objects(&first, &second, LocalSides::default(), false);
         assert_eq!(entries.len(), 1);
         assert_eq!(entries[0].status, DiffStatus::OnlyFirst);
     }
@@ -456,8 +465,138 @@ mod tests {
             },
         );
 
-        let entries = compare_objects(&first, &second, false);
+        let entries = compare_objects(&first, &second, LocalSides::default(), false);
         assert_eq!(entries.len(), 1);
         assert_eq!(entries[0].status, DiffStatus::OnlySecond);
     }
+
+    fn info(size: i64, modified: &str, etag: Option<&str>) -> FileInfo {
+        FileInfo {
+            size: Some(size),
+            modified: Some(modified.parse().unwrap()),
+            etag: etag.map(str::to_string),
+        }
+    }
+
+    fn status(first: FileInfo, second: FileInfo, sides: LocalSides) -> DiffStatus {
+        let first = HashMap::from([("file.txt".to_string(), first)]);
+        let second = HashMap::from([("file.txt".to_string(), second)]);
+        compare_objects(&first, &second, sides, false)[0]
+            .status
+            .clone()
+    }
+
+    #[test]
+    fn test_compare_objects_remote_vs_local() {
+        let remote_first = LocalSides {
+            first: false,
+            second: true,
+        };
+        let remote = info(100, "2026-01-02T00:00:00Z", Some("abc123"));
+
+        // Unchanged since upload, or downloaded with the object's mtime
+        let entries = compare_objects(
+            &HashMap::from([("file.txt".to_string(), remote.clone())]),
+            &HashMap::from([(
+                "file.txt".to_string(),
+                info(100, "2026-01-01T00:00:00Z", None),
+            )]),
+            remote_first,
+            false,
+        );
+        assert_eq!(entries[0].status, DiffStatus::Same);
+        assert!(entries[0].first_modified.is_some());
+        assert!(entries[0].second_modified.is_some());
+        assert_eq!(
+            status(
+                remote.clone(),
+                info(100, "2026-01-02T00:00:00Z", None),
+                remote_first
+            ),
+            DiffStatus::Same
+        );
+
+        // Edited locally after the upload, without changing size
+        assert_eq!(
+            status(
+                remote.clone(),
+                info(100, "2026-01-03T00:00:00Z", None),
+                remote_first
+            ),
+            DiffStatus::Different
+        );
+
+        // Same rule with the local path first
+        let local_first = LocalSides {
+            first: true,
+            second: false,
+        };
+        assert_eq!(
+            status(info(100, "2026-01-03T00:00:00Z", None), remote, local_first),
+            DiffStatus::Different
+        );
+    }
+
+    #[test]
+    fn test_compare_objects_remote_missing_etag() {
+        let remote = LocalSides::default();
+        let at = "2026-01-01T00:00:00Z";
+        assert_eq!(
+            status(info(100, at, Some("abc")), info(100, at, None), remote),
+            DiffStatus::Different
+        );
+        assert_eq!(
+            status(info(100, at, None), info(100, at, Some("abc")), remote),
+            DiffStatus::Same
+        );
+        assert_eq!(
+            status(info(100, at, None), info(100, at, None), remote),
+            DiffStatus::Same
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_local_checksums() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("same.txt"), b"hello").unwrap();
+        std::fs::write(dir.path().join("changed.txt"), b"hellO").unwrap();
+
+        let mut local = list_local_map(dir.path(), true).unwrap();
+        let mut remote = HashMap::new();
+        for key in ["same.txt", "changed.txt"] {
+            remote.insert(
+                key.to_string(),
+                FileInfo {
+                    size: Some(5),
+                    modified: None,
+                    etag: Some("5d41402abc4b2a76b9719d911017c592".to_string()),
+                },
+            );
+        }
+
+        apply_local_checksums(dir.path(), &mut local, &remote)
+            .await
+            .unwrap();
+
+        let sides = LocalSides {
+            first: true,
+            second: false,
+        };
+        let entries = compare_objects(&local, &remote, sides, false);
+        let same = entries.iter().find(|e| e.key == "same.txt").unwrap();
+        assert_eq!(same.status, DiffStatus::Same);
+        let changed = entries.iter().find(|e| e.key == "changed.txt").unwrap();
+        assert_eq!(changed.status, DiffStatus::Different);
+
+        // A single file is hashed from the root path itself
+        let file = dir.path().join("same.txt");
+        let mut single = list_local_map(&file, false).unwrap();
+        apply_local_checksums(&file, &mut single, &remote)
+            .await
+            .unwrap();
+        assert_eq!(
+            single["same.txt"].etag.as_deref(),
+            Some("5d41402abc4b2a76b9719d911017c592")
+        );
+    }
 }
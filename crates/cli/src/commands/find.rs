@@ -3,7 +3,7 @@
 //! Searches for objects matching specified patterns and filters.
 
 use clap::Args;
-use rc_core::{AliasManager, ListOptions, ObjectStore as _, RemotePath};
+use rc_core::{AliasManager, ListOptions, ObjectStore as _, RemotePath, rewind};
 use rc_s3::S3Client;
 use serde::Serialize;
 
@@ -205,12 +205,12 @@ fn build_filters(args: &FindArgs) -> Result<FindFilters, String> {
     let newer_than = args
         .newer
         .as_ref()
-        .map(|d| parse_duration_ago(d, now))
+        .map(|d| rewind::parse_duration_ago(d, now).map_err(|e| e.to_string()))
         .transpose()?;
     let older_than = args
         .older
         .as_ref()
-        .map(|d| parse_duration_ago(d, now))
+        .map(|d| rewind::parse_duration_ago(d, now).map_err(|e| e.to_string()))
         .transpose()?;
 
     Ok(FindFilters {
@@ -251,38 +251,6 @@ fn parse_size(s: &str) -> Result<i64, String> {
     Ok(num * multiplier)
 }
 
-/// Parse duration string and return timestamp that far in the past
-fn parse_duration_ago(s: &str, now: jiff::Timestamp) -> Result<jiff::Timestamp, String> {
-    let s = s.trim();
-    if s.is_empty() {
-        return Err("Duration cannot be empty".to_string());
-    }
-
-    let (num_str, suffix) = if s.ends_with(|c: char| c.is_ascii_alphabetic()) {
-        let idx = s.len() - 1;
-        (&s[..idx], &s[idx..])
-    } else {
-        (s, "s") // Default to seconds
-    };
-
-    let num: i64 = num_str
-        .parse()
-        .map_err(|_| format!("Invalid duration number: {num_str}"))?;
-
-    let seconds = match suffix.to_lowercase().as_str() {
-        "s" => num,
-        "m" => num * 60,
-        "h" => num * 3600,
-        "d" => num * 86400,
-        "w" => num * 604800,
-        _ => return Err(format!("Unknown duration suffix: {suffix}")),
-    };
-
-    let duration = jiff::Span::new().seconds(seconds);
-    now.checked_sub(duration)
-        .map_err(|e| format!("Duration overflow: {e}"))
-}
-
 async fn find_objects(
     client: &S3Client,
     path: &RemotePath,
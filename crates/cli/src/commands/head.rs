@@ -5,7 +5,7 @@
 use clap::Args;
 use rc_core::{AliasManager, ObjectStore as _, RemotePath};
 use rc_s3::S3Client;
-use std::io::{self, Write};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 
 use crate::exit_code::ExitCode;
 use crate::output::{Formatter, OutputConfig};
@@ -70,29 +70,43 @@ pub async fn execute(args: HeadArgs, output_config: OutputConfig) -> ExitCode {
 
     let path = RemotePath::new(&alias_name, &bucket, &key);
 
-    // Get object content
-    match client.get_object(&path).await {
-        Ok(data) => {
+    // Stream object content, reading only what is needed
+    match client.get_object_stream(&path).await {
+        Ok(stream) => {
             let output = if let Some(num_bytes) = args.bytes {
                 // Output first N bytes
-                let end = num_bytes.min(data.len());
-                &data[..end]
+                let mut data = Vec::new();
+                if let Err(e) = stream
+                    .body
+                    .take(num_bytes as u64)
+                    .read_to_end(&mut data)
+                    .await
+                {
+                    formatter.error(&format!("Failed to read object: {e}"));
+                    return ExitCode::NetworkError;
+                }
+                data
             } else {
                 // Output first N lines
-                let content = String::from_utf8_lossy(&data);
-                let lines: Vec<&str> = content.lines().take(args.lines).collect();
-                let result = lines.join("\n");
-
-                // Write string content and add newline
-                if let Err(e) = writeln!(io::stdout(), "{result}") {
-                    formatter.error(&format!("Failed to write to stdout: {e}"));
-                    return ExitCode::GeneralError;
-                }
-                return ExitCode::Success;
+                let lines = match read_lines(stream.body, args.lines).await {
+                    Ok(lines) => lines,
+                    Err(e) => {
+                        formatter.error(&format!("Failed to read object: {e}"));
+                        return ExitCode::NetworkError;
+                    }
+                };
+                let mut result = lines.join("\n");
+                result.push('\n');
+                result.into_bytes()
             };
 
             // Write bytes directly to stdout
-            if let Err(e) = io::stdout().write_all(output) {
+            let mut stdout = tokio::io::stdout();
+            if let Err(e) = stdout.write_all(&output).await {
+                formatter.error(&format!("Failed to write to stdout: {e}"));
+                return ExitCode::GeneralError;
+            }
+            if let Err(e) = stdout.flush().await {
                 formatter.error(&format!("Failed to write to stdout: {e}"));
                 return ExitCode::GeneralError;
             }
@@ -114,6 +128,32 @@ pub async fn execute(args: HeadArgs, output_config: OutputConfig) -> ExitCode {
     }
 }
 
+/// Read up to `count` lines, stripping line terminators
+async fn read_lines<R: tokio::io::AsyncRead + Unpin>(
+    reader: R,
+    count: usize,
+) -> std::io::Result<Vec<String>> {
+    let mut reader = BufReader::new(reader);
+    let mut lines = Vec::with_capacity(count.min(1024));
+    let mut buf = Vec::new();
+
+    while lines.len() < count {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf).await? == 0 {
+            break;
+        }
+        if buf.last() == Some(&b'\n') {
+            buf.pop();
+            if buf.last() == Some(&b'\r') {
+                buf.pop();
+            }
+        }
+        lines.push(String::from_utf8_lossy(&buf).into_owned());
+    }
+
+    Ok(lines)
+}
+
 /// Parse head path into (alias, bucket, key)
 fn parse_head_path(path: &str) -> Result<(String, String, String), String> {
     if path.is_empty() {
@@ -172,4 +212,14 @@ mod tests {
     fn test_parse_head_path_empty() {
         assert!(parse_head_path("").is_err());
     }
+
+    #[tokio::test]
+    async fn test_read_lines_stops_at_count() {
+        let data: &[u8] = b"one\r\ntwo\nthree\nfour";
+        let lines = read_lines(data, 3).await.unwrap();
+        assert_eq!(lines, vec!["one", "two", "three"]);
+
+        let lines = read_lines(data, 10).await.unwrap();
+        assert_eq!(lines, vec!["one", "two", "three", "four"]);
+    }
 }
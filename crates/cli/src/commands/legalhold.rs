@@ -0,0 +1,268 @@
+//! legalhold command - Manage object legal holds
+//!
+//! Place, lift, or show legal holds on objects, object versions and every
+//! object under a prefix. A legal hold keeps an object version from being
+//! deleted or overwritten until it is lifted, independent of any retention.
+
+use clap::{Args, Subcommand};
+use rc_core::{ObjectStore as _, RemotePath};
+use serde::Serialize;
+
+use crate::commands::common::{exit_code_from_error, feature_client, list_object_keys};
+use crate::exit_code::ExitCode;
+use crate::output::{Formatter, OutputConfig};
+
+/// Manage object legal holds
+#[derive(Args, Debug)]
+pub struct LegalHoldArgs {
+    #[command(subcommand)]
+    pub command: LegalHoldCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LegalHoldCommands {
+    /// Place a legal hold on objects
+    Set(LegalHoldPathArgs),
+
+    /// Lift the legal hold from objects
+    Clear(LegalHoldPathArgs),
+
+    /// Show the legal hold status of objects
+    Info(LegalHoldPathArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct LegalHoldPathArgs {
+    /// Object or prefix (alias/bucket/key or alias/bucket/prefix/)
+    pub path: String,
+
+    /// Apply to every object under the prefix
+    #[arg(short, long)]
+    pub recursive: bool,
+
+    /// Apply to a specific object version
+    #[arg(long, conflicts_with = "recursive")]
+    pub version_id: Option<String>,
+
+    /// Force operation even if capability detection fails
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct LegalHoldEntry {
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version_id: Option<String>,
+    legal_hold: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct LegalHoldOutput {
+    status: &'static str,
+    objects: Vec<LegalHoldEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    failed: Vec<String>,
+}
+
+/// What a legal hold command does to each object
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    Set,
+    Clear,
+    Info,
+}
+
+/// Execute the legalhold command
+pub async fn execute(args: LegalHoldArgs, output_config: OutputConfig) -> ExitCode {
+    match args.command {
+        LegalHoldCommands::Set(path_args) => run(path_args, Operation::Set, output_config).await,
+        LegalHoldCommands::Clear(path_args) => {
+            run(path_args, Operation::Clear, output_config).await
+        }
+        LegalHoldCommands::Info(path_args) => run(path_args, Operation::Info, output_config).await,
+    }
+}
+
+async fn run(
+    args: LegalHoldPathArgs,
+    operation: Operation,
+    output_config: OutputConfig,
+) -> ExitCode {
+    let formatter = Formatter::new(output_config);
+
+    let (alias_name, bucket, key) = match parse_legalhold_path(&args.path, args.recursive) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            formatter.error(&error);
+            return ExitCode::UsageError;
+        }
+    };
+
+    let client = match feature_client(
+        &alias_name,
+        &bucket,
+        "object_lock",
+        "object lock",
+        args.force,
+        &formatter,
+    )
+    .await
+    {
+        Ok(client) => client,
+        Err(code) => return code,
+    };
+
+    let keys = if args.recursive {
+        match list_object_keys(&client, &alias_name, &bucket, &key, &formatter).await {
+            Ok(keys) => keys,
+            Err(code) => return code,
+        }
+    } else {
+        vec![key]
+    };
+
+    let version_id = args.version_id.as_deref();
+    let mut objects = Vec::new();
+    let mut failed = Vec::new();
+    let mut last_error = None;
+
+    for key in keys {
+        let path = RemotePath::new(&alias_name, &bucket, &key);
+        let full_path = format!("{alias_name}/{bucket}/{key}");
+
+        let result = match operation {
+            Operation::Set => client
+                .set_object_legal_hold(&path, version_id, true)
+                .await
+                .map(|()| true),
+            Operation::Clear => client
+                .set_object_legal_hold(&path, version_id, false)
+                .await
+                .map(|()| false),
+            Operation::Info => client.get_object_legal_hold(&path, version_id).await,
+        };
+
+        match result {
+            Ok(enabled) => {
+                if !formatter.is_json() {
+                    let styled = formatter.style_file(&full_path);
+                    let line = match operation {
+                        Operation::Set => format!("Legal hold placed: {styled}"),
+                        Operation::Clear => format!("Legal hold lifted: {styled}"),
+                        Operation::Info => format!("{styled}  {}", status_name(enabled)),
+                    };
+                    formatter.println(&line);
+                }
+                objects.push(LegalHoldEntry {
+                    path: full_path,
+                    version_id: args.version_id.clone(),
+                    legal_hold: status_name(enabled),
+                });
+            }
+            Err(error) => {
+                let action = match operation {
+                    Operation::Set => "place legal hold on",
+                    Operation::Clear => "lift legal hold from",
+                    Operation::Info => "get legal hold of",
+                };
+                formatter.error(&format!("Failed to {action} {full_path}: {error}"));
+                failed.push(full_path);
+                last_error = Some(error);
+            }
+        }
+    }
+
+    if formatter.is_json() {
+        formatter.json(&LegalHoldOutput {
+            status: if failed.is_empty() {
+                "success"
+            } else {
+                "partial"
+            },
+            objects,
+            failed,
+        });
+    } else if args.recursive && operation != Operation::Info && !objects.is_empty() {
+        let verb = if operation == Operation::Set {
+            "Placed"
+        } else {
+            "Lifted"
+        };
+        formatter.success(&format!(
+            "{verb} legal hold on {} object(s).",
+            objects.len()
+        ));
+    }
+
+    match last_error {
+        Some(error) => exit_code_from_error(&error),
+        None => ExitCode::Success,
+    }
+}
+
+fn status_name(enabled: bool) -> &'static str {
+    if enabled { "ON" } else { "OFF" }
+}
+
+/// Parse legalhold path into (alias, bucket, key)
+///
+/// Without `recursive` the path must name a single object.
+fn parse_legalhold_path(path: &str, recursive: bool) -> Result<(String, String, String), String> {
+    if path.trim().is_empty() {
+        return Err("Path cannot be empty".to_string());
+    }
+
+    let parts: Vec<&str> = path.splitn(3, '/').collect();
+    if parts.len() < 2 || parts[0].is_empty() || parts[1].is_empty() {
+        return Err(format!(
+            "Invalid path format: '{path}'. Expected: alias/bucket/key"
+        ));
+    }
+
+    let key = parts.get(2).copied().unwrap_or_default();
+    if !recursive && (key.is_empty() || key.ends_with('/')) {
+        return Err(format!(
+            "'{path}' is not an object. Use --recursive to apply to the objects under it."
+        ));
+    }
+
+    Ok((parts[0].to_string(), parts[1].to_string(), key.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_legalhold_path() {
+        assert_eq!(
+            parse_legalhold_path("local/bucket/a/b.txt", false).unwrap(),
+            (
+                "local".to_string(),
+                "bucket".to_string(),
+                "a/b.txt".to_string()
+            )
+        );
+        assert_eq!(
+            parse_legalhold_path("local/bucket", true).unwrap(),
+            ("local".to_string(), "bucket".to_string(), String::new())
+        );
+        assert!(parse_legalhold_path("local/bucket", false).is_err());
+        assert!(parse_legalhold_path("local/bucket/dir/", false).is_err());
+        assert!(parse_legalhold_path("local", true).is_err());
+        assert!(parse_legalhold_path("", false).is_err());
+    }
+
+    #[test]
+    fn test_legal_hold_entry_json() {
+        let entry = LegalHoldEntry {
+            path: "local/bucket/key".to_string(),
+            version_id: None,
+            legal_hold: status_name(true),
+        };
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["legal_hold"], "ON");
+        assert!(json.get("version_id").is_none());
+    }
+}
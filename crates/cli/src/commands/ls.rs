@@ -3,8 +3,12 @@
 //! Lists buckets when given an alias only, or lists objects when given a bucket path.
 
 use clap::Args;
-use rc_core::{AliasManager, ListOptions, ObjectInfo, ObjectStore as _, RemotePath};
+use rc_core::{
+    AliasManager, ListOptions, MultipartUploadInfo, ObjectInfo, ObjectStore as _, ObjectVersion,
+    RemotePath, rewind,
+};
 use rc_s3::S3Client;
+use rc_s3::capability::require_capability;
 use serde::Serialize;
 
 use crate::exit_code::ExitCode;
@@ -24,7 +28,15 @@ pub struct LsArgs {
     #[arg(long)]
     pub versions: bool,
 
-    /// Include incomplete uploads
+    /// List objects as they were at a past time (duration like 2d, or RFC 3339)
+    #[arg(long, conflicts_with_all = ["versions", "incomplete"])]
+    pub rewind: Option<String>,
+
+    /// Only show versions with this version ID
+    #[arg(long, conflicts_with_all = ["rewind", "incomplete"])]
+    pub version_id: Option<String>,
+
+    /// List incomplete multipart uploads instead of objects
     #[arg(long)]
     pub incomplete: bool,
 
@@ -44,6 +56,28 @@ struct LsOutput {
     summary: Option<Summary>,
 }
 
+/// Output structure for `ls --versions` (JSON format)
+#[derive(Debug, Serialize)]
+struct LsVersionsOutput {
+    items: Vec<VersionItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<Summary>,
+}
+
+/// Output structure for `ls --incomplete` (JSON format)
+#[derive(Debug, Serialize)]
+struct LsIncompleteOutput {
+    items: Vec<MultipartUploadInfo>,
+}
+
+#[derive(Debug, Serialize)]
+struct VersionItem {
+    #[serde(flatten)]
+    version: ObjectVersion,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size_human: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 struct Summary {
     total_objects: usize,
@@ -98,6 +132,25 @@ pub async fn execute(args: LsArgs, output_config: OutputConfig) -> ExitCode {
     let bucket = bucket.unwrap();
     let path = RemotePath::new(&alias_name, &bucket, prefix.unwrap_or_default());
 
+    if args.versions || args.version_id.is_some() {
+        return list_versions(&client, &path, &args, &formatter).await;
+    }
+
+    if let Some(rewind) = &args.rewind {
+        let at = match rewind::parse_rewind(rewind, jiff::Timestamp::now()) {
+            Ok(at) => at,
+            Err(e) => {
+                formatter.error(&e.to_string());
+                return ExitCode::UsageError;
+            }
+        };
+        return list_objects_at(&client, &path, at, &args, &formatter).await;
+    }
+
+    if args.incomplete {
+        return list_incomplete(&client, &path, &args, &formatter).await;
+    }
+
     // List objects
     list_objects(&client, &path, &args, &formatter).await
 }
@@ -193,6 +246,67 @@ async fn list_objects(
         }
     }
 
+    print_objects(all_items, is_truncated, continuation_token, args, formatter);
+    ExitCode::Success
+}
+
+async fn list_objects_at(
+    client: &S3Client,
+    path: &RemotePath,
+    at: jiff::Timestamp,
+    args: &LsArgs,
+    formatter: &Formatter,
+) -> ExitCode {
+    match rewind::resolve_versions(client, path, at).await {
+        Ok(versions) => {
+            let items = objects_at(versions, &path.key, args.recursive);
+            print_objects(items, false, None, args, formatter);
+            ExitCode::Success
+        }
+        Err(e) => {
+            let err_str = e.to_string();
+            if err_str.contains("NotFound") || err_str.contains("NoSuchBucket") {
+                formatter.error(&format!("Bucket not found: {}", path.bucket));
+                return ExitCode::NotFound;
+            }
+            formatter.error(&format!("Failed to list object versions: {e}"));
+            ExitCode::NetworkError
+        }
+    }
+}
+
+/// Turn the versions current at some time into a listing under `prefix`
+///
+/// Without `recursive`, nested keys collapse into their first-level
+/// directory, as a delimited listing would show them.
+fn objects_at(versions: Vec<ObjectVersion>, prefix: &str, recursive: bool) -> Vec<ObjectInfo> {
+    let mut items = Vec::new();
+    let mut dirs = std::collections::BTreeSet::new();
+
+    for v in versions {
+        let Some(rest) = v.key.strip_prefix(prefix) else {
+            continue;
+        };
+        if !recursive && let Some(idx) = rest.find('/') {
+            dirs.insert(format!("{prefix}{}", &rest[..=idx]));
+            continue;
+        }
+        let mut info = ObjectInfo::file(&v.key, v.size_bytes.unwrap_or(0));
+        info.last_modified = v.last_modified;
+        info.etag = v.etag;
+        items.push(info);
+    }
+
+    dirs.into_iter().map(ObjectInfo::dir).chain(items).collect()
+}
+
+fn print_objects(
+    all_items: Vec<ObjectInfo>,
+    is_truncated: bool,
+    continuation_token: Option<String>,
+    args: &LsArgs,
+    formatter: &Formatter,
+) {
     // Calculate summary
     let total_objects = all_items.iter().filter(|i| !i.is_dir).count();
     let total_size: i64 = all_items.iter().filter_map(|i| i.size_bytes).sum();
@@ -242,10 +356,178 @@ async fn list_objects(
             ));
         }
     }
+}
+
+async fn list_versions(
+    client: &S3Client,
+    path: &RemotePath,
+    args: &LsArgs,
+    formatter: &Formatter,
+) -> ExitCode {
+    let caps = match client.capabilities().await {
+        Ok(c) => c,
+        Err(e) => {
+            formatter.error(&format!("Failed to detect capabilities: {e}"));
+            return ExitCode::NetworkError;
+        }
+    };
+    if let Err(e) = require_capability(&caps, "versioning") {
+        formatter.error(&e.to_string());
+        return ExitCode::UnsupportedFeature;
+    }
+
+    let versions = match client.list_object_versions(path, None).await {
+        Ok(v) => match &args.version_id {
+            Some(id) => v.into_iter().filter(|v| &v.version_id == id).collect(),
+            None => filter_versions(v, &path.key, args.recursive),
+        },
+        Err(e) => {
+            let err_str = e.to_string();
+            if err_str.contains("NotFound") || err_str.contains("NoSuchBucket") {
+                formatter.error(&format!("Bucket not found: {}", path.bucket));
+                return ExitCode::NotFound;
+            }
+            formatter.error(&format!("Failed to list object versions: {e}"));
+            return ExitCode::NetworkError;
+        }
+    };
+
+    let total_objects = versions.iter().filter(|v| !v.is_delete_marker).count();
+    let total_size: i64 = versions.iter().filter_map(|v| v.size_bytes).sum();
+
+    if formatter.is_json() {
+        let output = LsVersionsOutput {
+            items: versions
+                .into_iter()
+                .map(|v| VersionItem {
+                    size_human: v
+                        .size_bytes
+                        .map(|s| humansize::format_size(s as u64, humansize::BINARY)),
+                    version: v,
+                })
+                .collect(),
+            summary: if args.summarize {
+                Some(Summary {
+                    total_objects,
+                    total_size_bytes: total_size,
+                    total_size_human: humansize::format_size(total_size as u64, humansize::BINARY),
+                })
+            } else {
+                None
+            },
+        };
+        formatter.json(&output);
+    } else {
+        for v in &versions {
+            let date = v
+                .last_modified
+                .map(|d| d.strftime("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_else(|| "                   ".to_string());
+            let styled_date = formatter.style_date(&format!("[{date}]"));
+            let size = v
+                .size_bytes
+                .map(|s| humansize::format_size(s as u64, humansize::BINARY))
+                .unwrap_or_default();
+            let styled_size = formatter.style_size(&format!("{size:>10}"));
+            let flag = version_flag(v);
+            let styled_name = formatter.style_file(&v.key);
+            formatter.println(&format!(
+                "{styled_date} {styled_size} {:<36} {flag:<6} {styled_name}",
+                v.version_id
+            ));
+        }
+
+        if args.summarize {
+            let total_size_human = humansize::format_size(total_size as u64, humansize::BINARY);
+            formatter.println(&format!(
+                "\nTotal: {} versions, {}",
+                formatter.style_size(&total_objects.to_string()),
+                formatter.style_size(&total_size_human)
+            ));
+        }
+    }
 
     ExitCode::Success
 }
 
+async fn list_incomplete(
+    client: &S3Client,
+    path: &RemotePath,
+    args: &LsArgs,
+    formatter: &Formatter,
+) -> ExitCode {
+    let mut uploads = match client.list_multipart_uploads(path).await {
+        Ok(u) => u,
+        Err(e) => {
+            let err_str = e.to_string();
+            if err_str.contains("NotFound") || err_str.contains("NoSuchBucket") {
+                formatter.error(&format!("Bucket not found: {}", path.bucket));
+                return ExitCode::NotFound;
+            }
+            formatter.error(&format!("Failed to list incomplete uploads: {e}"));
+            return ExitCode::NetworkError;
+        }
+    };
+    uploads.retain(|u| is_listed(&u.key, &path.key, args.recursive));
+
+    if formatter.is_json() {
+        formatter.json(&LsIncompleteOutput { items: uploads });
+    } else {
+        for upload in &uploads {
+            let date = upload
+                .initiated
+                .map(|d| d.strftime("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_else(|| "                   ".to_string());
+            let styled_date = formatter.style_date(&format!("[{date}]"));
+            let styled_name = formatter.style_file(&upload.key);
+            formatter.println(&format!(
+                "{styled_date} {styled_name} ({})",
+                upload.upload_id
+            ));
+        }
+
+        if args.summarize {
+            formatter.println(&format!(
+                "\nTotal: {} incomplete uploads",
+                formatter.style_size(&uploads.len().to_string())
+            ));
+        }
+    }
+
+    ExitCode::Success
+}
+
+/// Keep versions of keys directly under `prefix` unless listing recursively
+fn filter_versions(
+    versions: Vec<ObjectVersion>,
+    prefix: &str,
+    recursive: bool,
+) -> Vec<ObjectVersion> {
+    versions
+        .into_iter()
+        .filter(|v| is_listed(&v.key, prefix, recursive))
+        .collect()
+}
+
+/// Whether a key from a flat listing belongs in the output
+///
+/// Version and upload listings have no delimiter support, so nested keys are
+/// dropped here to match what a non-recursive `ls` shows.
+fn is_listed(key: &str, prefix: &str, recursive: bool) -> bool {
+    key.strip_prefix(prefix)
+        .is_some_and(|rest| recursive || !rest.contains('/'))
+}
+
+/// Short marker shown next to a version in human output
+fn version_flag(version: &ObjectVersion) -> &'static str {
+    match (version.is_delete_marker, version.is_latest) {
+        (true, true) => "DEL*",
+        (true, false) => "DEL",
+        (false, true) => "LATEST",
+        (false, false) => "",
+    }
+}
+
 /// Parse ls path into (alias, bucket, prefix)
 fn parse_ls_path(path: &str) -> Result<(String, Option<String>, Option<String>), String> {
     let path = path.trim_end_matches('/');
@@ -308,4 +590,64 @@ mod tests {
     fn test_parse_ls_path_empty() {
         assert!(parse_ls_path("").is_err());
     }
+
+    fn version(key: &str, is_latest: bool, is_delete_marker: bool) -> ObjectVersion {
+        ObjectVersion {
+            key: key.to_string(),
+            version_id: "v1".to_string(),
+            is_latest,
+            is_delete_marker,
+            last_modified: None,
+            size_bytes: None,
+            etag: None,
+        }
+    }
+
+    #[test]
+    fn test_filter_versions() {
+        let versions = vec![
+            version("dir/a.txt", true, false),
+            version("dir/sub/b.txt", true, false),
+            version("other.txt", true, false),
+        ];
+
+        let flat = filter_versions(versions.clone(), "dir/", false);
+        assert_eq!(flat.len(), 1);
+        assert_eq!(flat[0].key, "dir/a.txt");
+
+        assert_eq!(filter_versions(versions, "dir/", true).len(), 2);
+    }
+
+    #[test]
+    fn test_is_listed() {
+        assert!(is_listed("dir/a.txt", "dir/", false));
+        assert!(!is_listed("dir/sub/b.txt", "dir/", false));
+        assert!(is_listed("dir/sub/b.txt", "dir/", true));
+        assert!(!is_listed("other.txt", "dir/", true));
+        assert!(is_listed("top.txt", "", false));
+    }
+
+    #[test]
+    fn test_objects_at() {
+        let versions = vec![
+            version("dir/a.txt", false, false),
+            version("dir/sub/b.txt", false, false),
+            version("dir/sub/c.txt", false, false),
+        ];
+
+        let flat = objects_at(versions.clone(), "dir/", false);
+        let keys: Vec<&str> = flat.iter().map(|i| i.key.as_str()).collect();
+        assert_eq!(keys, vec!["dir/sub/", "dir/a.txt"]);
+        assert!(flat[0].is_dir);
+
+        assert_eq!(objects_at(versions, "dir/", true).len(), 3);
+    }
+
+    #[test]
+    fn test_version_flag() {
+        assert_eq!(version_flag(&version("a", true, false)), "LATEST");
+        assert_eq!(version_flag(&version("a", false, false)), "");
+        assert_eq!(version_flag(&version("a", true, true)), "DEL*");
+        assert_eq!(version_flag(&version("a", false, true)), "DEL");
+    }
 }
@@ -3,7 +3,7 @@
 //! Creates a new bucket on the specified storage service.
 
 use clap::Args;
-use rc_core::{AliasManager, ObjectStore as _};
+use rc_core::{AliasManager, CreateBucketOptions, ObjectStore as _};
 use rc_s3::S3Client;
 use serde::Serialize;
 
@@ -24,7 +24,7 @@ pub struct MbArgs {
     #[arg(long)]
     pub region: Option<String>,
 
-    /// Enable object locking on the bucket
+    /// Enable object locking on the bucket (implies versioning)
     #[arg(long)]
     pub with_lock: bool,
 
@@ -38,9 +38,28 @@ struct MbOutput {
     status: &'static str,
     bucket: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    object_lock: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    versioning: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
     message: Option<String>,
 }
 
+impl MbOutput {
+    fn existing(bucket: &str) -> Self {
+        Self {
+            status: "success",
+            bucket: bucket.to_string(),
+            region: None,
+            object_lock: false,
+            versioning: false,
+            message: Some("Bucket already exists".to_string()),
+        }
+    }
+}
+
 /// Execute the mb command
 pub async fn execute(args: MbArgs, output_config: OutputConfig) -> ExitCode {
     let formatter = Formatter::new(output_config);
@@ -85,12 +104,7 @@ pub async fn execute(args: MbArgs, output_config: OutputConfig) -> ExitCode {
         match client.bucket_exists(&bucket).await {
             Ok(true) => {
                 if formatter.is_json() {
-                    let output = MbOutput {
-                        status: "success",
-                        bucket: bucket.clone(),
-                        message: Some("Bucket already exists".to_string()),
-                    };
-                    formatter.json(&output);
+                    formatter.json(&MbOutput::existing(&bucket));
                 } else {
                     formatter.success(&format!("Bucket '{alias_name}/{bucket}' already exists."));
                 }
@@ -104,19 +118,29 @@ pub async fn execute(args: MbArgs, output_config: OutputConfig) -> ExitCode {
         }
     }
 
+    let options = CreateBucketOptions {
+        region: args.region.clone(),
+        object_lock: args.with_lock,
+        versioning: args.with_versioning || args.with_lock,
+    };
+
     // Create the bucket
-    match client.create_bucket(&bucket).await {
+    match client.create_bucket(&bucket, &options).await {
         Ok(()) => {
             if formatter.is_json() {
                 let output = MbOutput {
                     status: "success",
                     bucket: bucket.clone(),
+                    region: options.region,
+                    object_lock: options.object_lock,
+                    versioning: options.versioning,
                     message: None,
                 };
                 formatter.json(&output);
             } else {
                 formatter.success(&format!(
-                    "Bucket '{alias_name}/{bucket}' created successfully."
+                    "Bucket '{alias_name}/{bucket}' created successfully{}.",
+                    describe_options(&options)
                 ));
             }
             ExitCode::Success
@@ -128,12 +152,7 @@ pub async fn execute(args: MbArgs, output_config: OutputConfig) -> ExitCode {
             {
                 if args.ignore_existing {
                     if formatter.is_json() {
-                        let output = MbOutput {
-                            status: "success",
-                            bucket: bucket.clone(),
-                            message: Some("Bucket already exists".to_string()),
-                        };
-                        formatter.json(&output);
+                        formatter.json(&MbOutput::existing(&bucket));
                     } else {
                         formatter
                             .success(&format!("Bucket '{alias_name}/{bucket}' already exists."));
@@ -155,6 +174,20 @@ pub async fn execute(args: MbArgs, output_config: OutputConfig) -> ExitCode {
     }
 }
 
+/// Describe the non-default options a bucket was created with, e.g. " with object lock in eu-west-1"
+fn describe_options(options: &CreateBucketOptions) -> String {
+    let mut description = String::new();
+    if options.object_lock {
+        description.push_str(" with object lock");
+    } else if options.versioning {
+        description.push_str(" with versioning");
+    }
+    if let Some(region) = &options.region {
+        description.push_str(&format!(" in {region}"));
+    }
+    description
+}
+
 /// Parse mb target path into (alias, bucket)
 fn parse_mb_path(path: &str) -> Result<(String, String), String> {
     let path = path.trim_end_matches('/');
@@ -219,6 +252,22 @@ mod tests {
         assert!(parse_mb_path("myalias/ab").is_err());
     }
 
+    #[test]
+    fn test_describe_options() {
+        assert_eq!(describe_options(&CreateBucketOptions::default()), "");
+        let options = CreateBucketOptions {
+            region: Some("eu-west-1".to_string()),
+            object_lock: true,
+            versioning: true,
+        };
+        assert_eq!(describe_options(&options), " with object lock in eu-west-1");
+        let options = CreateBucketOptions {
+            versioning: true,
+            ..Default::default()
+        };
+        assert_eq!(describe_options(&options), " with versioning");
+    }
+
     #[test]
     fn test_parse_mb_path_empty() {
         assert!(parse_mb_path("").is_err());
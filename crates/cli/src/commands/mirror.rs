@@ -1,15 +1,24 @@
 //! mirror command - Synchronize objects between two locations
 //!
 //! Mirrors objects from source to destination, optionally removing extra files.
+//! Either side may be a local directory; local files are compared to objects by
+//! size and modification time. With `--watch` the mirror keeps running and
+//! applies changes as they happen.
 
 use clap::Args;
+use futures::StreamExt;
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
-use rc_core::{AliasManager, ListOptions, ObjectStore as _, ParsedPath, RemotePath, parse_path};
-use rc_s3::S3Client;
+use rc_core::{
+    AliasManager, DeleteOptions, ListOptions, ObjectStore as _, ParsedPath, RemotePath, parse_path,
+};
+use rc_s3::{MultipartConfig, S3Client, etag, multipart};
 use serde::Serialize;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
+use crate::commands::cp::{walk_dir, write_stream_to_file};
 use crate::commands::diff::{DiffEntry, DiffStatus};
 use crate::exit_code::ExitCode;
 use crate::output::{Formatter, OutputConfig};
@@ -17,10 +26,10 @@ use crate::output::{Formatter, OutputConfig};
 /// Synchronize objects between two locations
 #[derive(Args, Debug)]
 pub struct MirrorArgs {
-    /// Source path (alias/bucket/prefix)
+    /// Source path (local directory or alias/bucket/prefix)
     pub source: String,
 
-    /// Destination path (alias/bucket/prefix)
+    /// Destination path (local directory or alias/bucket/prefix)
     pub target: String,
 
     /// Remove extra objects at destination
@@ -35,6 +44,10 @@ pub struct MirrorArgs {
     #[arg(short = 'n', long)]
     pub dry_run: bool,
 
+    /// Compare content checksums (MD5 / multipart ETag) instead of modification time
+    #[arg(long)]
+    pub checksum: bool,
+
     /// Number of parallel operations
     #[arg(short = 'P', long, default_value = "4")]
     pub parallel: usize,
@@ -42,8 +55,19 @@ pub struct MirrorArgs {
     /// Disable progress bar
     #[arg(long)]
     pub quiet: bool,
+
+    /// Keep running and mirror changes as they happen
+    #[arg(short, long, conflicts_with = "dry_run")]
+    pub watch: bool,
+
+    /// Polling interval in seconds for remote sources in watch mode
+    #[arg(long, default_value = "10", requires = "watch")]
+    pub interval: u64,
 }
 
+/// Quiet period used to coalesce filesystem events in watch mode
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
 #[derive(Debug, Serialize)]
 struct MirrorOutput {
     source: String,
@@ -55,11 +79,75 @@ struct MirrorOutput {
     dry_run: bool,
 }
 
+/// Size, modification time and ETag of a file or object being compared
 #[derive(Debug, Clone)]
-struct FileInfo {
-    size: Option<i64>,
-    modified: Option<String>,
-    etag: Option<String>,
+pub(crate) struct FileInfo {
+    pub size: Option<i64>,
+    pub modified: Option<jiff::Timestamp>,
+    pub etag: Option<String>,
+}
+
+/// One side of a mirror operation
+enum Location {
+    Local(PathBuf),
+    Remote {
+        client: Arc<S3Client>,
+        path: RemotePath,
+    },
+}
+
+impl Location {
+    /// Full remote path for a relative key
+    fn remote_path(path: &RemotePath, key: &str) -> RemotePath {
+        let sep = if path.key.is_empty() || path.key.ends_with('/') {
+            ""
+        } else {
+            "/"
+        };
+        RemotePath::new(&path.alias, &path.bucket, format!("{}{sep}{key}", path.key))
+    }
+
+    /// Full local path for a relative key
+    ///
+    /// Keys come from remote listings, so every `/`-separated segment must be
+    /// a plain file name; `..`, `.`, empty and absolute segments are rejected
+    /// rather than allowed to point outside `root`.
+    fn local_path(root: &Path, key: &str) -> Result<PathBuf, rc_core::Error> {
+        key.split('/').try_fold(root.to_path_buf(), |path, part| {
+            let mut components = Path::new(part).components();
+            match (components.next(), components.next()) {
+                (Some(std::path::Component::Normal(name)), None) if name == part => {
+                    Ok(path.join(part))
+                }
+                _ => Err(rc_core::Error::InvalidPath(format!(
+                    "Refusing to mirror key '{key}' outside the target directory"
+                ))),
+            }
+        })
+    }
+
+    /// List all files below this location, keyed by relative path
+    async fn list(&self) -> Result<HashMap<String, FileInfo>, rc_core::Error> {
+        match self {
+            Location::Local(root) => list_local_map(root, true),
+            Location::Remote { client, path } => list_objects_map(client, path, true).await,
+        }
+    }
+
+    /// Remove a single file or object
+    async fn remove(&self, key: &str) -> Result<(), rc_core::Error> {
+        match self {
+            Location::Local(root) => {
+                tokio::fs::remove_file(Self::local_path(root, key)?).await?;
+                Ok(())
+            }
+            Location::Remote { client, path } => {
+                client
+                    .delete_object(&Self::remote_path(path, key), &DeleteOptions::default())
+                    .await
+            }
+        }
+    }
 }
 
 /// Execute the mirror command
@@ -67,16 +155,9 @@ pub async fn execute(args: MirrorArgs, output_config: OutputConfig) -> ExitCode
     let formatter = Formatter::new(output_config);
 
     // Parse both paths
-    let source_parsed = parse_path(&args.source);
-    let target_parsed = parse_path(&args.target);
-
-    // Both must be remote for now
-    let (source_path, target_path) = match (&source_parsed, &target_parsed) {
-        (Ok(ParsedPath::Remote(s)), Ok(ParsedPath::Remote(t))) => (s.clone(), t.clone()),
-        (Ok(ParsedPath::Local(_)), _) | (_, Ok(ParsedPath::Local(_))) => {
-            formatter.error("Local paths are not yet supported in mirror command");
-            return ExitCode::UsageError;
-        }
+    let (source_parsed, target_parsed) = match (parse_path(&args.source), parse_path(&args.target))
+    {
+        (Ok(s), Ok(t)) => (s, t),
         (Err(e), _) => {
             formatter.error(&format!("Invalid source path: {e}"));
             return ExitCode::UsageError;
@@ -87,6 +168,18 @@ pub async fn execute(args: MirrorArgs, output_config: OutputConfig) -> ExitCode
         }
     };
 
+    if let (ParsedPath::Local(_), ParsedPath::Local(_)) = (&source_parsed, &target_parsed) {
+        formatter.error("Cannot mirror between two local paths. Use rsync or similar.");
+        return ExitCode::UsageError;
+    }
+
+    if let ParsedPath::Local(dir) = &source_parsed
+        && !dir.is_dir()
+    {
+        formatter.error(&format!("Source is not a directory: {}", dir.display()));
+        return ExitCode::NotFound;
+    }
+
     // Load aliases
     let alias_manager = match AliasManager::new() {
         Ok(am) => am,
@@ -97,55 +190,94 @@ pub async fn execute(args: MirrorArgs, output_config: OutputConfig) -> ExitCode
     };
 
     // Create clients
-    let source_alias = match alias_manager.get(&source_path.alias) {
-        Ok(a) => a,
-        Err(_) => {
-            formatter.error(&format!("Alias '{}' not found", source_path.alias));
-            return ExitCode::NotFound;
-        }
+    let source = match resolve_location(source_parsed, &alias_manager, "source", &formatter).await {
+        Ok(l) => l,
+        Err(code) => return code,
     };
 
-    let target_alias = match alias_manager.get(&target_path.alias) {
-        Ok(a) => a,
-        Err(_) => {
-            formatter.error(&format!("Alias '{}' not found", target_path.alias));
-            return ExitCode::NotFound;
-        }
+    let target = match resolve_location(target_parsed, &alias_manager, "target", &formatter).await {
+        Ok(l) => l,
+        Err(code) => return code,
     };
 
-    let source_client = Arc::new(match S3Client::new(source_alias).await {
-        Ok(c) => c,
-        Err(e) => {
-            formatter.error(&format!("Failed to create source client: {e}"));
-            return ExitCode::NetworkError;
-        }
-    });
+    let stats = match sync_once(&source, &target, &args, &formatter).await {
+        Ok(stats) => stats,
+        Err(code) => return code,
+    };
 
-    let target_client = Arc::new(match S3Client::new(target_alias).await {
-        Ok(c) => c,
-        Err(e) => {
-            formatter.error(&format!("Failed to create target client: {e}"));
-            return ExitCode::NetworkError;
+    if args.dry_run {
+        if formatter.is_json() {
+            print_json_summary(&args, &stats, &formatter);
         }
-    });
+        return ExitCode::Success;
+    }
+
+    if args.watch {
+        return watch(&source, &target, &args, &formatter).await;
+    }
+
+    print_summary(&args, &stats, &formatter);
+
+    if stats.errors > 0 {
+        ExitCode::GeneralError
+    } else {
+        ExitCode::Success
+    }
+}
+
+/// Counts from a single synchronization pass
+#[derive(Debug, Default)]
+struct SyncStats {
+    copied: usize,
+    removed: usize,
+    skipped: usize,
+    errors: usize,
+}
 
+/// Compare source and target once and apply the resulting copies and removals
+///
+/// In dry-run mode the planned operations are printed instead and counted as
+/// if they had been performed.
+async fn sync_once(
+    source: &Location,
+    target: &Location,
+    args: &MirrorArgs,
+    formatter: &Formatter,
+) -> Result<SyncStats, ExitCode> {
     // List objects from both paths
-    let source_objects = match list_objects_map(&source_client, &source_path).await {
+    let mut source_objects = match source.list().await {
         Ok(o) => o,
         Err(e) => {
             formatter.error(&format!("Failed to list source: {e}"));
-            return ExitCode::NetworkError;
+            return Err(ExitCode::NetworkError);
         }
     };
 
-    let target_objects = match list_objects_map(&target_client, &target_path).await {
+    let mut target_objects = match target.list().await {
         Ok(o) => o,
         Err(e) => {
             formatter.error(&format!("Failed to list target: {e}"));
-            return ExitCode::NetworkError;
+            return Err(ExitCode::NetworkError);
         }
     };
 
+    // Hash local files in the form of the other side's ETags
+    if args.checksum {
+        let result = match (source, target) {
+            (Location::Local(root), _) => {
+                apply_local_checksums(root, &mut source_objects, &target_objects).await
+            }
+            (_, Location::Local(root)) => {
+                apply_local_checksums(root, &mut target_objects, &source_objects).await
+            }
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            formatter.error(&format!("Failed to compute checksums: {e}"));
+            return Err(ExitCode::GeneralError);
+        }
+    }
+
     // Compare and determine operations
     let diff_entries = compare_objects_internal(&source_objects, &target_objects);
 
@@ -215,23 +347,17 @@ pub async fn execute(args: MirrorArgs, output_config: OutputConfig) -> ExitCode
                 to_remove.len(),
                 skipped
             ));
-        } else {
-            let output = MirrorOutput {
-                source: args.source.clone(),
-                target: args.target.clone(),
-                copied: to_copy.len(),
-                removed: to_remove.len(),
-                skipped,
-                errors: 0,
-                dry_run: true,
-            };
-            formatter.json(&output);
         }
-        return ExitCode::Success;
+        return Ok(SyncStats {
+            copied: to_copy.len(),
+            removed: to_remove.len(),
+            skipped,
+            errors: 0,
+        });
     }
 
-    // Progress bar setup
-    let multi_progress = if !args.quiet && !formatter.is_json() {
+    // Progress bar setup (watch mode prints per-change lines instead)
+    let multi_progress = if !args.quiet && !args.watch && !formatter.is_json() {
         Some(MultiProgress::new())
     } else {
         None
@@ -249,53 +375,31 @@ pub async fn execute(args: MirrorArgs, output_config: OutputConfig) -> ExitCode
         pb
     });
 
+    let parallel = args.parallel.max(1);
+
     // Perform copy operations
     let mut copied = 0;
     let mut errors = 0;
 
-    for (key, _) in &to_copy {
-        let source_sep = if source_path.key.is_empty() || source_path.key.ends_with('/') {
-            ""
-        } else {
-            "/"
-        };
-        let source_full = RemotePath::new(
-            &source_path.alias,
-            &source_path.bucket,
-            format!("{}{source_sep}{key}", source_path.key),
-        );
-
-        let target_sep = if target_path.key.is_empty() || target_path.key.ends_with('/') {
-            ""
-        } else {
-            "/"
-        };
-        let target_full = RemotePath::new(
-            &target_path.alias,
-            &target_path.bucket,
-            format!("{}{target_sep}{key}", target_path.key),
-        );
-
-        // Get object content and upload to target
-        match source_client.get_object(&source_full).await {
-            Ok(data) => match target_client.put_object(&target_full, data, None).await {
-                Ok(_) => {
-                    copied += 1;
-                    if !args.quiet && !formatter.is_json() {
-                        formatter.println(&format!("+ {key}"));
-                    }
-                }
-                Err(e) => {
-                    errors += 1;
-                    if !formatter.is_json() {
-                        formatter.error(&format!("Failed to upload {key}: {e}"));
-                    }
+    let mut copies = futures::stream::iter(
+        to_copy
+            .iter()
+            .map(|(key, info)| async move { (*key, copy_entry(source, target, key, info).await) }),
+    )
+    .buffer_unordered(parallel);
+
+    while let Some((key, result)) = copies.next().await {
+        match result {
+            Ok(()) => {
+                copied += 1;
+                if !args.quiet && !formatter.is_json() {
+                    formatter.println(&format!("+ {key}"));
                 }
-            },
+            }
             Err(e) => {
                 errors += 1;
                 if !formatter.is_json() {
-                    formatter.error(&format!("Failed to download {key}: {e}"));
+                    formatter.error(&format!("Failed to copy {key}: {e}"));
                 }
             }
         }
@@ -309,19 +413,15 @@ pub async fn execute(args: MirrorArgs, output_config: OutputConfig) -> ExitCode
     let mut removed = 0;
 
     if args.remove {
-        for key in &to_remove {
-            let sep = if target_path.key.is_empty() || target_path.key.ends_with('/') {
-                ""
-            } else {
-                "/"
-            };
-            let target_full = RemotePath::new(
-                &target_path.alias,
-                &target_path.bucket,
-                format!("{}{sep}{key}", target_path.key),
-            );
-
-            match target_client.delete_object(&target_full).await {
+        let mut removals = futures::stream::iter(
+            to_remove
+                .iter()
+                .map(|key| async move { (*key, target.remove(key).await) }),
+        )
+        .buffer_unordered(parallel);
+
+        while let Some((key, result)) = removals.next().await {
+            match result {
                 Ok(_) => {
                     removed += 1;
                     if !args.quiet && !formatter.is_json() {
@@ -346,35 +446,361 @@ pub async fn execute(args: MirrorArgs, output_config: OutputConfig) -> ExitCode
         pb.finish_with_message("Done");
     }
 
-    // Output results
-    if formatter.is_json() {
-        let output = MirrorOutput {
-            source: args.source.clone(),
-            target: args.target.clone(),
-            copied,
-            removed,
-            skipped,
-            errors,
-            dry_run: false,
+    Ok(SyncStats {
+        copied,
+        removed,
+        skipped,
+        errors,
+    })
+}
+
+/// Keep the target in sync until interrupted
+///
+/// Local sources are watched for filesystem events; remote sources are
+/// re-listed every `--interval` seconds. Each change triggers a full
+/// comparison, so deletions are propagated with `--remove` as well.
+async fn watch(
+    source: &Location,
+    target: &Location,
+    args: &MirrorArgs,
+    formatter: &Formatter,
+) -> ExitCode {
+    if !formatter.is_json() {
+        formatter.println(&format!(
+            "Watching {} for changes (press Ctrl+C to stop)...",
+            args.source
+        ));
+    }
+
+    // Keep the watcher alive for the duration of the loop
+    let (mut events, _watcher) = match source {
+        Location::Local(root) => match watch_local(root) {
+            Ok((rx, watcher)) => (Some(rx), Some(watcher)),
+            Err(e) => {
+                formatter.error(&format!("Failed to watch {}: {e}", root.display()));
+                return ExitCode::GeneralError;
+            }
+        },
+        Location::Remote { .. } => (None, None),
+    };
+
+    let mut ticker = tokio::time::interval(Duration::from_secs(args.interval.max(1)));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    ticker.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            changed = next_change(&mut events, &mut ticker) => {
+                if !changed {
+                    formatter.error("Filesystem watcher stopped unexpectedly");
+                    return ExitCode::GeneralError;
+                }
+            }
+        }
+
+        // A pass over a large tree can take a while; stop it on Ctrl+C as well
+        let result = tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            result = sync_once(source, target, args, formatter) => result,
         };
-        formatter.json(&output);
+
+        // Listing failures are reported and retried on the next change
+        if let Ok(stats) = result
+            && formatter.is_json()
+            && stats.copied + stats.removed + stats.errors > 0
+        {
+            print_json_summary(args, &stats, formatter);
+        }
+    }
+
+    ExitCode::Success
+}
+
+/// Wait for the next reason to re-sync; returns false if the watcher is gone
+async fn next_change(
+    events: &mut Option<tokio::sync::mpsc::UnboundedReceiver<()>>,
+    ticker: &mut tokio::time::Interval,
+) -> bool {
+    let Some(rx) = events else {
+        ticker.tick().await;
+        return true;
+    };
+
+    if rx.recv().await.is_none() {
+        return false;
+    }
+
+    // Coalesce bursts of events (e.g. a build writing many files) into one pass
+    while let Ok(Some(())) = tokio::time::timeout(WATCH_DEBOUNCE, rx.recv()).await {}
+    true
+}
+
+/// Start a recursive filesystem watcher that signals on every modifying event
+fn watch_local(
+    root: &Path,
+) -> notify::Result<(
+    tokio::sync::mpsc::UnboundedReceiver<()>,
+    notify::RecommendedWatcher,
+)> {
+    use notify::Watcher as _;
+
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res
+            && !event.kind.is_access()
+        {
+            let _ = tx.send(());
+        }
+    })?;
+    watcher.watch(root, notify::RecursiveMode::Recursive)?;
+
+    Ok((rx, watcher))
+}
+
+fn print_json_summary(args: &MirrorArgs, stats: &SyncStats, formatter: &Formatter) {
+    let output = MirrorOutput {
+        source: args.source.clone(),
+        target: args.target.clone(),
+        copied: stats.copied,
+        removed: stats.removed,
+        skipped: stats.skipped,
+        errors: stats.errors,
+        dry_run: args.dry_run,
+    };
+    formatter.json(&output);
+}
+
+fn print_summary(args: &MirrorArgs, stats: &SyncStats, formatter: &Formatter) {
+    if formatter.is_json() {
+        print_json_summary(args, stats, formatter);
     } else {
         formatter.println("");
         formatter.println(&format!(
-            "Mirror complete: {copied} copied, {removed} removed, {skipped} skipped, {errors} errors"
+            "Mirror complete: {} copied, {} removed, {} skipped, {} errors",
+            stats.copied, stats.removed, stats.skipped, stats.errors
         ));
     }
+}
 
-    if errors > 0 {
-        ExitCode::GeneralError
+/// Turn a parsed path into a mirror location, creating an S3 client for remote paths
+async fn resolve_location(
+    parsed: ParsedPath,
+    alias_manager: &AliasManager,
+    side: &str,
+    formatter: &Formatter,
+) -> Result<Location, ExitCode> {
+    let path = match parsed {
+        ParsedPath::Local(dir) => return Ok(Location::Local(dir)),
+        ParsedPath::Remote(path) => path,
+    };
+
+    let alias = match alias_manager.get(&path.alias) {
+        Ok(a) => a,
+        Err(_) => {
+            formatter.error(&format!("Alias '{}' not found", path.alias));
+            return Err(ExitCode::NotFound);
+        }
+    };
+
+    let client = match S3Client::new(alias).await {
+        Ok(c) => c,
+        Err(e) => {
+            formatter.error(&format!("Failed to create {side} client: {e}"));
+            return Err(ExitCode::NetworkError);
+        }
+    };
+
+    Ok(Location::Remote {
+        client: Arc::new(client),
+        path,
+    })
+}
+
+/// Copy a single entry from source to target
+async fn copy_entry(
+    source: &Location,
+    target: &Location,
+    key: &str,
+    info: &FileInfo,
+) -> Result<(), rc_core::Error> {
+    match (source, target) {
+        (
+            Location::Remote {
+                client: source_client,
+                path: source_path,
+            },
+            Location::Remote {
+                client: target_client,
+                path: target_path,
+            },
+        ) => {
+            // Stream object content from source to target
+            let stream = source_client
+                .get_object_stream(&Location::remote_path(source_path, key))
+                .await?;
+            target_client
+                .put_object_stream(
+                    &Location::remote_path(target_path, key),
+                    stream.body,
+                    stream.info.content_type.as_deref(),
+                )
+                .await?;
+        }
+        (Location::Local(root), Location::Remote { client, path }) => {
+            let file = Location::local_path(root, key)?;
+            let target = Location::remote_path(path, key);
+            let content_type = mime_guess::from_path(&file)
+                .first()
+                .map(|m| m.essence_str().to_string());
+
+            let config = MultipartConfig::default();
+            if config.use_multipart(info.size.unwrap_or(0) as u64) {
+                multipart::upload_file(
+                    client,
+                    &target,
+                    &file,
+                    content_type.as_deref(),
+                    &config,
+                    &|_| {},
+                )
+                .await?;
+            } else {
+                client
+                    .put_object_file(&target, &file, content_type.as_deref())
+                    .await?;
+            }
+        }
+        (Location::Remote { client, path }, Location::Local(root)) => {
+            let file = Location::local_path(root, key)?;
+            if let Some(parent) = file.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+
+            let mut stream = client
+                .get_object_stream(&Location::remote_path(path, key))
+                .await?;
+            write_stream_to_file(&mut stream.body, &file).await?;
+
+            // Match the object's mtime so the next run sees the file as unchanged
+            if let Some(modified) = stream.info.last_modified.or(info.modified) {
+                let f = std::fs::File::options().write(true).open(&file)?;
+                f.set_modified(modified.into())?;
+            }
+        }
+        (Location::Local(_), Location::Local(_)) => {
+            return Err(rc_core::Error::General(
+                "Cannot mirror between two local paths".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Fill in ETags for local files whose size matches the other side
+///
+/// `local` is a listing of `root` from [`list_local_map`]. Each candidate file
+/// is hashed in the form of the other side's ETag: a plain MD5, or a
+/// multipart-style ETag using the part size inferred from its suffix.
+pub(crate) async fn apply_local_checksums(
+    root: &Path,
+    local: &mut HashMap<String, FileInfo>,
+    other: &HashMap<String, FileInfo>,
+) -> Result<(), rc_core::Error> {
+    for (key, info) in local.iter_mut() {
+        let Some(other_info) = other.get(key) else {
+            continue;
+        };
+        if info.size != other_info.size {
+            continue;
+        }
+
+        // A single-file listing is keyed by the file name
+        let path = if root.is_file() {
+            root.to_path_buf()
+        } else {
+            Location::local_path(root, key)?
+        };
+        let size = info.size.unwrap_or(0) as u64;
+        let remote_etag = other_info.etag.clone();
+
+        let etag = tokio::task::spawn_blocking(move || {
+            etag::local_etag_like(&path, size, remote_etag.as_deref())
+        })
+        .await
+        .map_err(|e| rc_core::Error::General(e.to_string()))??;
+        info.etag = Some(etag);
+    }
+
+    Ok(())
+}
+
+/// List local files, keyed by `/`-separated path relative to `root`
+///
+/// A missing root lists as empty, so a new mirror target can be created. A
+/// single file is keyed by its file name, matching the single-object case for
+/// remote paths. Without `recursive` only the files directly in `root` are
+/// listed.
+pub(crate) fn list_local_map(
+    root: &Path,
+    recursive: bool,
+) -> Result<HashMap<String, FileInfo>, rc_core::Error> {
+    let mut files = HashMap::new();
+
+    if !root.exists() {
+        return Ok(files);
+    }
+
+    let file_name = |path: &Path| {
+        path.file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+
+    let entries = if root.is_file() {
+        vec![(root.to_path_buf(), file_name(root))]
+    } else if recursive {
+        walk_dir(root, root)?
     } else {
-        ExitCode::Success
+        let mut entries = Vec::new();
+        for entry in std::fs::read_dir(root)? {
+            let path = entry?.path();
+            if path.is_file() {
+                let name = file_name(&path);
+                entries.push((path, name));
+            }
+        }
+        entries
+    };
+
+    for (path, relative) in entries {
+        let metadata = std::fs::metadata(&path)?;
+        files.insert(
+            relative.replace('\\', "/"),
+            FileInfo {
+                size: Some(metadata.len() as i64),
+                modified: metadata
+                    .modified()
+                    .ok()
+                    .and_then(|t| jiff::Timestamp::try_from(t).ok()),
+                etag: None,
+            },
+        );
     }
+
+    Ok(files)
 }
 
-async fn list_objects_map(
+/// List remote objects, keyed by `/`-separated key relative to `path`
+///
+/// An object whose key is exactly `path` is keyed by its file name, like a
+/// single local file. Without `recursive` only the objects directly under
+/// `path` are listed.
+pub(crate) async fn list_objects_map(
     client: &S3Client,
     path: &RemotePath,
+    recursive: bool,
 ) -> Result<HashMap<String, FileInfo>, rc_core::Error> {
     let mut objects = HashMap::new();
     let mut continuation_token: Option<String> = None;
@@ -382,9 +808,9 @@ async fn list_objects_map(
 
     loop {
         let options = ListOptions {
-            recursive: true,
+            recursive,
             max_keys: Some(1000),
-            continuation_token: continuation_token.clone(),
+            continuation_token: continuation_token.take(),
             ..Default::default()
         };
 
@@ -399,25 +825,30 @@ async fn list_objects_map(
             let relative_key = item.key.strip_prefix(base_prefix).unwrap_or(&item.key);
             let relative_key = relative_key.trim_start_matches('/').to_string();
 
-            if relative_key.is_empty() {
-                continue;
-            }
+            // Single object case
+            let relative_key = if relative_key.is_empty() {
+                Path::new(&item.key)
+                    .file_name()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or(item.key.clone())
+            } else {
+                relative_key
+            };
 
             objects.insert(
                 relative_key,
                 FileInfo {
                     size: item.size_bytes,
-                    modified: item.last_modified.map(|t| t.to_string()),
+                    modified: item.last_modified,
                     etag: item.etag,
                 },
             );
         }
 
-        if result.truncated {
-            continuation_token = result.continuation_token;
-        } else {
+        if !result.truncated || result.continuation_token.is_none() {
             break;
         }
+        continuation_token = result.continuation_token;
     }
 
     Ok(objects)
@@ -432,9 +863,15 @@ fn compare_objects_internal(
     // Check objects in source
     for (key, source_info) in source {
         if let Some(target_info) = target.get(key) {
-            // Object exists in both
+            // Object exists in both; without ETags on both sides (a local file is
+            // involved) fall back to checking whether the source is newer
             let is_same = source_info.size == target_info.size
-                && (source_info.etag == target_info.etag || source_info.etag.is_none());
+                && match (&source_info.etag, &target_info.etag) {
+                    (Some(source_etag), Some(target_etag)) => {
+                        etag::etags_match(source_etag, target_etag)
+                    }
+                    _ => !is_newer(source_info.modified, target_info.modified),
+                };
 
             let status = if is_same {
                 DiffStatus::Same
@@ -447,8 +884,8 @@ fn compare_objects_internal(
                 status,
                 first_size: source_info.size,
                 second_size: target_info.size,
-                first_modified: source_info.modified.clone(),
-                second_modified: target_info.modified.clone(),
+                first_modified: source_info.modified.map(|t| t.to_string()),
+                second_modified: target_info.modified.map(|t| t.to_string()),
             });
         } else {
             // Only in source
@@ -457,7 +894,7 @@ fn compare_objects_internal(
                 status: DiffStatus::OnlyFirst,
                 first_size: source_info.size,
                 second_size: None,
-                first_modified: source_info.modified.clone(),
+                first_modified: source_info.modified.map(|t| t.to_string()),
                 second_modified: None,
             });
         }
@@ -472,7 +909,7 @@ fn compare_objects_internal(
                 first_size: None,
                 second_size: target_info.size,
                 first_modified: None,
-                second_modified: target_info.modified.clone(),
+                second_modified: target_info.modified.map(|t| t.to_string()),
             });
         }
     }
@@ -481,6 +918,14 @@ fn compare_objects_internal(
     entries
 }
 
+/// Whether `source` is newer than `target`, at whole-second precision
+pub(crate) fn is_newer(source: Option<jiff::Timestamp>, target: Option<jiff::Timestamp>) -> bool {
+    match (source, target) {
+        (Some(s), Some(t)) => s.as_second() > t.as_second(),
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -611,6 +1056,149 @@ mod tests {
         assert_eq!(entries[0].status, DiffStatus::Different);
     }
 
+    #[test]
+    fn test_compare_local_uses_mtime() {
+        let older = jiff::Timestamp::from_second(1_700_000_000).ok();
+        let newer = jiff::Timestamp::from_second(1_700_000_100).ok();
+
+        let mut source = HashMap::new();
+        source.insert(
+            "same.txt".to_string(),
+            FileInfo {
+                size: Some(100),
+                modified: older,
+                etag: None,
+            },
+        );
+        source.insert(
+            "changed.txt".to_string(),
+            FileInfo {
+                size: Some(100),
+                modified: newer,
+                etag: None,
+            },
+        );
+
+        let mut target = HashMap::new();
+        for key in ["same.txt", "changed.txt"] {
+            target.insert(
+                key.to_string(),
+                FileInfo {
+                    size: Some(100),
+                    modified: older,
+                    etag: Some("abc".to_string()),
+                },
+            );
+        }
+
+        let entries = compare_objects_internal(&source, &target);
+        let same = entries.iter().find(|e| e.key == "same.txt").unwrap();
+        assert_eq!(same.status, DiffStatus::Same);
+        let changed = entries.iter().find(|e| e.key == "changed.txt").unwrap();
+        assert_eq!(changed.status, DiffStatus::Different);
+    }
+
+    #[tokio::test]
+    async fn test_checksum_overrides_mtime() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("same.txt"), b"hello").unwrap();
+        std::fs::write(dir.path().join("changed.txt"), b"hellO").unwrap();
+
+        let mut source = list_local_map(dir.path(), true).unwrap();
+        let mut target = HashMap::new();
+        for key in ["same.txt", "changed.txt"] {
+            target.insert(
+                key.to_string(),
+                FileInfo {
+                    size: Some(5),
+                    // Older than the local files, which would otherwise mark both as changed
+                    modified: jiff::Timestamp::from_second(0).ok(),
+                    etag: Some("5d41402abc4b2a76b9719d911017c592".to_string()),
+                },
+            );
+        }
+
+        apply_local_checksums(dir.path(), &mut source, &target)
+            .await
+            .unwrap();
+
+        let entries = compare_objects_internal(&source, &target);
+        let same = entries.iter().find(|e| e.key == "same.txt").unwrap();
+        assert_eq!(same.status, DiffStatus::Same);
+        let changed = entries.iter().find(|e| e.key == "changed.txt").unwrap();
+        assert_eq!(changed.status, DiffStatus::Different);
+    }
+
+    #[test]
+    fn test_list_local_map() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("sub")).unwrap();
+        std::fs::write(dir.path().join("a.txt"), b"hello").unwrap();
+        std::fs::write(dir.path().join("sub").join("b.txt"), b"hi").unwrap();
+
+        let files = list_local_map(dir.path(), true).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files["a.txt"].size, Some(5));
+        assert_eq!(files["sub/b.txt"].size, Some(2));
+        assert!(files["sub/b.txt"].modified.is_some());
+
+        let flat = list_local_map(dir.path(), false).unwrap();
+        assert_eq!(flat.len(), 1);
+        assert!(flat.contains_key("a.txt"));
+
+        let single = list_local_map(&dir.path().join("a.txt"), true).unwrap();
+        assert_eq!(single.len(), 1);
+        assert_eq!(single["a.txt"].size, Some(5));
+
+        let missing = list_local_map(&dir.path().join("missing"), true).unwrap();
+        assert!(missing.is_empty());
+    }
+
+    #[test]
+    fn test_location_paths() {
+        let remote = RemotePath::new("a", "bucket", "prefix");
+        assert_eq!(
+            Location::remote_path(&remote, "sub/file.txt").key,
+            "prefix/sub/file.txt"
+        );
+
+        let root = Path::new("/tmp/out");
+        assert_eq!(
+            Location::local_path(root, "sub/file.txt").unwrap(),
+            Path::new("/tmp/out").join("sub").join("file.txt")
+        );
+
+        for key in [
+            "../../.ssh/authorized_keys",
+            "sub/../../escape",
+            "/etc/passwd",
+            "sub//file.txt",
+            "./file.txt",
+            "sub/",
+            "",
+        ] {
+            assert!(Location::local_path(root, key).is_err(), "{key}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_local_signals_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let (rx, _watcher) = watch_local(dir.path()).unwrap();
+        let mut events = Some(rx);
+        let mut ticker = tokio::time::interval(Duration::from_secs(3600));
+
+        std::fs::write(dir.path().join("new.txt"), b"data").unwrap();
+
+        let changed = tokio::time::timeout(
+            Duration::from_secs(10),
+            next_change(&mut events, &mut ticker),
+        )
+        .await
+        .expect("filesystem event");
+        assert!(changed);
+    }
+
     #[test]
     fn test_mirror_args_defaults() {
         let args = MirrorArgs {
@@ -619,8 +1207,11 @@ mod tests {
             remove: false,
             overwrite: false,
             dry_run: false,
+            checksum: false,
             parallel: 4,
             quiet: false,
+            watch: false,
+            interval: 10,
         };
         assert_eq!(args.parallel, 4);
         assert!(!args.remove);
@@ -5,18 +5,23 @@
 //! in the command implementation template.
 
 use clap::{Parser, Subcommand};
+use rc_core::config::{COLOR_VALUES, OUTPUT_VALUES, parse_bool};
+use rc_core::{ConfigManager, Defaults};
 
 use crate::exit_code::ExitCode;
-use crate::output::OutputConfig;
+use crate::output::{Formatter, OutputConfig};
 
 mod admin;
 mod alias;
 mod cat;
+mod common;
 mod completions;
+mod config;
 pub mod cp;
 pub mod diff;
 mod find;
 mod head;
+mod legalhold;
 mod ls;
 mod mb;
 mod mirror;
@@ -24,12 +29,16 @@ mod mv;
 mod pipe;
 mod quota;
 mod rb;
+mod retention;
 mod rm;
 mod share;
+mod sql;
 mod stat;
 mod tag;
 mod tree;
+mod undo;
 mod version;
+mod watch;
 
 /// rc - Rust S3 CLI Client
 ///
@@ -44,10 +53,18 @@ pub struct Cli {
     #[arg(long, global = true, default_value = "false")]
     pub json: bool,
 
+    /// Output format: human or json (overrides RC_OUTPUT and config)
+    #[arg(long, global = true, value_name = "FORMAT", value_parser = OUTPUT_VALUES.to_vec(), conflicts_with = "json")]
+    pub output: Option<String>,
+
     /// Disable colored output
     #[arg(long, global = true, default_value = "false")]
     pub no_color: bool,
 
+    /// Color mode: auto, always or never (overrides RC_COLOR and config)
+    #[arg(long, global = true, value_name = "WHEN", value_parser = COLOR_VALUES.to_vec(), conflicts_with = "no_color")]
+    pub color: Option<String>,
+
     /// Disable progress bar
     #[arg(long, global = true, default_value = "false")]
     pub no_progress: bool,
@@ -70,6 +87,10 @@ pub enum Commands {
     #[command(subcommand)]
     Alias(alias::AliasCommands),
 
+    /// Show and change default settings
+    #[command(subcommand)]
+    Config(config::ConfigCommands),
+
     /// Manage IAM users, policies, groups, and service accounts
     #[command(subcommand)]
     Admin(admin::AdminCommands),
@@ -135,28 +156,121 @@ pub enum Commands {
     #[command(subcommand)]
     Quota(quota::QuotaCommands),
 
+    /// Reverse recent deletes and overwrites in a versioned bucket
+    Undo(undo::UndoArgs),
+
+    /// Manage object retention
+    #[command(subcommand)]
+    Retention(retention::RetentionCommands),
+
+    /// Manage object legal holds
+    #[command(subcommand)]
+    Legalhold(legalhold::LegalHoldCommands),
+
+    /// Watch for object events
+    Watch(watch::WatchArgs),
+
+    /// Run S3 Select queries
+    Sql(sql::SqlArgs),
+
     // Phase 6: Utilities
     /// Generate shell completion scripts
     Completions(completions::CompletionsArgs),
-    // /// Manage object retention
-    // Retention(retention::RetentionArgs),
-    // /// Watch for object events
-    // Watch(watch::WatchArgs),
-    // /// Run S3 Select queries
-    // Sql(sql::SqlArgs),
 }
 
-/// Execute the CLI command and return an exit code
-pub async fn execute(cli: Cli) -> ExitCode {
+/// Resolve output settings with precedence CLI flag > environment variable > config
+///
+/// Returns the output configuration and whether colors are forced on.
+fn resolve_output_config(
+    cli: &Cli,
+    defaults: &Defaults,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<(OutputConfig, bool), String> {
+    let output = if cli.json {
+        "json".to_string()
+    } else if let Some(output) = &cli.output {
+        output.clone()
+    } else if let Some(output) = env("RC_OUTPUT") {
+        output.trim().to_ascii_lowercase()
+    } else {
+        defaults.output.clone()
+    };
+    if !OUTPUT_VALUES.contains(&output.as_str()) {
+        return Err(format!(
+            "Invalid output format '{output}'. Expected one of: {}",
+            OUTPUT_VALUES.join(", ")
+        ));
+    }
+
+    let color = if cli.no_color {
+        "never".to_string()
+    } else if let Some(color) = &cli.color {
+        color.clone()
+    } else if let Some(color) = env("RC_COLOR") {
+        color.trim().to_ascii_lowercase()
+    } else if env("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        "never".to_string()
+    } else {
+        defaults.color.clone()
+    };
+    if !COLOR_VALUES.contains(&color.as_str()) {
+        return Err(format!(
+            "Invalid color mode '{color}'. Expected one of: {}",
+            COLOR_VALUES.join(", ")
+        ));
+    }
+
+    let progress = if cli.no_progress {
+        false
+    } else if let Some(progress) = env("RC_PROGRESS") {
+        parse_bool(&progress).ok_or_else(|| {
+            format!("Invalid RC_PROGRESS value '{progress}'. Expected true or false")
+        })?
+    } else {
+        defaults.progress
+    };
+
     let output_config = OutputConfig {
-        json: cli.json,
-        no_color: cli.no_color,
-        no_progress: cli.no_progress,
+        json: output == "json",
+        no_color: color == "never",
+        no_progress: !progress,
         quiet: cli.quiet,
     };
+    Ok((output_config, color == "always"))
+}
+
+/// Execute the CLI command and return an exit code
+pub async fn execute(cli: Cli) -> ExitCode {
+    // A missing or unreadable config falls back to the built-in defaults and
+    // invalid values in it are replaced on load, so only a bad flag or
+    // environment variable can fail here; commands that need the config
+    // report load errors themselves
+    let defaults = match ConfigManager::new().and_then(|manager| manager.load()) {
+        Ok(config) => config.defaults,
+        Err(e) => {
+            tracing::debug!(error = %e, "Using built-in defaults");
+            Defaults::default()
+        }
+    };
+
+    let output_config =
+        match resolve_output_config(&cli, &defaults, |name| std::env::var(name).ok()) {
+            Ok((output_config, force_color)) => {
+                if force_color {
+                    console::set_colors_enabled(true);
+                    console::set_colors_enabled_stderr(true);
+                }
+                output_config
+            }
+            Err(e) => {
+                Formatter::new(OutputConfig::default()).error(&e);
+                return ExitCode::UsageError;
+            }
+        };
 
     match cli.command {
         Commands::Alias(cmd) => alias::execute(cmd, output_config).await,
+        Commands::Config(cmd) => config::execute(cmd, output_config).await,
         Commands::Admin(cmd) => admin::execute(cmd, output_config).await,
         Commands::Ls(args) => ls::execute(args, output_config).await,
         Commands::Mb(args) => mb::execute(args, output_config).await,
@@ -180,6 +294,92 @@ pub async fn execute(cli: Cli) -> ExitCode {
         Commands::Quota(cmd) => {
             quota::execute(quota::QuotaArgs { command: cmd }, output_config).await
         }
+        Commands::Undo(args) => undo::execute(args, output_config).await,
+        Commands::Retention(cmd) => {
+            retention::execute(retention::RetentionArgs { command: cmd }, output_config).await
+        }
+        Commands::Legalhold(cmd) => {
+            legalhold::execute(legalhold::LegalHoldArgs { command: cmd }, output_config).await
+        }
+        Commands::Watch(args) => watch::execute(args, output_config).await,
+        Commands::Sql(args) => sql::execute(args, output_config).await,
         Commands::Completions(args) => completions::execute(args),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(args: &[&str], env: &[(&str, &str)], defaults: &Defaults) -> (OutputConfig, bool) {
+        let cli = Cli::try_parse_from([&["rc"], args, &["completions", "bash"]].concat()).unwrap();
+        resolve_output_config(&cli, defaults, |name| {
+            env.iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, v)| v.to_string())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_output_precedence() {
+        let mut defaults = Defaults::default();
+        defaults.set("output", "json").unwrap();
+
+        // Config default applies without flags or environment
+        assert!(resolve(&[], &[], &defaults).0.json);
+        // Environment overrides config
+        assert!(!resolve(&[], &[("RC_OUTPUT", "human")], &defaults).0.json);
+        // CLI overrides environment
+        assert!(
+            resolve(&["--json"], &[("RC_OUTPUT", "human")], &Defaults::default())
+                .0
+                .json
+        );
+        assert!(
+            !resolve(&["--output", "human"], &[("RC_OUTPUT", "json")], &defaults)
+                .0
+                .json
+        );
+    }
+
+    #[test]
+    fn test_color_and_progress_precedence() {
+        let mut defaults = Defaults::default();
+        defaults.set("color", "never").unwrap();
+        defaults.set("progress", "false").unwrap();
+
+        let (config, force_color) = resolve(&[], &[], &defaults);
+        assert!(config.no_color);
+        assert!(!force_color);
+        assert!(config.no_progress);
+
+        let (config, force_color) = resolve(
+            &[],
+            &[("RC_COLOR", "always"), ("RC_PROGRESS", "1")],
+            &defaults,
+        );
+        assert!(!config.no_color);
+        assert!(force_color);
+        assert!(!config.no_progress);
+
+        let (config, _) = resolve(&[], &[("NO_COLOR", "1")], &Defaults::default());
+        assert!(config.no_color);
+
+        let (config, force_color) = resolve(
+            &["--color", "auto", "--no-progress"],
+            &[("RC_COLOR", "never"), ("RC_PROGRESS", "true")],
+            &defaults,
+        );
+        assert!(!config.no_color);
+        assert!(!force_color);
+        assert!(config.no_progress);
+    }
+
+    #[test]
+    fn test_invalid_environment_value() {
+        let cli = Cli::try_parse_from(["rc", "completions", "bash"]).unwrap();
+        let env = |name: &str| (name == "RC_OUTPUT").then(|| "yaml".to_string());
+        assert!(resolve_output_config(&cli, &Defaults::default(), env).is_err());
+    }
+}
@@ -3,7 +3,7 @@
 //! Moves objects between locations (copy + delete).
 
 use clap::Args;
-use rc_core::{AliasManager, ObjectStore as _, ParsedPath, RemotePath, parse_path};
+use rc_core::{AliasManager, DeleteOptions, ObjectStore as _, ParsedPath, RemotePath, parse_path};
 use rc_s3::S3Client;
 use serde::Serialize;
 
@@ -102,6 +102,8 @@ async fn move_local_to_s3(
         dry_run: args.dry_run,
         storage_class: None,
         content_type: None,
+        rewind: None,
+        version_id: None,
     };
 
     let cp_result = cp::execute(
@@ -156,6 +158,8 @@ async fn move_s3_to_local(
         dry_run: args.dry_run,
         storage_class: None,
         content_type: None,
+        rewind: None,
+        version_id: None,
     };
 
     let cp_result = cp::execute(
@@ -198,7 +202,7 @@ async fn move_s3_to_local(
             }
         };
 
-        if let Err(e) = client.delete_object(src).await {
+        if let Err(e) = client.delete_object(src, &DeleteOptions::default()).await {
             formatter.error(&format!("Failed to delete source: {e}"));
             return ExitCode::NetworkError;
         }
@@ -213,12 +217,6 @@ async fn move_s3_to_s3(
     args: &MvArgs,
     formatter: &Formatter,
 ) -> ExitCode {
-    // For S3-to-S3, we need same alias for server-side copy
-    if src.alias != dst.alias {
-        formatter.error("Cross-alias S3-to-S3 move not yet supported.");
-        return ExitCode::UnsupportedFeature;
-    }
-
     let alias_manager = match AliasManager::new() {
         Ok(am) => am,
         Err(e) => {
@@ -243,6 +241,27 @@ async fn move_s3_to_s3(
         }
     };
 
+    // Different aliases need a separate destination client; data is streamed between them
+    let dst_client = if src.alias == dst.alias {
+        None
+    } else {
+        let alias = match alias_manager.get(&dst.alias) {
+            Ok(a) => a,
+            Err(_) => {
+                formatter.error(&format!("Alias '{}' not found", dst.alias));
+                return ExitCode::NotFound;
+            }
+        };
+
+        match S3Client::new(alias).await {
+            Ok(c) => Some(c),
+            Err(e) => {
+                formatter.error(&format!("Failed to create S3 client: {e}"));
+                return ExitCode::NetworkError;
+            }
+        }
+    };
+
     let src_display = format!("{}/{}/{}", src.alias, src.bucket, src.key);
     let dst_display = format!("{}/{}/{}", dst.alias, dst.bucket, dst.key);
 
@@ -252,10 +271,18 @@ async fn move_s3_to_s3(
     }
 
     // Copy
-    match client.copy_object(src, dst).await {
+    let result = match &dst_client {
+        Some(dst_client) => {
+            crate::commands::cp::copy_across_clients(&client, src, None, dst_client, dst, formatter)
+                .await
+        }
+        None => client.copy_object(src, dst).await,
+    };
+
+    match result {
         Ok(info) => {
             // Delete source
-            if let Err(e) = client.delete_object(src).await {
+            if let Err(e) = client.delete_object(src, &DeleteOptions::default()).await {
                 formatter.error(&format!("Copied but failed to delete source: {e}"));
                 return ExitCode::GeneralError;
             }
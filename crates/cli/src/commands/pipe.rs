@@ -6,7 +6,6 @@ use clap::Args;
 use rc_core::{AliasManager, ObjectStore as _, RemotePath};
 use rc_s3::S3Client;
 use serde::Serialize;
-use std::io::Read;
 
 use crate::exit_code::ExitCode;
 use crate::output::{Formatter, OutputConfig};
@@ -80,23 +79,17 @@ pub async fn execute(args: PipeArgs, output_config: OutputConfig) -> ExitCode {
         }
     };
 
-    // Read from stdin
-    let mut buffer = Vec::new();
-    if let Err(e) = std::io::stdin().read_to_end(&mut buffer) {
-        formatter.error(&format!("Failed to read from stdin: {e}"));
-        return ExitCode::GeneralError;
-    }
-
-    let size = buffer.len() as i64;
     let target = RemotePath::new(&alias_name, &bucket, &key);
     let target_display = format!("{alias_name}/{bucket}/{key}");
 
-    // Upload
+    // Stream stdin to the target without buffering it whole
+    let stdin: rc_core::ByteReader = Box::pin(tokio::io::stdin());
     match client
-        .put_object(&target, buffer, Some(&args.content_type))
+        .put_object_stream(&target, stdin, Some(&args.content_type))
         .await
     {
         Ok(info) => {
+            let size = info.size_bytes.unwrap_or(0);
             if formatter.is_json() {
                 let output = PipeOutput {
                     status: "success",
@@ -3,7 +3,10 @@
 //! Removes a bucket from the specified storage service.
 
 use clap::Args;
-use rc_core::{AliasManager, ObjectStore as _};
+use rc_core::{
+    AliasManager, DeleteTarget, ListOptions, MultipartUploadInfo, ObjectStore as _, RemotePath,
+    VersionMarker,
+};
 use rc_s3::S3Client;
 use serde::Serialize;
 
@@ -16,11 +19,12 @@ pub struct RbArgs {
     /// Target path (alias/bucket)
     pub target: String,
 
-    /// Force remove even if bucket is not empty (deletes all objects first)
+    /// Force remove even if bucket is not empty (deletes all objects, versions
+    /// and incomplete uploads first)
     #[arg(long)]
     pub force: bool,
 
-    /// Remove bucket even if it has incomplete multipart uploads
+    /// Abort incomplete multipart uploads instead of refusing to remove the bucket
     #[arg(long)]
     pub dangerous: bool,
 }
@@ -85,8 +89,59 @@ pub async fn execute(args: RbArgs, output_config: OutputConfig) -> ExitCode {
         }
     }
 
-    // TODO: If --force is specified, delete all objects first
-    // This will be implemented in Phase 3 when we have delete_object
+    // Empty the bucket first when forced
+    let mut message = None;
+    if args.force {
+        match empty_bucket(&client, &alias_name, &bucket).await {
+            Ok(removed) => {
+                if removed.objects > 0 || removed.uploads > 0 {
+                    message = Some(removed.to_string());
+                }
+            }
+            Err(e) => {
+                formatter.error(&format!(
+                    "Failed to empty bucket '{alias_name}/{bucket}': {e}"
+                ));
+                let err_str = e.to_string();
+                return if err_str.contains("AccessDenied") {
+                    ExitCode::AuthError
+                } else {
+                    ExitCode::NetworkError
+                };
+            }
+        }
+    } else {
+        // Incomplete uploads would be orphaned, so only drop them when asked to
+        let root = RemotePath::new(&alias_name, &bucket, "");
+        match client.list_multipart_uploads(&root).await {
+            Ok(uploads) if uploads.is_empty() => {}
+            Ok(uploads) if !args.dangerous => {
+                formatter.error(&format!(
+                    "Bucket '{alias_name}/{bucket}' has {} incomplete upload(s). Use --dangerous to abort them and remove the bucket.",
+                    uploads.len()
+                ));
+                return ExitCode::Conflict;
+            }
+            Ok(uploads) => match abort_uploads(&client, &root, &uploads).await {
+                Ok(count) => {
+                    message = Some(
+                        EmptiedBucket {
+                            objects: 0,
+                            uploads: count,
+                        }
+                        .to_string(),
+                    );
+                }
+                Err(e) => {
+                    formatter.error(&format!("Failed to abort incomplete uploads: {e}"));
+                    return ExitCode::NetworkError;
+                }
+            },
+            Err(e) => {
+                tracing::debug!("Failed to list incomplete uploads for {bucket}: {e}");
+            }
+        }
+    }
 
     // Delete the bucket
     match client.delete_bucket(&bucket).await {
@@ -95,10 +150,13 @@ pub async fn execute(args: RbArgs, output_config: OutputConfig) -> ExitCode {
                 let output = RbOutput {
                     status: "success",
                     bucket: bucket.clone(),
-                    message: None,
+                    message,
                 };
                 formatter.json(&output);
             } else {
+                if let Some(message) = &message {
+                    formatter.println(&format!("{message}."));
+                }
                 formatter.success(&format!(
                     "Bucket '{alias_name}/{bucket}' removed successfully."
                 ));
@@ -110,7 +168,7 @@ pub async fn execute(args: RbArgs, output_config: OutputConfig) -> ExitCode {
             if err_str.contains("BucketNotEmpty") {
                 if args.force {
                     formatter.error(&format!(
-                        "Bucket '{alias_name}/{bucket}' is not empty. --force with object deletion not yet implemented."
+                        "Bucket '{alias_name}/{bucket}' is still not empty after removing its contents. Objects may have been written concurrently or be protected by object lock."
                     ));
                 } else {
                     formatter.error(&format!(
@@ -134,6 +192,118 @@ pub async fn execute(args: RbArgs, output_config: OutputConfig) -> ExitCode {
     }
 }
 
+/// Maximum number of keys per DeleteObjects request
+const DELETE_BATCH_SIZE: usize = 1000;
+
+/// What was removed while emptying a bucket
+#[derive(Debug, Default, PartialEq)]
+struct EmptiedBucket {
+    objects: usize,
+    uploads: usize,
+}
+
+impl std::fmt::Display for EmptiedBucket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.objects, self.uploads) {
+            (objects, 0) => write!(f, "Removed {objects} object(s)"),
+            (0, uploads) => write!(f, "Aborted {uploads} incomplete upload(s)"),
+            (objects, uploads) => write!(
+                f,
+                "Removed {objects} object(s) and aborted {uploads} incomplete upload(s)"
+            ),
+        }
+    }
+}
+
+/// Delete every object, version, delete marker and incomplete upload in a bucket
+///
+/// Buckets that have ever had versioning enabled are emptied version by
+/// version, since deleting by key alone would only add delete markers.
+async fn empty_bucket(
+    client: &S3Client,
+    alias_name: &str,
+    bucket: &str,
+) -> rc_core::Result<EmptiedBucket> {
+    let mut removed = EmptiedBucket::default();
+    let root = RemotePath::new(alias_name, bucket, "");
+
+    // None means versioning was never configured on this bucket
+    let versioned = client.get_versioning(bucket).await?.is_some();
+
+    if versioned {
+        let mut marker: Option<VersionMarker> = None;
+        loop {
+            let page = client
+                .list_object_versions_page(&root, marker.as_ref(), DELETE_BATCH_SIZE as i32)
+                .await?;
+
+            let targets: Vec<DeleteTarget> = page
+                .versions
+                .into_iter()
+                .map(|v| DeleteTarget::version(v.key, v.version_id))
+                .collect();
+            if !targets.is_empty() {
+                removed.objects += client.delete_objects(bucket, targets, false).await?.len();
+            }
+
+            match page.next {
+                Some(next) => marker = Some(next),
+                None => break,
+            }
+        }
+    }
+
+    // Also covers objects a versioning-unaware listing would still return
+    let mut continuation_token: Option<String> = None;
+    loop {
+        let options = ListOptions {
+            recursive: true,
+            max_keys: Some(DELETE_BATCH_SIZE as i32),
+            continuation_token: continuation_token.take(),
+            ..Default::default()
+        };
+        let result = client.list_objects(&root, options).await?;
+
+        let targets: Vec<DeleteTarget> = result
+            .items
+            .into_iter()
+            .filter(|item| !item.is_dir)
+            .map(|item| item.key.into())
+            .collect();
+        for chunk in targets.chunks(DELETE_BATCH_SIZE) {
+            removed.objects += client
+                .delete_objects(bucket, chunk.to_vec(), false)
+                .await?
+                .len();
+        }
+
+        if !result.truncated || result.continuation_token.is_none() {
+            break;
+        }
+        continuation_token = result.continuation_token;
+    }
+
+    let uploads = client.list_multipart_uploads(&root).await?;
+    removed.uploads = abort_uploads(client, &root, &uploads).await?;
+
+    Ok(removed)
+}
+
+/// Abort the given incomplete uploads, returning how many were aborted
+async fn abort_uploads(
+    client: &S3Client,
+    root: &RemotePath,
+    uploads: &[MultipartUploadInfo],
+) -> rc_core::Result<usize> {
+    for upload in uploads {
+        let path = RemotePath::new(&root.alias, &root.bucket, &upload.key);
+        client
+            .abort_multipart_upload(&path, &upload.upload_id)
+            .await?;
+    }
+    Ok(uploads.len())
+}
+
 /// Parse rb target path into (alias, bucket)
 fn parse_rb_path(path: &str) -> Result<(String, String), String> {
     let path = path.trim_end_matches('/');
@@ -187,4 +357,28 @@ mod tests {
     fn test_parse_rb_path_empty() {
         assert!(parse_rb_path("").is_err());
     }
+
+    #[test]
+    fn test_emptied_bucket_display() {
+        let removed = EmptiedBucket {
+            objects: 3,
+            uploads: 0,
+        };
+        assert_eq!(removed.to_string(), "Removed 3 object(s)");
+
+        let removed = EmptiedBucket {
+            objects: 0,
+            uploads: 2,
+        };
+        assert_eq!(removed.to_string(), "Aborted 2 incomplete upload(s)");
+
+        let removed = EmptiedBucket {
+            objects: 1,
+            uploads: 2,
+        };
+        assert_eq!(
+            removed.to_string(),
+            "Removed 1 object(s) and aborted 2 incomplete upload(s)"
+        );
+    }
 }
@@ -0,0 +1,660 @@
+//! retention command - Manage object lock retention
+//!
+//! Set, clear, or show the retention of objects, and the default retention
+//! applied to new objects in an object lock enabled bucket. A path naming
+//! only a bucket targets its default retention unless `--recursive` is given.
+
+use clap::{Args, Subcommand};
+use jiff::tz::TimeZone;
+use jiff::{Span, Timestamp};
+use rc_core::{
+    DefaultRetention, ObjectRetention, ObjectStore as _, RemotePath, RetentionMode,
+    RetentionValidity,
+};
+use rc_s3::S3Client;
+use serde::Serialize;
+
+use crate::commands::common::{exit_code_from_error, feature_client, list_object_keys};
+use crate::exit_code::ExitCode;
+use crate::output::{Formatter, OutputConfig};
+
+/// Manage object retention
+#[derive(Args, Debug)]
+pub struct RetentionArgs {
+    #[command(subcommand)]
+    pub command: RetentionCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RetentionCommands {
+    /// Lock objects, or set the default retention of a bucket
+    Set(SetRetentionArgs),
+
+    /// Remove the retention of objects, or the default retention of a bucket
+    Clear(RetentionPathArgs),
+
+    /// Show the retention of objects, or the object lock settings of a bucket
+    Info(RetentionPathArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct RetentionPathArgs {
+    /// Object, prefix or bucket (alias/bucket[/key])
+    pub path: String,
+
+    /// Apply to every object under the prefix
+    #[arg(short, long)]
+    pub recursive: bool,
+
+    /// Apply to a specific object version
+    #[arg(long, conflicts_with = "recursive")]
+    pub version_id: Option<String>,
+
+    /// Force operation even if capability detection fails
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct SetRetentionArgs {
+    /// Object, prefix or bucket (alias/bucket[/key])
+    pub path: String,
+
+    /// Retention mode (GOVERNANCE or COMPLIANCE)
+    #[arg(long, value_parser = parse_mode)]
+    pub mode: RetentionMode,
+
+    /// How long to retain objects (e.g., 30d, 1y)
+    #[arg(long, value_parser = parse_validity)]
+    pub validity: RetentionValidity,
+
+    /// Apply to every object under the prefix
+    #[arg(short, long)]
+    pub recursive: bool,
+
+    /// Apply to a specific object version
+    #[arg(long, conflicts_with = "recursive")]
+    pub version_id: Option<String>,
+
+    /// Bypass governance retention when shortening it
+    #[arg(long)]
+    pub bypass: bool,
+
+    /// Force operation even if capability detection fails
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RetentionTarget {
+    /// Default retention of a bucket
+    Bucket { alias: String, bucket: String },
+
+    /// A single object
+    Object {
+        alias: String,
+        bucket: String,
+        key: String,
+    },
+
+    /// Every object under a prefix
+    Prefix {
+        alias: String,
+        bucket: String,
+        prefix: String,
+    },
+}
+
+impl RetentionTarget {
+    fn alias_name(&self) -> &str {
+        match self {
+            Self::Bucket { alias, .. }
+            | Self::Object { alias, .. }
+            | Self::Prefix { alias, .. } => alias,
+        }
+    }
+
+    fn bucket_name(&self) -> &str {
+        match self {
+            Self::Bucket { bucket, .. }
+            | Self::Object { bucket, .. }
+            | Self::Prefix { bucket, .. } => bucket,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ObjectRetentionEntry {
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mode: Option<RetentionMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retain_until: Option<Timestamp>,
+}
+
+#[derive(Debug, Serialize)]
+struct RetentionOutput {
+    status: &'static str,
+    objects: Vec<ObjectRetentionEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    failed: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct BucketRetentionOutput {
+    bucket: String,
+    object_lock: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mode: Option<RetentionMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    validity: Option<String>,
+}
+
+/// Execute the retention command
+pub async fn execute(args: RetentionArgs, output_config: OutputConfig) -> ExitCode {
+    match args.command {
+        RetentionCommands::Set(set_args) => execute_set(set_args, output_config).await,
+        RetentionCommands::Clear(path_args) => execute_clear(path_args, output_config).await,
+        RetentionCommands::Info(path_args) => execute_info(path_args, output_config).await,
+    }
+}
+
+async fn execute_set(args: SetRetentionArgs, output_config: OutputConfig) -> ExitCode {
+    let formatter = Formatter::new(output_config);
+
+    let target = match resolve_target(&args.path, args.recursive, args.version_id.as_deref()) {
+        Ok(target) => target,
+        Err(error) => {
+            formatter.error(&error);
+            return ExitCode::UsageError;
+        }
+    };
+
+    let client = match setup_client(&target, args.force, &formatter).await {
+        Ok(client) => client,
+        Err(code) => return code,
+    };
+
+    if let RetentionTarget::Bucket { alias, bucket } = &target {
+        let default = DefaultRetention {
+            mode: args.mode,
+            validity: args.validity,
+        };
+        if let Err(error) = client.set_default_retention(bucket, Some(&default)).await {
+            formatter.error(&format!("Failed to set default retention: {error}"));
+            return exit_code_from_error(&error);
+        }
+
+        if formatter.is_json() {
+            formatter.json(&BucketRetentionOutput {
+                bucket: format!("{alias}/{bucket}"),
+                object_lock: true,
+                mode: Some(default.mode),
+                validity: Some(default.validity.to_string()),
+            });
+        } else {
+            formatter.success(&format!(
+                "Default retention of '{alias}/{bucket}' set to {} for {}",
+                default.mode, default.validity
+            ));
+        }
+        return ExitCode::Success;
+    }
+
+    let retain_until = match retain_until(Timestamp::now(), args.validity) {
+        Ok(at) => at,
+        Err(error) => {
+            formatter.error(&error);
+            return ExitCode::UsageError;
+        }
+    };
+    let retention = ObjectRetention {
+        mode: args.mode,
+        retain_until,
+    };
+
+    apply_to_objects(
+        &client,
+        &target,
+        args.version_id.as_deref(),
+        Some(&retention),
+        args.bypass,
+        &formatter,
+    )
+    .await
+}
+
+async fn execute_clear(args: RetentionPathArgs, output_config: OutputConfig) -> ExitCode {
+    let formatter = Formatter::new(output_config);
+
+    let target = match resolve_target(&args.path, args.recursive, args.version_id.as_deref()) {
+        Ok(target) => target,
+        Err(error) => {
+            formatter.error(&error);
+            return ExitCode::UsageError;
+        }
+    };
+
+    let client = match setup_client(&target, args.force, &formatter).await {
+        Ok(client) => client,
+        Err(code) => return code,
+    };
+
+    if let RetentionTarget::Bucket { alias, bucket } = &target {
+        if let Err(error) = client.set_default_retention(bucket, None).await {
+            formatter.error(&format!("Failed to clear default retention: {error}"));
+            return exit_code_from_error(&error);
+        }
+
+        if formatter.is_json() {
+            formatter.json(&BucketRetentionOutput {
+                bucket: format!("{alias}/{bucket}"),
+                object_lock: true,
+                mode: None,
+                validity: None,
+            });
+        } else {
+            formatter.success(&format!("Default retention of '{alias}/{bucket}' cleared"));
+        }
+        return ExitCode::Success;
+    }
+
+    // Governance retention can only be removed by bypassing it; compliance
+    // retention is rejected by the server regardless
+    apply_to_objects(
+        &client,
+        &target,
+        args.version_id.as_deref(),
+        None,
+        true,
+        &formatter,
+    )
+    .await
+}
+
+async fn execute_info(args: RetentionPathArgs, output_config: OutputConfig) -> ExitCode {
+    let formatter = Formatter::new(output_config);
+
+    let target = match resolve_target(&args.path, args.recursive, args.version_id.as_deref()) {
+        Ok(target) => target,
+        Err(error) => {
+            formatter.error(&error);
+            return ExitCode::UsageError;
+        }
+    };
+
+    let client = match setup_client(&target, args.force, &formatter).await {
+        Ok(client) => client,
+        Err(code) => return code,
+    };
+
+    if let RetentionTarget::Bucket { alias, bucket } = &target {
+        let config = match client.get_object_lock_config(bucket).await {
+            Ok(config) => config.unwrap_or_default(),
+            Err(error) => {
+                formatter.error(&format!("Failed to get object lock configuration: {error}"));
+                return exit_code_from_error(&error);
+            }
+        };
+
+        if formatter.is_json() {
+            formatter.json(&BucketRetentionOutput {
+                bucket: format!("{alias}/{bucket}"),
+                object_lock: config.enabled,
+                mode: config.default_retention.map(|r| r.mode),
+                validity: config.default_retention.map(|r| r.validity.to_string()),
+            });
+        } else if !config.enabled {
+            formatter.println(&format!(
+                "Object lock is not enabled on '{alias}/{bucket}'."
+            ));
+        } else {
+            match config.default_retention {
+                Some(default) => formatter.println(&format!(
+                    "Default retention of '{alias}/{bucket}': {} for {}",
+                    default.mode, default.validity
+                )),
+                None => formatter.println(&format!(
+                    "Object lock is enabled on '{alias}/{bucket}' without a default retention."
+                )),
+            }
+        }
+        return ExitCode::Success;
+    }
+
+    let keys = match object_keys(&client, &target, &formatter).await {
+        Ok(keys) => keys,
+        Err(code) => return code,
+    };
+
+    let (alias, bucket) = (target.alias_name(), target.bucket_name());
+    let mut objects = Vec::new();
+    let mut failed = Vec::new();
+    let mut last_error = None;
+
+    for key in keys {
+        let path = RemotePath::new(alias, bucket, &key);
+        let full_path = format!("{alias}/{bucket}/{key}");
+        match client
+            .get_object_retention(&path, args.version_id.as_deref())
+            .await
+        {
+            Ok(retention) => {
+                if !formatter.is_json() {
+                    let styled = formatter.style_file(&full_path);
+                    match &retention {
+                        Some(r) => formatter.println(&format!(
+                            "{styled}  {}  until {}",
+                            r.mode,
+                            formatter.style_date(&r.retain_until.to_string())
+                        )),
+                        None => formatter.println(&format!("{styled}  no retention")),
+                    }
+                }
+                objects.push(ObjectRetentionEntry {
+                    path: full_path,
+                    version_id: args.version_id.clone(),
+                    mode: retention.as_ref().map(|r| r.mode),
+                    retain_until: retention.map(|r| r.retain_until),
+                });
+            }
+            Err(error) => {
+                formatter.error(&format!("Failed to get retention of {full_path}: {error}"));
+                failed.push(full_path);
+                last_error = Some(error);
+            }
+        }
+    }
+
+    if formatter.is_json() {
+        formatter.json(&RetentionOutput {
+            status: status_name(&failed),
+            objects,
+            failed,
+        });
+    }
+
+    match last_error {
+        Some(error) => exit_code_from_error(&error),
+        None => ExitCode::Success,
+    }
+}
+
+/// Set (`Some`) or clear (`None`) the retention of every object in `target`
+async fn apply_to_objects(
+    client: &S3Client,
+    target: &RetentionTarget,
+    version_id: Option<&str>,
+    retention: Option<&ObjectRetention>,
+    bypass_governance: bool,
+    formatter: &Formatter,
+) -> ExitCode {
+    let keys = match object_keys(client, target, formatter).await {
+        Ok(keys) => keys,
+        Err(code) => return code,
+    };
+
+    let (alias, bucket) = (target.alias_name(), target.bucket_name());
+    let mut objects = Vec::new();
+    let mut failed = Vec::new();
+    let mut last_error = None;
+
+    for key in keys {
+        let path = RemotePath::new(alias, bucket, &key);
+        let full_path = format!("{alias}/{bucket}/{key}");
+        match client
+            .put_object_retention(&path, version_id, retention, bypass_governance)
+            .await
+        {
+            Ok(()) => {
+                if !formatter.is_json() {
+                    let styled = formatter.style_file(&full_path);
+                    match retention {
+                        Some(r) => formatter.println(&format!(
+                            "Locked: {styled} ({} until {})",
+                            r.mode,
+                            formatter.style_date(&r.retain_until.to_string())
+                        )),
+                        None => formatter.println(&format!("Cleared: {styled}")),
+                    }
+                }
+                objects.push(ObjectRetentionEntry {
+                    path: full_path,
+                    version_id: version_id.map(str::to_string),
+                    mode: retention.map(|r| r.mode),
+                    retain_until: retention.map(|r| r.retain_until),
+                });
+            }
+            Err(error) => {
+                formatter.error(&format!(
+                    "Failed to update retention of {full_path}: {error}"
+                ));
+                failed.push(full_path);
+                last_error = Some(error);
+            }
+        }
+    }
+
+    if formatter.is_json() {
+        formatter.json(&RetentionOutput {
+            status: status_name(&failed),
+            objects,
+            failed,
+        });
+    } else if matches!(target, RetentionTarget::Prefix { .. }) && !objects.is_empty() {
+        let verb = if retention.is_some() {
+            "Set"
+        } else {
+            "Cleared"
+        };
+        formatter.success(&format!("{verb} retention on {} object(s).", objects.len()));
+    }
+
+    match last_error {
+        Some(error) => exit_code_from_error(&error),
+        None => ExitCode::Success,
+    }
+}
+
+/// Keys of the objects a target refers to
+async fn object_keys(
+    client: &S3Client,
+    target: &RetentionTarget,
+    formatter: &Formatter,
+) -> Result<Vec<String>, ExitCode> {
+    let (alias, bucket, prefix) = match target {
+        RetentionTarget::Object { key, .. } => return Ok(vec![key.clone()]),
+        RetentionTarget::Prefix {
+            alias,
+            bucket,
+            prefix,
+        } => (alias, bucket, prefix),
+        RetentionTarget::Bucket { .. } => return Ok(Vec::new()),
+    };
+
+    list_object_keys(client, alias, bucket, prefix, formatter).await
+}
+
+async fn setup_client(
+    target: &RetentionTarget,
+    force: bool,
+    formatter: &Formatter,
+) -> Result<S3Client, ExitCode> {
+    feature_client(
+        target.alias_name(),
+        target.bucket_name(),
+        "retention",
+        "object lock retention",
+        force,
+        formatter,
+    )
+    .await
+}
+
+/// Work out what a path refers to
+///
+/// A bucket path targets the bucket default retention unless `recursive` is
+/// set, in which case it targets every object in the bucket.
+fn resolve_target(
+    path: &str,
+    recursive: bool,
+    version_id: Option<&str>,
+) -> Result<RetentionTarget, String> {
+    if path.trim().is_empty() {
+        return Err("Path cannot be empty".to_string());
+    }
+
+    let parts: Vec<&str> = path.splitn(3, '/').collect();
+    if parts.len() < 2 || parts[0].is_empty() {
+        return Err("Path must include alias and bucket (alias/bucket[/key])".to_string());
+    }
+
+    let alias = parts[0].to_string();
+    let bucket = parts[1].trim_end_matches('/').to_string();
+    if bucket.is_empty() {
+        return Err("Bucket name is required (alias/bucket[/key])".to_string());
+    }
+    let key = parts.get(2).copied().unwrap_or_default().to_string();
+
+    if recursive {
+        return Ok(RetentionTarget::Prefix {
+            alias,
+            bucket,
+            prefix: key,
+        });
+    }
+
+    if key.is_empty() {
+        if version_id.is_some() {
+            return Err("--version-id requires an object path".to_string());
+        }
+        return Ok(RetentionTarget::Bucket { alias, bucket });
+    }
+
+    if key.ends_with('/') {
+        return Err(format!(
+            "'{path}' is a prefix. Use --recursive to apply to the objects under it."
+        ));
+    }
+
+    Ok(RetentionTarget::Object { alias, bucket, key })
+}
+
+fn parse_mode(value: &str) -> Result<RetentionMode, String> {
+    value.parse().map_err(|e: rc_core::Error| e.to_string())
+}
+
+/// Parse a retention validity such as `30d` or `1y`
+fn parse_validity(value: &str) -> Result<RetentionValidity, String> {
+    let value = value.trim();
+    let invalid = || {
+        format!("Invalid validity '{value}'. Expected a number of days or years (e.g., 30d, 1y)")
+    };
+
+    let (number, in_years) = if let Some(days) = value.strip_suffix(['d', 'D']) {
+        (days, false)
+    } else if let Some(years) = value.strip_suffix(['y', 'Y']) {
+        (years, true)
+    } else {
+        return Err(invalid());
+    };
+
+    let number: u32 = number.parse().map_err(|_| invalid())?;
+    if number == 0 {
+        return Err(format!("Validity must be greater than zero: '{value}'"));
+    }
+
+    Ok(if in_years {
+        RetentionValidity::Years(number)
+    } else {
+        RetentionValidity::Days(number)
+    })
+}
+
+/// Time until which an object locked at `now` for `validity` is retained
+fn retain_until(now: Timestamp, validity: RetentionValidity) -> Result<Timestamp, String> {
+    let result = match validity {
+        RetentionValidity::Days(days) => now.checked_add(Span::new().hours(i64::from(days) * 24)),
+        RetentionValidity::Years(years) => now
+            .to_zoned(TimeZone::UTC)
+            .checked_add(Span::new().years(i64::from(years)))
+            .map(|zoned| zoned.timestamp()),
+    };
+    result.map_err(|e| format!("Validity {validity} is too long: {e}"))
+}
+
+fn status_name(failed: &[String]) -> &'static str {
+    if failed.is_empty() {
+        "success"
+    } else {
+        "partial"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_target() {
+        assert_eq!(
+            resolve_target("local/bucket", false, None).unwrap(),
+            RetentionTarget::Bucket {
+                alias: "local".to_string(),
+                bucket: "bucket".to_string(),
+            }
+        );
+        assert_eq!(
+            resolve_target("local/bucket/a/b.txt", false, Some("v1")).unwrap(),
+            RetentionTarget::Object {
+                alias: "local".to_string(),
+                bucket: "bucket".to_string(),
+                key: "a/b.txt".to_string(),
+            }
+        );
+        assert_eq!(
+            resolve_target("local/bucket", true, None).unwrap(),
+            RetentionTarget::Prefix {
+                alias: "local".to_string(),
+                bucket: "bucket".to_string(),
+                prefix: String::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_resolve_target_errors() {
+        assert!(resolve_target("", false, None).is_err());
+        assert!(resolve_target("local", false, None).is_err());
+        assert!(resolve_target("local/bucket", false, Some("v1")).is_err());
+        assert!(resolve_target("local/bucket/dir/", false, None).is_err());
+    }
+
+    #[test]
+    fn test_parse_validity() {
+        assert_eq!(parse_validity("30d").unwrap(), RetentionValidity::Days(30));
+        assert_eq!(parse_validity("1Y").unwrap(), RetentionValidity::Years(1));
+        assert!(parse_validity("0d").is_err());
+        assert!(parse_validity("30").is_err());
+        assert!(parse_validity("2w").is_err());
+        assert!(parse_validity("").is_err());
+        assert!(parse_validity("3日").is_err());
+        assert!(parse_validity("日").is_err());
+    }
+
+    #[test]
+    fn test_retain_until() {
+        let now: Timestamp = "2024-02-29T12:00:00Z".parse().unwrap();
+        assert_eq!(
+            retain_until(now, RetentionValidity::Days(1)).unwrap(),
+            "2024-03-01T12:00:00Z".parse::<Timestamp>().unwrap()
+        );
+        // Leap day clamps to the end of February
+        assert_eq!(
+            retain_until(now, RetentionValidity::Years(1)).unwrap(),
+            "2025-02-28T12:00:00Z".parse::<Timestamp>().unwrap()
+        );
+    }
+}
@@ -3,7 +3,10 @@
 //! Removes one or more objects from a bucket.
 
 use clap::Args;
-use rc_core::{AliasManager, ListOptions, ObjectStore as _, RemotePath};
+use rc_core::{
+    AliasManager, DeleteOptions, DeleteTarget, ListOptions, MultipartUploadInfo, ObjectStore as _,
+    ObjectVersion, RemotePath, VersionMarker, rewind,
+};
 use rc_s3::S3Client;
 use serde::Serialize;
 
@@ -29,14 +32,26 @@ pub struct RmArgs {
     #[arg(long)]
     pub dry_run: bool,
 
-    /// Remove incomplete multipart uploads older than specified duration
+    /// Remove incomplete multipart uploads instead of objects
     #[arg(long)]
     pub incomplete: bool,
 
-    /// Include versions (requires versioning support)
-    #[arg(long)]
+    /// Only remove incomplete uploads initiated longer ago than this (e.g., 1h, 7d)
+    #[arg(long, requires = "incomplete")]
+    pub older_than: Option<String>,
+
+    /// Permanently delete all versions and delete markers (requires versioning support)
+    #[arg(long, conflicts_with = "incomplete")]
     pub versions: bool,
 
+    /// Permanently delete a single object version
+    #[arg(long, conflicts_with_all = ["recursive", "incomplete", "versions"])]
+    pub version_id: Option<String>,
+
+    /// With --versions, only delete versions as old as this (duration like 30d, or RFC 3339)
+    #[arg(long, requires = "versions")]
+    pub rewind: Option<String>,
+
     /// Bypass governance retention
     #[arg(long)]
     pub bypass: bool,
@@ -90,7 +105,14 @@ pub async fn execute(args: RmArgs, output_config: OutputConfig) -> ExitCode {
         };
         formatter.json(&output);
     } else if !args.dry_run && !all_deleted.is_empty() {
-        formatter.success(&format!("Removed {} object(s).", all_deleted.len()));
+        let noun = if args.incomplete {
+            "incomplete upload(s)"
+        } else if args.versions || args.version_id.is_some() {
+            "object version(s)"
+        } else {
+            "object(s)"
+        };
+        formatter.success(&format!("Removed {} {noun}.", all_deleted.len()));
     }
 
     if has_error {
@@ -142,6 +164,49 @@ async fn process_rm_path(
 
     let is_prefix = key.ends_with('/') || key.is_empty();
 
+    if args.incomplete {
+        return delete_incomplete(
+            &client,
+            &alias_name,
+            &bucket,
+            &key,
+            is_prefix,
+            args,
+            formatter,
+        )
+        .await;
+    }
+
+    if let Some(version_id) = &args.version_id {
+        if is_prefix {
+            formatter.error("--version-id requires an object key, not a prefix");
+            return Err((ExitCode::UsageError, vec![path_str.to_string()]));
+        }
+        return delete_version(
+            &client,
+            &alias_name,
+            &bucket,
+            &key,
+            version_id,
+            args,
+            formatter,
+        )
+        .await;
+    }
+
+    if args.versions {
+        return delete_versions(
+            &client,
+            &alias_name,
+            &bucket,
+            &key,
+            is_prefix,
+            args,
+            formatter,
+        )
+        .await;
+    }
+
     // If recursive or prefix, list and delete all matching objects
     if args.recursive || is_prefix {
         delete_recursive(&client, &alias_name, &bucket, &key, args, formatter).await
@@ -168,7 +233,12 @@ async fn delete_single(
         return Ok(vec![full_path]);
     }
 
-    match client.delete_object(&path).await {
+    let options = DeleteOptions {
+        bypass_governance: args.bypass,
+        ..Default::default()
+    };
+
+    match client.delete_object(&path, &options).await {
         Ok(()) => {
             if !formatter.is_json() {
                 let styled_path = formatter.style_file(&full_path);
@@ -273,11 +343,12 @@ async fn delete_recursive(
 
     for chunk in keys_to_delete.chunks(1000) {
         let chunk_keys: Vec<String> = chunk.to_vec();
+        let targets = chunk_keys.iter().cloned().map(DeleteTarget::from).collect();
 
-        match client.delete_objects(bucket, chunk_keys.clone()).await {
-            Ok(deleted_keys) => {
-                for key in &deleted_keys {
-                    let full_path = format!("{alias_name}/{bucket}/{key}");
+        match client.delete_objects(bucket, targets, args.bypass).await {
+            Ok(deleted_targets) => {
+                for target in &deleted_targets {
+                    let full_path = format!("{alias_name}/{bucket}/{}", target.key);
                     if !formatter.is_json() {
                         let styled_path = formatter.style_file(&full_path);
                         formatter.println(&format!("Removed: {styled_path}"));
@@ -301,6 +372,281 @@ async fn delete_recursive(
     }
 }
 
+async fn delete_version(
+    client: &S3Client,
+    alias_name: &str,
+    bucket: &str,
+    key: &str,
+    version_id: &str,
+    args: &RmArgs,
+    formatter: &Formatter,
+) -> Result<Vec<String>, (ExitCode, Vec<String>)> {
+    let path = RemotePath::new(alias_name, bucket, key);
+    let label = version_label(&format!("{alias_name}/{bucket}/{key}"), version_id);
+
+    if args.dry_run {
+        let styled_path = formatter.style_file(&label);
+        formatter.println(&format!("Would remove: {styled_path}"));
+        return Ok(vec![label]);
+    }
+
+    let options = DeleteOptions {
+        version_id: Some(version_id.to_string()),
+        bypass_governance: args.bypass,
+    };
+
+    match client.delete_object(&path, &options).await {
+        Ok(()) => {
+            if !formatter.is_json() {
+                let styled_path = formatter.style_file(&label);
+                formatter.println(&format!("Removed: {styled_path}"));
+            }
+            Ok(vec![label])
+        }
+        Err(e) => {
+            let err_str = e.to_string();
+            if err_str.contains("NotFound")
+                || err_str.contains("NoSuchKey")
+                || err_str.contains("NoSuchVersion")
+            {
+                if args.force {
+                    Ok(vec![])
+                } else {
+                    formatter.error(&format!("Object version not found: {label}"));
+                    Err((ExitCode::NotFound, vec![label]))
+                }
+            } else if err_str.contains("AccessDenied") {
+                formatter.error(&format!("Access denied: {label}"));
+                Err((ExitCode::AuthError, vec![label]))
+            } else {
+                formatter.error(&format!("Failed to remove {label}: {e}"));
+                Err((ExitCode::NetworkError, vec![label]))
+            }
+        }
+    }
+}
+
+async fn delete_versions(
+    client: &S3Client,
+    alias_name: &str,
+    bucket: &str,
+    key: &str,
+    is_prefix: bool,
+    args: &RmArgs,
+    formatter: &Formatter,
+) -> Result<Vec<String>, (ExitCode, Vec<String>)> {
+    let path = RemotePath::new(alias_name, bucket, key);
+
+    let before = match &args.rewind {
+        Some(rewind) => match rewind::parse_rewind(rewind, jiff::Timestamp::now()) {
+            Ok(at) => Some(at),
+            Err(e) => {
+                formatter.error(&e.to_string());
+                return Err((ExitCode::UsageError, vec![]));
+            }
+        },
+        None => None,
+    };
+
+    // A plain key only matches its own versions, not other keys sharing the prefix
+    let exact = (!(args.recursive || is_prefix)).then_some(key);
+
+    let target_label = |t: &DeleteTarget| {
+        let full_path = format!("{alias_name}/{bucket}/{}", t.key);
+        version_label(&full_path, t.version_id.as_deref().unwrap_or("null"))
+    };
+
+    let mut deleted = Vec::new();
+    let mut failed = Vec::new();
+    let mut found = false;
+    let mut marker: Option<VersionMarker> = None;
+
+    loop {
+        let page = match client
+            .list_object_versions_page(&path, marker.as_ref(), 1000)
+            .await
+        {
+            Ok(page) => page,
+            Err(e) => {
+                let err_str = e.to_string();
+                if err_str.contains("NotFound") || err_str.contains("NoSuchBucket") {
+                    formatter.error(&format!("Bucket not found: {bucket}"));
+                    return Err((ExitCode::NotFound, failed));
+                }
+                formatter.error(&format!("Failed to list object versions: {e}"));
+                return Err((ExitCode::NetworkError, failed));
+            }
+        };
+
+        let targets = version_targets(page.versions, exact, before);
+        found |= !targets.is_empty();
+
+        if args.dry_run {
+            for label in targets.iter().map(target_label) {
+                let styled_path = formatter.style_file(&label);
+                formatter.println(&format!("Would remove: {styled_path}"));
+                deleted.push(label);
+            }
+        } else if !targets.is_empty() {
+            match client
+                .delete_objects(bucket, targets.clone(), args.bypass)
+                .await
+            {
+                Ok(deleted_targets) => {
+                    for target in &deleted_targets {
+                        let label = target_label(target);
+                        if !formatter.is_json() {
+                            let styled_path = formatter.style_file(&label);
+                            formatter.println(&format!("Removed: {styled_path}"));
+                        }
+                        deleted.push(label);
+                    }
+                }
+                Err(e) => {
+                    formatter.error(&format!("Failed to delete batch: {e}"));
+                    failed.extend(targets.iter().map(target_label));
+                }
+            }
+        }
+
+        match page.next {
+            Some(next) => marker = Some(next),
+            None => break,
+        }
+    }
+
+    if !found && !args.force {
+        formatter.warning(&format!(
+            "No versions found matching: {alias_name}/{bucket}/{key}"
+        ));
+    }
+
+    if !failed.is_empty() {
+        Err((ExitCode::GeneralError, failed))
+    } else {
+        Ok(deleted)
+    }
+}
+
+/// Versions from one listing page that `rm --versions` should delete
+///
+/// `exact` limits the page to a single key; `before` keeps only versions last
+/// modified at or before that time.
+fn version_targets(
+    versions: Vec<ObjectVersion>,
+    exact: Option<&str>,
+    before: Option<jiff::Timestamp>,
+) -> Vec<DeleteTarget> {
+    versions
+        .into_iter()
+        .filter(|v| exact.is_none_or(|key| v.key == key))
+        .filter(|v| before.is_none_or(|at| v.last_modified.is_some_and(|modified| modified <= at)))
+        .map(|v| DeleteTarget::version(v.key, v.version_id))
+        .collect()
+}
+
+/// Display form of an object version
+fn version_label(full_path: &str, version_id: &str) -> String {
+    format!("{full_path} (version {version_id})")
+}
+
+async fn delete_incomplete(
+    client: &S3Client,
+    alias_name: &str,
+    bucket: &str,
+    key: &str,
+    is_prefix: bool,
+    args: &RmArgs,
+    formatter: &Formatter,
+) -> Result<Vec<String>, (ExitCode, Vec<String>)> {
+    let cutoff = match &args.older_than {
+        Some(d) => match rewind::parse_duration_ago(d, jiff::Timestamp::now()) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                formatter.error(&format!("Invalid --older-than: {e}"));
+                return Err((ExitCode::UsageError, vec![]));
+            }
+        },
+        None => None,
+    };
+
+    let path = RemotePath::new(alias_name, bucket, key);
+    let uploads = match client.list_multipart_uploads(&path).await {
+        Ok(uploads) => uploads,
+        Err(e) => {
+            let err_str = e.to_string();
+            if err_str.contains("NotFound") || err_str.contains("NoSuchBucket") {
+                formatter.error(&format!("Bucket not found: {bucket}"));
+                return Err((ExitCode::NotFound, vec![]));
+            }
+            formatter.error(&format!("Failed to list incomplete uploads: {e}"));
+            return Err((ExitCode::NetworkError, vec![]));
+        }
+    };
+
+    let exact = !(args.recursive || is_prefix);
+    let uploads: Vec<MultipartUploadInfo> = uploads
+        .into_iter()
+        .filter(|u| !exact || u.key == key)
+        .filter(|u| is_older(u, cutoff))
+        .collect();
+
+    if uploads.is_empty() && !args.force {
+        formatter.warning(&format!(
+            "No incomplete uploads found matching: {alias_name}/{bucket}/{key}"
+        ));
+    }
+
+    let mut removed = Vec::new();
+    let mut failed = Vec::new();
+
+    for upload in uploads {
+        let full_path = format!("{alias_name}/{bucket}/{}", upload.key);
+        let label = format!("{full_path} ({})", upload.upload_id);
+
+        if args.dry_run {
+            let styled_path = formatter.style_file(&label);
+            formatter.println(&format!("Would remove: {styled_path}"));
+            removed.push(full_path);
+            continue;
+        }
+
+        let upload_path = RemotePath::new(alias_name, bucket, &upload.key);
+        match client
+            .abort_multipart_upload(&upload_path, &upload.upload_id)
+            .await
+        {
+            Ok(()) => {
+                if !formatter.is_json() {
+                    let styled_path = formatter.style_file(&label);
+                    formatter.println(&format!("Removed: {styled_path}"));
+                }
+                removed.push(full_path);
+            }
+            Err(e) => {
+                formatter.error(&format!("Failed to remove {label}: {e}"));
+                failed.push(full_path);
+            }
+        }
+    }
+
+    if !failed.is_empty() {
+        Err((ExitCode::GeneralError, failed))
+    } else {
+        Ok(removed)
+    }
+}
+
+/// Whether an upload was initiated before `cutoff`
+///
+/// Uploads with an unknown initiation time are only matched without a cutoff.
+fn is_older(upload: &MultipartUploadInfo, cutoff: Option<jiff::Timestamp>) -> bool {
+    match cutoff {
+        Some(cutoff) => upload.initiated.is_some_and(|t| t < cutoff),
+        None => true,
+    }
+}
+
 /// Parse rm path into (alias, bucket, key)
 fn parse_rm_path(path: &str) -> Result<(String, String, String), String> {
     if path.is_empty() {
@@ -367,4 +713,89 @@ mod tests {
     fn test_parse_rm_path_empty() {
         assert!(parse_rm_path("").is_err());
     }
+
+    #[test]
+    fn test_version_label() {
+        assert_eq!(
+            version_label("a/b/key.txt", "3HL4kqtJ"),
+            "a/b/key.txt (version 3HL4kqtJ)"
+        );
+    }
+
+    fn version(key: &str, id: &str, secs: i64) -> ObjectVersion {
+        ObjectVersion {
+            key: key.to_string(),
+            version_id: id.to_string(),
+            is_latest: false,
+            is_delete_marker: false,
+            last_modified: Some(jiff::Timestamp::from_second(secs).unwrap()),
+            size_bytes: None,
+            etag: None,
+        }
+    }
+
+    #[test]
+    fn test_version_targets() {
+        let page = || {
+            vec![
+                version("a.txt", "v2", 200),
+                version("a.txt", "v1", 100),
+                version("a.txt.bak", "v1", 50),
+                ObjectVersion {
+                    is_delete_marker: true,
+                    ..version("b.txt", "m1", 300)
+                },
+            ]
+        };
+
+        // --versions on a prefix removes every version and delete marker
+        assert_eq!(
+            version_targets(page(), None, None),
+            vec![
+                DeleteTarget::version("a.txt", "v2"),
+                DeleteTarget::version("a.txt", "v1"),
+                DeleteTarget::version("a.txt.bak", "v1"),
+                DeleteTarget::version("b.txt", "m1"),
+            ]
+        );
+
+        // A plain key ignores other keys that share it as a prefix
+        assert_eq!(
+            version_targets(page(), Some("a.txt"), None),
+            vec![
+                DeleteTarget::version("a.txt", "v2"),
+                DeleteTarget::version("a.txt", "v1"),
+            ]
+        );
+
+        // --rewind keeps versions written after the cutoff
+        let at = jiff::Timestamp::from_second(100).unwrap();
+        assert_eq!(
+            version_targets(page(), None, Some(at)),
+            vec![
+                DeleteTarget::version("a.txt", "v1"),
+                DeleteTarget::version("a.txt.bak", "v1"),
+            ]
+        );
+        assert_eq!(
+            version_targets(page(), Some("a.txt"), Some(at)),
+            vec![DeleteTarget::version("a.txt", "v1")]
+        );
+    }
+
+    #[test]
+    fn test_is_older() {
+        let now = jiff::Timestamp::now();
+        let week_ago = now.checked_sub(jiff::Span::new().hours(7 * 24)).unwrap();
+        let upload = |initiated| MultipartUploadInfo {
+            key: "key".to_string(),
+            upload_id: "id".to_string(),
+            initiated,
+        };
+
+        assert!(is_older(&upload(Some(week_ago)), Some(now)));
+        assert!(!is_older(&upload(Some(now)), Some(week_ago)));
+        assert!(!is_older(&upload(None), Some(now)));
+        assert!(is_older(&upload(None), None));
+    }
 }
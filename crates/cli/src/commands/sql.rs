@@ -0,0 +1,286 @@
+//! sql command - Run S3 Select queries
+//!
+//! Runs an SQL expression on CSV, JSON or Parquet objects with S3 Select and
+//! writes the returned records to stdout. A path ending in `/` queries every
+//! object under that prefix in turn.
+
+use clap::Args;
+use rc_core::{ObjectStore as _, RemotePath, SelectCompression, SelectFormat, SelectOptions};
+
+use crate::commands::common::{exit_code_from_error, feature_client, list_object_keys};
+use crate::exit_code::ExitCode;
+use crate::output::{Formatter, OutputConfig};
+
+/// Run S3 Select queries
+#[derive(Args, Debug)]
+pub struct SqlArgs {
+    /// Object or prefix to query (alias/bucket/key or alias/bucket/prefix/)
+    pub path: String,
+
+    /// SQL expression (e.g., "select * from S3Object s")
+    #[arg(short = 'e', long)]
+    pub query: String,
+
+    /// Input format: csv, json or parquet (inferred from the key by default)
+    #[arg(long, value_parser = parse_input_format)]
+    pub input_format: Option<SelectFormat>,
+
+    /// Input compression: none, gzip or bzip2 (inferred from the key by default)
+    #[arg(long, value_parser = parse_compression)]
+    pub compression: Option<SelectCompression>,
+
+    /// CSV input has no header line with column names
+    #[arg(long)]
+    pub csv_no_header: bool,
+
+    /// Field delimiter of CSV input: one character, or \t (inferred as a tab for .tsv keys)
+    #[arg(long, value_parser = parse_delimiter)]
+    pub csv_delimiter: Option<char>,
+
+    /// JSON input is a single document rather than one document per line
+    #[arg(long)]
+    pub json_document: bool,
+
+    /// Output format: csv or json (defaults to json with --json, csv otherwise)
+    #[arg(long, value_parser = parse_output_format)]
+    pub output_format: Option<SelectFormat>,
+
+    /// Force operation even if capability detection fails
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// Execute the sql command
+pub async fn execute(args: SqlArgs, output_config: OutputConfig) -> ExitCode {
+    let formatter = Formatter::new(output_config);
+
+    let (alias_name, bucket, key) = match parse_sql_path(&args.path) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            formatter.error(&e);
+            return ExitCode::UsageError;
+        }
+    };
+
+    if args.input_format == Some(SelectFormat::Parquet)
+        && args
+            .compression
+            .is_some_and(|c| c != SelectCompression::None)
+    {
+        formatter.error("Parquet input cannot be combined with --compression");
+        return ExitCode::UsageError;
+    }
+
+    let client = match feature_client(
+        &alias_name,
+        &bucket,
+        "sql",
+        "S3 Select",
+        args.force,
+        &formatter,
+    )
+    .await
+    {
+        Ok(client) => client,
+        Err(code) => return code,
+    };
+
+    let keys = if key.is_empty() || key.ends_with('/') {
+        match list_object_keys(&client, &alias_name, &bucket, &key, &formatter).await {
+            Ok(keys) => keys,
+            Err(code) => return code,
+        }
+    } else {
+        vec![key]
+    };
+
+    let output_format = args.output_format.unwrap_or(if formatter.is_json() {
+        SelectFormat::Json
+    } else {
+        SelectFormat::Csv
+    });
+
+    let mut stdout = tokio::io::stdout();
+    let mut last_error = None;
+
+    for key in keys {
+        let (inferred_format, inferred_compression, inferred_delimiter) = infer_input(&key);
+        let options = SelectOptions {
+            expression: args.query.clone(),
+            input_format: args
+                .input_format
+                .or(inferred_format)
+                .unwrap_or(SelectFormat::Csv),
+            compression: args.compression.unwrap_or(inferred_compression),
+            csv_header: !args.csv_no_header,
+            csv_delimiter: args.csv_delimiter.unwrap_or(inferred_delimiter),
+            json_lines: !args.json_document,
+            output_format,
+        };
+
+        let path = RemotePath::new(&alias_name, &bucket, &key);
+        if let Err(e) = client
+            .select_object_content(&path, &options, &mut stdout)
+            .await
+        {
+            formatter.error(&format!("Failed to query {alias_name}/{bucket}/{key}: {e}"));
+            // Later objects will fail the same way
+            if matches!(e, rc_core::Error::UnsupportedFeature(_)) {
+                return ExitCode::UnsupportedFeature;
+            }
+            last_error = Some(e);
+        }
+    }
+
+    match last_error {
+        Some(e) => exit_code_from_error(&e),
+        None => ExitCode::Success,
+    }
+}
+
+/// Guess the input format, compression and CSV field delimiter of an object from its key
+fn infer_input(key: &str) -> (Option<SelectFormat>, SelectCompression, char) {
+    let lower = key.to_ascii_lowercase();
+    let (name, compression) = if let Some(name) = lower.strip_suffix(".gz") {
+        (name, SelectCompression::Gzip)
+    } else if let Some(name) = lower.strip_suffix(".bz2") {
+        (name, SelectCompression::Bzip2)
+    } else {
+        (lower.as_str(), SelectCompression::None)
+    };
+
+    let extension = name.rsplit_once('.').map(|(_, ext)| ext);
+    let format = match extension {
+        Some("csv" | "tsv") => Some(SelectFormat::Csv),
+        Some("json" | "jsonl" | "ndjson") => Some(SelectFormat::Json),
+        Some("parquet") => Some(SelectFormat::Parquet),
+        _ => None,
+    };
+    let delimiter = if extension == Some("tsv") { '\t' } else { ',' };
+
+    (format, compression, delimiter)
+}
+
+fn parse_input_format(value: &str) -> Result<SelectFormat, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "csv" => Ok(SelectFormat::Csv),
+        "json" => Ok(SelectFormat::Json),
+        "parquet" => Ok(SelectFormat::Parquet),
+        _ => Err(format!(
+            "Invalid input format '{value}'. Expected csv, json or parquet"
+        )),
+    }
+}
+
+fn parse_output_format(value: &str) -> Result<SelectFormat, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "csv" => Ok(SelectFormat::Csv),
+        "json" => Ok(SelectFormat::Json),
+        _ => Err(format!(
+            "Invalid output format '{value}'. Expected csv or json"
+        )),
+    }
+}
+
+fn parse_delimiter(value: &str) -> Result<char, String> {
+    if value == "\\t" {
+        return Ok('\t');
+    }
+
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(format!(
+            "Invalid delimiter '{value}'. Expected a single character or \\t"
+        )),
+    }
+}
+
+fn parse_compression(value: &str) -> Result<SelectCompression, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "none" => Ok(SelectCompression::None),
+        "gzip" | "gz" => Ok(SelectCompression::Gzip),
+        "bzip2" | "bz2" => Ok(SelectCompression::Bzip2),
+        _ => Err(format!(
+            "Invalid compression '{value}'. Expected none, gzip or bzip2"
+        )),
+    }
+}
+
+/// Parse sql path into (alias, bucket, key)
+fn parse_sql_path(path: &str) -> Result<(String, String, String), String> {
+    if path.is_empty() {
+        return Err("Path cannot be empty".to_string());
+    }
+
+    let parts: Vec<&str> = path.splitn(3, '/').collect();
+
+    if parts.len() < 2 || parts[0].is_empty() || parts[1].is_empty() {
+        return Err(format!(
+            "Invalid path format: '{path}'. Expected: alias/bucket/key or alias/bucket/prefix/"
+        ));
+    }
+
+    let key = parts.get(2).copied().unwrap_or_default();
+    Ok((parts[0].to_string(), parts[1].to_string(), key.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_input() {
+        assert_eq!(
+            infer_input("logs/app.csv"),
+            (Some(SelectFormat::Csv), SelectCompression::None, ',')
+        );
+        assert_eq!(
+            infer_input("logs/app.tsv.gz"),
+            (Some(SelectFormat::Csv), SelectCompression::Gzip, '\t')
+        );
+        assert_eq!(
+            infer_input("logs/app.JSON.gz"),
+            (Some(SelectFormat::Json), SelectCompression::Gzip, ',')
+        );
+        assert_eq!(
+            infer_input("data/part-0.parquet"),
+            (Some(SelectFormat::Parquet), SelectCompression::None, ',')
+        );
+        assert_eq!(
+            infer_input("logs/app.log.bz2"),
+            (None, SelectCompression::Bzip2, ',')
+        );
+        assert_eq!(infer_input("README"), (None, SelectCompression::None, ','));
+    }
+
+    #[test]
+    fn test_parse_formats() {
+        assert_eq!(
+            parse_input_format("Parquet").unwrap(),
+            SelectFormat::Parquet
+        );
+        assert!(parse_input_format("xml").is_err());
+        assert_eq!(parse_output_format("json").unwrap(), SelectFormat::Json);
+        assert!(parse_output_format("parquet").is_err());
+        assert_eq!(parse_compression("gzip").unwrap(), SelectCompression::Gzip);
+        assert!(parse_compression("zstd").is_err());
+        assert_eq!(parse_delimiter("\\t").unwrap(), '\t');
+        assert_eq!(parse_delimiter(";").unwrap(), ';');
+        assert!(parse_delimiter(";;").is_err());
+    }
+
+    #[test]
+    fn test_parse_sql_path() {
+        assert_eq!(
+            parse_sql_path("local/bucket/logs/").unwrap(),
+            (
+                "local".to_string(),
+                "bucket".to_string(),
+                "logs/".to_string()
+            )
+        );
+        assert!(parse_sql_path("local").is_err());
+        assert!(parse_sql_path("").is_err());
+    }
+}
@@ -3,7 +3,7 @@
 //! Displays detailed metadata information about an object.
 
 use clap::Args;
-use rc_core::{AliasManager, ObjectStore as _, RemotePath};
+use rc_core::{AliasManager, ObjectStore as _, RemotePath, rewind};
 use rc_s3::S3Client;
 use serde::Serialize;
 
@@ -16,11 +16,11 @@ pub struct StatArgs {
     /// Object path (alias/bucket/key)
     pub path: String,
 
-    /// Show version ID information
-    #[arg(long)]
+    /// Show a specific version
+    #[arg(long, conflicts_with = "rewind")]
     pub version_id: Option<String>,
 
-    /// Rewind to a specific time
+    /// Show the version current at a past time (duration like 2d, or RFC 3339)
     #[arg(long)]
     pub rewind: Option<String>,
 }
@@ -85,8 +85,40 @@ pub async fn execute(args: StatArgs, output_config: OutputConfig) -> ExitCode {
 
     let path = RemotePath::new(&alias_name, &bucket, &key);
 
+    // Resolve the version to show, if any
+    let at = match args
+        .rewind
+        .as_deref()
+        .map(|r| rewind::parse_rewind(r, jiff::Timestamp::now()))
+        .transpose()
+    {
+        Ok(at) => at,
+        Err(e) => {
+            formatter.error(&e.to_string());
+            return ExitCode::UsageError;
+        }
+    };
+
+    let version_id =
+        match rewind::select_version(&client, &path, args.version_id.as_deref(), at).await {
+            Ok(v) => v,
+            Err(e @ rc_core::Error::NotFound(_)) => {
+                formatter.error(&e.to_string());
+                return ExitCode::NotFound;
+            }
+            Err(e) => {
+                formatter.error(&format!("Failed to resolve version: {e}"));
+                return ExitCode::NetworkError;
+            }
+        };
+
     // Get object metadata
-    match client.head_object(&path).await {
+    let result = match &version_id {
+        Some(v) => client.head_object_version(&path, v).await,
+        None => client.head_object(&path).await,
+    };
+
+    match result {
         Ok(info) => {
             if formatter.is_json() {
                 let output = StatOutput {
@@ -97,7 +129,7 @@ pub async fn execute(args: StatArgs, output_config: OutputConfig) -> ExitCode {
                     etag: info.etag.clone(),
                     content_type: info.content_type.clone(),
                     storage_class: info.storage_class.clone(),
-                    version_id: args.version_id,
+                    version_id: version_id.clone(),
                 };
                 formatter.json(&output);
             } else {
@@ -133,6 +165,9 @@ pub async fn execute(args: StatArgs, output_config: OutputConfig) -> ExitCode {
                 if let Some(sc) = &info.storage_class {
                     formatter.println(&format_kv("Class", sc));
                 }
+                if let Some(v) = &version_id {
+                    formatter.println(&format_kv("VersionID", v));
+                }
             }
             ExitCode::Success
         }
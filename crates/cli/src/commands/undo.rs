@@ -0,0 +1,456 @@
+//! undo command - Reverse recent changes in a versioned bucket
+//!
+//! Walks the version history of each key and reverses its latest operations:
+//! deletes are undone by removing their delete markers, overwrites by copying
+//! the previous version over the latest one.
+
+use std::collections::BTreeMap;
+
+use clap::Args;
+use rc_core::{
+    AliasManager, DeleteOptions, ObjectStore as _, ObjectVersion, RemotePath, VersionMarker,
+};
+use rc_s3::S3Client;
+use serde::Serialize;
+
+use crate::exit_code::ExitCode;
+use crate::output::{Formatter, OutputConfig};
+
+/// Reverse recent deletes and overwrites in a versioned bucket
+#[derive(Args, Debug)]
+pub struct UndoArgs {
+    /// Object or prefix to undo (alias/bucket/key or alias/bucket/prefix/)
+    pub path: String,
+
+    /// Undo changes for every object under the prefix
+    #[arg(short, long)]
+    pub recursive: bool,
+
+    /// Number of operations to undo per object
+    #[arg(long, default_value = "1", value_parser = clap::value_parser!(u32).range(1..))]
+    pub last: u32,
+
+    /// Only show what would be undone (dry run)
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// How the latest operations on a key are reversed
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum UndoAction {
+    /// Remove delete markers, making the version below them current again
+    RemoveDeleteMarkers(Vec<String>),
+
+    /// Copy an earlier version over the latest one
+    Restore(String),
+
+    /// The key did not exist before the undone operations, so delete it
+    Delete,
+}
+
+#[derive(Debug, Serialize)]
+struct UndoEntry {
+    key: String,
+    action: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    version_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct UndoOutput {
+    status: &'static str,
+    dry_run: bool,
+    undone: Vec<UndoEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    failed: Vec<String>,
+}
+
+impl UndoAction {
+    fn name(&self) -> &'static str {
+        match self {
+            UndoAction::RemoveDeleteMarkers(_) => "remove_delete_marker",
+            UndoAction::Restore(_) => "restore",
+            UndoAction::Delete => "delete",
+        }
+    }
+
+    fn version_ids(&self) -> Vec<String> {
+        match self {
+            UndoAction::RemoveDeleteMarkers(ids) => ids.clone(),
+            UndoAction::Restore(id) => vec![id.clone()],
+            UndoAction::Delete => vec![],
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            UndoAction::RemoveDeleteMarkers(ids) => {
+                format!("remove {} delete marker(s)", ids.len())
+            }
+            UndoAction::Restore(id) => format!("restore version {id}"),
+            UndoAction::Delete => "delete (did not exist before)".to_string(),
+        }
+    }
+}
+
+/// Execute the undo command
+pub async fn execute(args: UndoArgs, output_config: OutputConfig) -> ExitCode {
+    let formatter = Formatter::new(output_config);
+
+    let (alias_name, bucket, key) = match parse_undo_path(&args.path) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            formatter.error(&e);
+            return ExitCode::UsageError;
+        }
+    };
+
+    let is_prefix = key.is_empty() || key.ends_with('/');
+    if is_prefix && !args.recursive {
+        formatter.error(&format!(
+            "'{}' is a prefix. Use --recursive to undo changes under it.",
+            args.path
+        ));
+        return ExitCode::UsageError;
+    }
+
+    // Load alias
+    let alias_manager = match AliasManager::new() {
+        Ok(am) => am,
+        Err(e) => {
+            formatter.error(&format!("Failed to load aliases: {e}"));
+            return ExitCode::GeneralError;
+        }
+    };
+
+    let alias = match alias_manager.get(&alias_name) {
+        Ok(a) => a,
+        Err(_) => {
+            formatter.error(&format!("Alias '{alias_name}' not found"));
+            return ExitCode::NotFound;
+        }
+    };
+
+    // Create S3 client
+    let client = match S3Client::new(alias).await {
+        Ok(c) => c,
+        Err(e) => {
+            formatter.error(&format!("Failed to create S3 client: {e}"));
+            return ExitCode::NetworkError;
+        }
+    };
+
+    // Without versioning there is no history to go back to
+    match client.get_versioning(&bucket).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            formatter.error(&format!(
+                "Versioning has never been enabled on '{alias_name}/{bucket}'; there is nothing to undo."
+            ));
+            return ExitCode::UnsupportedFeature;
+        }
+        Err(e) => {
+            let err_str = e.to_string();
+            if err_str.contains("NoSuchBucket") || err_str.contains("NotFound") {
+                formatter.error(&format!("Bucket '{alias_name}/{bucket}' does not exist"));
+                return ExitCode::NotFound;
+            }
+            formatter.error(&format!("Failed to get versioning status: {e}"));
+            return ExitCode::NetworkError;
+        }
+    }
+
+    let path = RemotePath::new(&alias_name, &bucket, &key);
+    let mut pending: BTreeMap<String, Vec<ObjectVersion>> = BTreeMap::new();
+    let mut marker: Option<VersionMarker> = None;
+    let mut undone = Vec::new();
+    let mut failed = Vec::new();
+
+    loop {
+        let page = match client
+            .list_object_versions_page(&path, marker.as_ref(), 1000)
+            .await
+        {
+            Ok(page) => page,
+            Err(e) => {
+                formatter.error(&format!("Failed to list object versions: {e}"));
+                return ExitCode::NetworkError;
+            }
+        };
+
+        for v in page.versions {
+            if args.recursive || v.key == key {
+                pending.entry(v.key.clone()).or_default().push(v);
+            }
+        }
+
+        for (object_key, mut history) in take_complete(&mut pending, page.next.as_ref()) {
+            // Newest first, trusting the latest flag over second-precision timestamps
+            history.sort_by(|a, b| {
+                b.is_latest
+                    .cmp(&a.is_latest)
+                    .then_with(|| b.last_modified.cmp(&a.last_modified))
+            });
+
+            let Some(action) = plan_undo(&history, args.last as usize) else {
+                continue;
+            };
+
+            let full_path = format!("{alias_name}/{bucket}/{object_key}");
+            let styled_path = formatter.style_file(&full_path);
+
+            if args.dry_run {
+                if !formatter.is_json() {
+                    formatter.println(&format!(
+                        "Would undo: {styled_path} ({})",
+                        action.describe()
+                    ));
+                }
+            } else {
+                let object = RemotePath::new(&alias_name, &bucket, &object_key);
+                if let Err(e) = apply_undo(&client, &object, &action).await {
+                    formatter.error(&format!("Failed to undo {full_path}: {e}"));
+                    failed.push(full_path);
+                    continue;
+                }
+                if !formatter.is_json() {
+                    formatter.println(&format!("Undone: {styled_path} ({})", action.describe()));
+                }
+            }
+
+            undone.push(UndoEntry {
+                key: object_key,
+                action: action.name(),
+                version_ids: action.version_ids(),
+            });
+        }
+
+        match page.next {
+            Some(next) => marker = Some(next),
+            None => break,
+        }
+    }
+
+    if formatter.is_json() {
+        let output = UndoOutput {
+            status: if failed.is_empty() {
+                "success"
+            } else {
+                "partial"
+            },
+            dry_run: args.dry_run,
+            undone,
+            failed: failed.clone(),
+        };
+        formatter.json(&output);
+    } else if undone.is_empty() && failed.is_empty() {
+        formatter.warning(&format!("Nothing to undo for {}", args.path));
+    } else if !args.dry_run && !undone.is_empty() {
+        formatter.success(&format!("Undid changes to {} object(s).", undone.len()));
+    }
+
+    if failed.is_empty() {
+        ExitCode::Success
+    } else {
+        ExitCode::GeneralError
+    }
+}
+
+/// Remove and return the histories in `pending` that are complete
+///
+/// Keys are listed in order, so every key before the `next` marker has all
+/// its versions. The marker's own key may continue on the next page and stays
+/// in `pending`.
+fn take_complete(
+    pending: &mut BTreeMap<String, Vec<ObjectVersion>>,
+    next: Option<&VersionMarker>,
+) -> BTreeMap<String, Vec<ObjectVersion>> {
+    match next {
+        Some(next) => {
+            let rest = pending.split_off(&next.key);
+            std::mem::replace(pending, rest)
+        }
+        None => std::mem::take(pending),
+    }
+}
+
+/// Work out how to reverse the latest `count` operations of a key
+///
+/// `history` must be ordered newest first. Returns `None` when there is
+/// nothing to undo, for example when the key has a single version and was
+/// already deleted.
+fn plan_undo(history: &[ObjectVersion], count: usize) -> Option<UndoAction> {
+    let latest = history.first()?;
+    let count = count.min(history.len());
+    let undone = &history[..count];
+
+    match history.get(count) {
+        // Only deletes are undone: dropping their markers brings the version back as it was
+        Some(target) if !target.is_delete_marker && undone.iter().all(|v| v.is_delete_marker) => {
+            Some(UndoAction::RemoveDeleteMarkers(
+                undone.iter().map(|v| v.version_id.clone()).collect(),
+            ))
+        }
+        Some(target) if !target.is_delete_marker => {
+            Some(UndoAction::Restore(target.version_id.clone()))
+        }
+        // The key was absent before the undone operations
+        _ if latest.is_delete_marker => None,
+        _ => Some(UndoAction::Delete),
+    }
+}
+
+async fn apply_undo(
+    client: &S3Client,
+    path: &RemotePath,
+    action: &UndoAction,
+) -> rc_core::Result<()> {
+    match action {
+        UndoAction::RemoveDeleteMarkers(version_ids) => {
+            for version_id in version_ids {
+                let options = DeleteOptions {
+                    version_id: Some(version_id.clone()),
+                    ..Default::default()
+                };
+                client.delete_object(path, &options).await?;
+            }
+        }
+        UndoAction::Restore(version_id) => {
+            client.copy_object_version(path, version_id, path).await?;
+        }
+        UndoAction::Delete => {
+            client
+                .delete_object(path, &DeleteOptions::default())
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Parse undo path into (alias, bucket, key)
+fn parse_undo_path(path: &str) -> Result<(String, String, String), String> {
+    if path.is_empty() {
+        return Err("Path cannot be empty".to_string());
+    }
+
+    let parts: Vec<&str> = path.splitn(3, '/').collect();
+
+    if parts.len() < 2 || parts[1].is_empty() {
+        return Err(format!(
+            "Invalid path format: '{path}'. Expected: alias/bucket[/key]"
+        ));
+    }
+
+    let key = parts.get(2).copied().unwrap_or_default();
+    Ok((parts[0].to_string(), parts[1].to_string(), key.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn put(id: &str) -> ObjectVersion {
+        ObjectVersion {
+            key: "key".to_string(),
+            version_id: id.to_string(),
+            is_latest: false,
+            is_delete_marker: false,
+            last_modified: None,
+            size_bytes: Some(1),
+            etag: None,
+        }
+    }
+
+    fn delete(id: &str) -> ObjectVersion {
+        ObjectVersion {
+            is_delete_marker: true,
+            size_bytes: None,
+            ..put(id)
+        }
+    }
+
+    #[test]
+    fn test_parse_undo_path() {
+        assert_eq!(
+            parse_undo_path("a/b/c/d.txt").unwrap(),
+            ("a".to_string(), "b".to_string(), "c/d.txt".to_string())
+        );
+        assert_eq!(
+            parse_undo_path("a/b").unwrap(),
+            ("a".to_string(), "b".to_string(), String::new())
+        );
+        assert!(parse_undo_path("a").is_err());
+        assert!(parse_undo_path("").is_err());
+    }
+
+    #[test]
+    fn test_plan_undo_delete() {
+        let history = vec![delete("d1"), put("v1")];
+        assert_eq!(
+            plan_undo(&history, 1),
+            Some(UndoAction::RemoveDeleteMarkers(vec!["d1".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_plan_undo_overwrite() {
+        let history = vec![put("v2"), put("v1")];
+        assert_eq!(
+            plan_undo(&history, 1),
+            Some(UndoAction::Restore("v1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_plan_undo_multiple() {
+        let history = vec![delete("d2"), put("v3"), put("v2"), put("v1")];
+        assert_eq!(
+            plan_undo(&history, 2),
+            Some(UndoAction::Restore("v2".to_string()))
+        );
+
+        let history = vec![delete("d2"), delete("d1"), put("v1")];
+        assert_eq!(
+            plan_undo(&history, 2),
+            Some(UndoAction::RemoveDeleteMarkers(vec![
+                "d2".to_string(),
+                "d1".to_string()
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_plan_undo_creation() {
+        // Undoing the only put removes the object
+        assert_eq!(plan_undo(&[put("v1")], 1), Some(UndoAction::Delete));
+        // Already deleted with nothing earlier to restore
+        assert_eq!(plan_undo(&[delete("d1")], 1), None);
+        assert_eq!(plan_undo(&[], 1), None);
+    }
+
+    #[test]
+    fn test_take_complete_carries_marker_key() {
+        let version = |key: &str, id: &str| ObjectVersion {
+            key: key.to_string(),
+            ..put(id)
+        };
+        let mut pending = BTreeMap::new();
+        pending.insert("a".to_string(), vec![version("a", "v1")]);
+        pending.insert("b".to_string(), vec![version("b", "v2")]);
+
+        // The page ended inside "b", so only "a" is complete
+        let next = VersionMarker {
+            key: "b".to_string(),
+            version_id: Some("v2".to_string()),
+        };
+        let complete = take_complete(&mut pending, Some(&next));
+        assert_eq!(complete.keys().collect::<Vec<_>>(), ["a"]);
+        assert_eq!(pending.keys().collect::<Vec<_>>(), ["b"]);
+
+        // The rest of "b" joins its partial history on the last page
+        pending.get_mut("b").unwrap().push(version("b", "v1"));
+        let complete = take_complete(&mut pending, None);
+        assert_eq!(complete["b"].len(), 2);
+        assert!(pending.is_empty());
+    }
+}
@@ -0,0 +1,238 @@
+//! watch command - Stream bucket event notifications
+//!
+//! Listens for object events on a bucket or prefix and prints them as they
+//! happen, one line per event, or one JSON document per line with `--json`.
+
+use clap::Args;
+use futures::StreamExt;
+use rc_core::AliasManager;
+use rc_s3::S3Client;
+use rc_s3::notification::{
+    BucketEvent, EVENT_DELETE, EVENT_GET, EVENT_PUT, ListenOptions, NotificationClient,
+};
+
+use crate::commands::common::exit_code_from_error;
+use crate::exit_code::ExitCode;
+use crate::output::{Formatter, OutputConfig};
+
+/// Watch for object events
+#[derive(Args, Debug)]
+pub struct WatchArgs {
+    /// Bucket or prefix to watch (alias/bucket[/prefix])
+    pub path: String,
+
+    /// Event types to report (comma-separated: put, delete, get)
+    #[arg(long, value_delimiter = ',', default_value = "put,delete,get")]
+    pub events: Vec<String>,
+
+    /// Only report keys ending with this suffix (e.g., .jpg)
+    #[arg(long)]
+    pub suffix: Option<String>,
+
+    /// Force operation even if capability detection fails
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// Execute the watch command
+pub async fn execute(args: WatchArgs, output_config: OutputConfig) -> ExitCode {
+    let formatter = Formatter::new(output_config);
+
+    let (alias_name, bucket, prefix) = match parse_watch_path(&args.path) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            formatter.error(&e);
+            return ExitCode::UsageError;
+        }
+    };
+
+    let events = match event_names(&args.events) {
+        Ok(events) => events,
+        Err(e) => {
+            formatter.error(&e);
+            return ExitCode::UsageError;
+        }
+    };
+
+    // Load alias
+    let alias_manager = match AliasManager::new() {
+        Ok(am) => am,
+        Err(e) => {
+            formatter.error(&format!("Failed to load aliases: {e}"));
+            return ExitCode::GeneralError;
+        }
+    };
+
+    let alias = match alias_manager.get(&alias_name) {
+        Ok(a) => a,
+        Err(_) => {
+            formatter.error(&format!("Alias '{alias_name}' not found"));
+            return ExitCode::NotFound;
+        }
+    };
+
+    if !args.force {
+        let client = match S3Client::new(alias.clone()).await {
+            Ok(c) => c,
+            Err(e) => {
+                formatter.error(&format!("Failed to create S3 client: {e}"));
+                return ExitCode::NetworkError;
+            }
+        };
+
+        match client.probe_capability(&bucket, "watch").await {
+            Ok(true) => {}
+            Ok(false) => {
+                formatter.error(
+                    "Backend does not support event notifications. Use --force to attempt anyway.",
+                );
+                return ExitCode::UnsupportedFeature;
+            }
+            Err(e) => {
+                formatter.error(&format!("Failed to detect capabilities: {e}"));
+                return exit_code_from_error(&e);
+            }
+        }
+    }
+
+    let client = match NotificationClient::new(&alias).await {
+        Ok(c) => c,
+        Err(e) => {
+            formatter.error(&format!("Failed to create notification client: {e}"));
+            return ExitCode::NetworkError;
+        }
+    };
+
+    let options = ListenOptions {
+        prefix,
+        suffix: args.suffix.clone().unwrap_or_default(),
+        events,
+    };
+
+    let mut stream = match client.listen(&bucket, &options).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            formatter.error(&format!("Failed to watch '{alias_name}/{bucket}': {e}"));
+            return ExitCode::from_i32(e.exit_code()).unwrap_or(ExitCode::NetworkError);
+        }
+    };
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => return ExitCode::Success,
+            event = stream.next() => match event {
+                Some(Ok(event)) => print_event(&alias_name, &event, &formatter),
+                Some(Err(e)) => {
+                    formatter.error(&format!("Event stream failed: {e}"));
+                    return ExitCode::NetworkError;
+                }
+                None => {
+                    formatter.error("Event stream closed by the server");
+                    return ExitCode::NetworkError;
+                }
+            }
+        }
+    }
+}
+
+fn print_event(alias_name: &str, event: &BucketEvent, formatter: &Formatter) {
+    if formatter.is_json() {
+        formatter.json_line(event);
+        return;
+    }
+
+    let date = event
+        .time
+        .map(|t| t.strftime("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| "                   ".to_string());
+    let size = event
+        .size
+        .map(|s| humansize::format_size(s.max(0) as u64, humansize::BINARY))
+        .unwrap_or_default();
+    let path = format!("{alias_name}/{}/{}", event.bucket, event.key);
+
+    formatter.println(&format!(
+        "{} {} {} {}",
+        formatter.style_date(&format!("[{date}]")),
+        formatter.style_size(&format!("{size:>10}")),
+        formatter.style_key(&event.event_name),
+        formatter.style_file(&path)
+    ));
+}
+
+/// Map `--events` values to S3 event names
+fn event_names(events: &[String]) -> Result<Vec<String>, String> {
+    let mut names = Vec::new();
+    for event in events {
+        let name = match event.trim().to_ascii_lowercase().as_str() {
+            "put" => EVENT_PUT,
+            "delete" => EVENT_DELETE,
+            "get" => EVENT_GET,
+            other => {
+                return Err(format!(
+                    "Unknown event type '{other}'. Expected put, delete or get"
+                ));
+            }
+        };
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    Ok(names)
+}
+
+/// Parse watch path into (alias, bucket, prefix)
+fn parse_watch_path(path: &str) -> Result<(String, String, String), String> {
+    if path.is_empty() {
+        return Err("Path cannot be empty".to_string());
+    }
+
+    let parts: Vec<&str> = path.splitn(3, '/').collect();
+
+    if parts.len() < 2 || parts[0].is_empty() || parts[1].is_empty() {
+        return Err(format!(
+            "Invalid path format: '{path}'. Expected: alias/bucket[/prefix]"
+        ));
+    }
+
+    let prefix = parts.get(2).copied().unwrap_or_default();
+    Ok((
+        parts[0].to_string(),
+        parts[1].to_string(),
+        prefix.to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_watch_path() {
+        assert_eq!(
+            parse_watch_path("local/photos/2024/").unwrap(),
+            (
+                "local".to_string(),
+                "photos".to_string(),
+                "2024/".to_string()
+            )
+        );
+        assert_eq!(
+            parse_watch_path("local/photos").unwrap(),
+            ("local".to_string(), "photos".to_string(), String::new())
+        );
+        assert!(parse_watch_path("local").is_err());
+        assert!(parse_watch_path("").is_err());
+    }
+
+    #[test]
+    fn test_event_names() {
+        let events = event_names(&["put".to_string(), "DELETE".to_string()]).unwrap();
+        assert_eq!(events, vec![EVENT_PUT, EVENT_DELETE]);
+
+        let events = event_names(&["put".to_string(), "put".to_string()]).unwrap();
+        assert_eq!(events, vec![EVENT_PUT]);
+
+        assert!(event_names(&["copy".to_string()]).is_err());
+    }
+}
@@ -110,6 +110,11 @@ impl Formatter {
         !self.config.no_color && !self.config.json
     }
 
+    /// Get the output configuration
+    pub fn config(&self) -> &OutputConfig {
+        &self.config
+    }
+
     /// Get the current theme
     pub fn theme(&self) -> &Theme {
         &self.theme
@@ -232,6 +237,16 @@ impl Formatter {
         }
     }
 
+    /// Output JSON as a single line
+    ///
+    /// Used for streamed output, where each value is one line of NDJSON.
+    pub fn json_line<T: Serialize>(&self, value: &T) {
+        match serde_json::to_string(value) {
+            Ok(json) => println!("{json}"),
+            Err(e) => eprintln!("Error serializing output: {e}"),
+        }
+    }
+
     /// Print a line of text (respects quiet mode)
     pub fn println(&self, message: &str) {
         if self.config.quiet {
@@ -2136,6 +2136,83 @@ mod version_operations {
         // Cleanup
         cleanup_bucket(config_dir.path(), &bucket_name);
     }
+
+    #[test]
+    fn test_undo_dry_run_json() {
+        let (config_dir, bucket_name) = match setup_with_alias("undo") {
+            Some(v) => v,
+            None => {
+                eprintln!("Skipping: S3 test config not available");
+                return;
+            }
+        };
+
+        let output = run_rc(
+            &["version", "enable", &format!("test/{}", bucket_name)],
+            config_dir.path(),
+        );
+        if !output.status.success() {
+            eprintln!(
+                "Enable versioning not supported: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+            cleanup_bucket(config_dir.path(), &bucket_name);
+            return;
+        }
+
+        // Upload the object twice so there is an overwrite to undo
+        let temp_file = tempfile::Builder::new()
+            .suffix(".txt")
+            .tempfile()
+            .expect("Failed to create temp file");
+        for content in ["first", "second"] {
+            std::fs::write(temp_file.path(), content).expect("Failed to write");
+            let output = run_rc(
+                &[
+                    "cp",
+                    temp_file.path().to_str().unwrap(),
+                    &format!("test/{}/undo.txt", bucket_name),
+                ],
+                config_dir.path(),
+            );
+            assert!(output.status.success(), "Failed to upload {content}");
+        }
+
+        let output = run_rc(
+            &[
+                "undo",
+                &format!("test/{}/undo.txt", bucket_name),
+                "--dry-run",
+                "--json",
+            ],
+            config_dir.path(),
+        );
+        assert!(
+            output.status.success(),
+            "Failed to undo: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        // Dry-run output must be a single JSON document
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json: serde_json::Value =
+            serde_json::from_str(&stdout).expect("Output should be valid JSON");
+        assert_eq!(json["dry_run"], true);
+        assert_eq!(json["undone"][0]["key"], "undo.txt");
+        assert_eq!(json["undone"][0]["action"], "restore");
+
+        // Nothing was changed
+        let output = run_rc(
+            &["cat", &format!("test/{}/undo.txt", bucket_name)],
+            config_dir.path(),
+        );
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "second");
+
+        let _ = run_rc(
+            &["rb", "--force", &format!("test/{}", bucket_name)],
+            config_dir.path(),
+        );
+    }
 }
 
 mod tag_operations {
@@ -102,6 +102,13 @@ pub struct Alias {
     #[serde(default = "default_bucket_lookup")]
     pub bucket_lookup: String,
 
+    /// Base domain for virtual-hosted requests (e.g. "s3.internal.corp")
+    ///
+    /// When set, bucket requests go to `<bucket>.<bucket_domain>` instead of
+    /// the endpoint host; the scheme and port still come from the endpoint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bucket_domain: Option<String>,
+
     /// Allow insecure TLS connections
     #[serde(default)]
     pub insecure: bool,
@@ -147,6 +154,7 @@ impl Alias {
             region: default_region(),
             signature: default_signature(),
             bucket_lookup: default_bucket_lookup(),
+            bucket_domain: None,
             insecure: false,
             ca_bundle: None,
             retry: None,
@@ -11,12 +11,13 @@ use serde::{Deserialize, Serialize};
 
 use crate::alias::Alias;
 use crate::error::{Error, Result};
+use crate::migrations::{self, MIGRATIONS, Migration};
 
 /// Current configuration schema version
 ///
 /// IMPORTANT: Bumping this version requires:
-/// 1. Adding a migration in migrations/
-/// 2. Updating migration tests
+/// 1. Adding a migration in migrations/ and registering it in `MIGRATIONS`
+/// 2. Adding a fixture for the old version to the migration tests
 /// 3. Marking the change as BREAKING
 pub const SCHEMA_VERSION: u32 = 1;
 
@@ -26,6 +27,12 @@ const DEFAULT_OUTPUT: &str = "human";
 /// Default color setting
 const DEFAULT_COLOR: &str = "auto";
 
+/// Accepted values for `defaults.output`
+pub const OUTPUT_VALUES: &[&str] = &["human", "json"];
+
+/// Accepted values for `defaults.color`
+pub const COLOR_VALUES: &[&str] = &["auto", "always", "never"];
+
 /// Main configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -79,6 +86,89 @@ impl Default for Defaults {
     }
 }
 
+impl Defaults {
+    /// Keys accepted by [`Defaults::get`] and [`Defaults::set`]
+    pub const KEYS: &'static [&'static str] = &["output", "color", "progress"];
+
+    /// Get a default setting by key
+    pub fn get(&self, key: &str) -> Result<String> {
+        match key {
+            "output" => Ok(self.output.clone()),
+            "color" => Ok(self.color.clone()),
+            "progress" => Ok(self.progress.to_string()),
+            _ => Err(unknown_key(key)),
+        }
+    }
+
+    /// Set a default setting by key, validating the value
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "output" => self.output = check_value(key, value, OUTPUT_VALUES)?,
+            "color" => self.color = check_value(key, value, COLOR_VALUES)?,
+            "progress" => {
+                self.progress = parse_bool(value).ok_or_else(|| {
+                    Error::Config(format!(
+                        "Invalid value '{value}' for 'progress'. Expected true or false"
+                    ))
+                })?
+            }
+            _ => return Err(unknown_key(key)),
+        }
+        Ok(())
+    }
+
+    /// Replace invalid values with the built-in defaults
+    ///
+    /// Returns one message per replaced value. A hand-edited file must not
+    /// stop every command, including the `rc config set` that would fix it.
+    fn sanitize(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for (key, value, allowed, default) in [
+            ("output", &mut self.output, OUTPUT_VALUES, DEFAULT_OUTPUT),
+            ("color", &mut self.color, COLOR_VALUES, DEFAULT_COLOR),
+        ] {
+            match check_value(key, value, allowed) {
+                Ok(normalized) => *value = normalized,
+                Err(_) => {
+                    warnings.push(format!(
+                        "Invalid value '{value}' for '{key}'; using '{default}'"
+                    ));
+                    *value = default.to_string();
+                }
+            }
+        }
+        warnings
+    }
+}
+
+/// Parse a boolean setting such as `true`, `off` or `1`
+pub fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn check_value(key: &str, value: &str, allowed: &[&str]) -> Result<String> {
+    let value = value.trim().to_ascii_lowercase();
+    if allowed.contains(&value.as_str()) {
+        Ok(value)
+    } else {
+        Err(Error::Config(format!(
+            "Invalid value '{value}' for '{key}'. Expected one of: {}",
+            allowed.join(", ")
+        )))
+    }
+}
+
+fn unknown_key(key: &str) -> Error {
+    Error::Config(format!(
+        "Unknown setting '{key}'. Expected one of: {}",
+        Defaults::KEYS.join(", ")
+    ))
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -122,31 +212,78 @@ impl ConfigManager {
         &self.config_path
     }
 
+    /// Directory for transfer state files used to resume interrupted transfers
+    pub fn state_dir(&self) -> PathBuf {
+        self.config_path
+            .parent()
+            .map(|dir| dir.join("state"))
+            .unwrap_or_else(|| PathBuf::from("state"))
+    }
+
     /// Load configuration from disk
     ///
     /// If the configuration file doesn't exist, returns a default configuration.
-    /// If the schema version doesn't match, attempts migration.
+    /// Files written with an older schema are upgraded in place after a copy of
+    /// the original is saved next to it as `config.toml.v<N>.bak`.
     pub fn load(&self) -> Result<Config> {
+        self.load_with(SCHEMA_VERSION, MIGRATIONS)
+    }
+
+    /// Load configuration, upgrading files older than `target` with `migrations`
+    fn load_with(&self, target: u32, migrations: &[Migration]) -> Result<Config> {
         if !self.config_path.exists() {
             return Ok(Config::default());
         }
 
         let content = std::fs::read_to_string(&self.config_path)?;
-        let mut config: Config = toml::from_str(&content)?;
+        let mut table: toml::Table = toml::from_str(&content)?;
 
-        // Check schema version and migrate if necessary
-        if config.schema_version < SCHEMA_VERSION {
-            config = self.migrate(config)?;
-        } else if config.schema_version > SCHEMA_VERSION {
+        let version = migrations::schema_version(&table)?;
+        if version > target {
             return Err(Error::Config(format!(
-                "Configuration file version {} is newer than supported version {}. Please upgrade rc.",
-                config.schema_version, SCHEMA_VERSION
+                "Configuration file version {version} is newer than supported version {target}. Please upgrade rc."
             )));
         }
+        if version == target {
+            return Ok(self.with_valid_defaults(toml::Value::Table(table).try_into()?));
+        }
+
+        // Keep the original so a failed or unwanted upgrade can be undone by hand
+        let backup_path = self.backup_path(version);
+        std::fs::copy(&self.config_path, &backup_path)?;
+
+        migrations::migrate(&mut table, version, target, migrations)?;
+        let config = self.with_valid_defaults(toml::Value::Table(table).try_into()?);
+        self.save(&config)?;
+
+        tracing::info!(
+            "Migrated {} from schema version {version} to {target} (backup: {})",
+            self.config_path.display(),
+            backup_path.display()
+        );
 
         Ok(config)
     }
 
+    /// Log and replace default settings the file holds invalid values for
+    fn with_valid_defaults(&self, mut config: Config) -> Config {
+        for warning in config.defaults.sanitize() {
+            tracing::warn!("{}: {warning}", self.config_path.display());
+        }
+        config
+    }
+
+    /// Path of the backup written before migrating from `version`
+    fn backup_path(&self, version: u32) -> PathBuf {
+        let mut name = self
+            .config_path
+            .file_name()
+            .map(|n| n.to_os_string())
+            .unwrap_or_else(|| "config.toml".into());
+        name.push(format!(".v{version}.bak"));
+        self.config_path.with_file_name(name)
+    }
+
     /// Save configuration to disk
     ///
     /// Creates parent directories if they don't exist.
@@ -170,20 +307,6 @@ impl ConfigManager {
 
         Ok(())
     }
-
-    /// Migrate configuration from older schema version
-    fn migrate(&self, config: Config) -> Result<Config> {
-        let mut config = config;
-
-        // Add migration logic here when schema version is bumped
-        // Example:
-        // if config.schema_version == 1 {
-        //     config = migrate_v1_to_v2(config)?;
-        // }
-
-        config.schema_version = SCHEMA_VERSION;
-        Ok(config)
-    }
 }
 
 impl Default for ConfigManager {
@@ -214,6 +337,31 @@ mod tests {
         assert!(config.aliases.is_empty());
     }
 
+    #[test]
+    fn test_defaults_get_set() {
+        let mut defaults = Defaults::default();
+        assert_eq!(defaults.get("output").unwrap(), "human");
+        assert_eq!(defaults.get("progress").unwrap(), "true");
+
+        defaults.set("output", "JSON").unwrap();
+        defaults.set("color", "never").unwrap();
+        defaults.set("progress", "off").unwrap();
+        assert_eq!(defaults.output, "json");
+        assert_eq!(defaults.color, "never");
+        assert!(!defaults.progress);
+
+        assert!(defaults.set("output", "yaml").is_err());
+        assert!(defaults.set("progress", "maybe").is_err());
+        assert!(defaults.set("theme", "dark").is_err());
+        assert!(defaults.get("theme").is_err());
+    }
+
+    #[test]
+    fn test_state_dir_next_to_config() {
+        let manager = ConfigManager::with_path(PathBuf::from("/tmp/rc/config.toml"));
+        assert_eq!(manager.state_dir(), PathBuf::from("/tmp/rc/state"));
+    }
+
     #[test]
     fn test_load_nonexistent_returns_default() {
         let (manager, _temp_dir) = temp_config_manager();
@@ -234,6 +382,7 @@ mod tests {
             region: "us-east-1".to_string(),
             signature: "v4".to_string(),
             bucket_lookup: "auto".to_string(),
+            bucket_domain: None,
             insecure: false,
             ca_bundle: None,
             retry: None,
@@ -247,6 +396,36 @@ mod tests {
         assert_eq!(loaded.aliases[0].name, "test");
     }
 
+    #[test]
+    fn test_load_replaces_invalid_defaults() {
+        let (manager, _temp_dir) = temp_config_manager();
+        let content = format!(
+            "schema_version = {SCHEMA_VERSION}\n[defaults]\noutput = \"yaml\"\ncolor = \"Never\"\nprogress = false\n"
+        );
+        std::fs::write(manager.config_path(), content).unwrap();
+
+        let config = manager.load().unwrap();
+        assert_eq!(config.defaults.output, "human");
+        assert_eq!(config.defaults.color, "never");
+        assert!(!config.defaults.progress);
+    }
+
+    #[test]
+    fn test_defaults_sanitize() {
+        let mut defaults = Defaults {
+            output: "yaml".to_string(),
+            color: "blue".to_string(),
+            progress: true,
+        };
+        let warnings = defaults.sanitize();
+
+        assert_eq!(defaults.output, "human");
+        assert_eq!(defaults.color, "auto");
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("'yaml' for 'output'"), "{warnings:?}");
+        assert!(Defaults::default().sanitize().is_empty());
+    }
+
     #[test]
     fn test_schema_version_too_new() {
         let (manager, _temp_dir) = temp_config_manager();
@@ -268,4 +447,132 @@ mod tests {
                 .contains("newer than supported")
         );
     }
+
+    /// Expected outcome of loading a fixture
+    enum Expect {
+        Loaded(fn(&Config)),
+        Rejected(&'static str),
+    }
+
+    /// Config files as written by each schema version, oldest first
+    const FIXTURES: &[(&str, &str, Expect)] = &[
+        (
+            "v1 minimal",
+            include_str!("migrations/fixtures/v1_minimal.toml"),
+            Expect::Loaded(|config| {
+                assert_eq!(config.defaults.output, "human");
+                assert!(config.aliases.is_empty());
+            }),
+        ),
+        (
+            "v1 full",
+            include_str!("migrations/fixtures/v1_full.toml"),
+            Expect::Loaded(|config| {
+                assert_eq!(config.defaults.output, "json");
+                assert_eq!(config.defaults.color, "never");
+                assert!(!config.defaults.progress);
+                assert_eq!(config.aliases.len(), 2);
+                assert_eq!(config.aliases[0].signature, "v4");
+                let legacy = &config.aliases[1];
+                assert_eq!(legacy.signature, "v2");
+                assert_eq!(legacy.bucket_domain.as_deref(), Some("s3.example.com"));
+                assert_eq!(legacy.retry.as_ref().unwrap().max_attempts, 5);
+                assert_eq!(legacy.timeout.as_ref().unwrap().read_ms, 60000);
+            }),
+        ),
+        (
+            "missing version",
+            "[defaults]\noutput = \"json\"\n",
+            Expect::Rejected("missing 'schema_version'"),
+        ),
+        (
+            "future version",
+            "schema_version = 999\n",
+            Expect::Rejected("newer than supported"),
+        ),
+    ];
+
+    #[test]
+    fn test_load_fixtures() {
+        for (name, content, expect) in FIXTURES {
+            let (manager, temp_dir) = temp_config_manager();
+            std::fs::write(manager.config_path(), content).unwrap();
+
+            match (manager.load(), expect) {
+                (Ok(config), Expect::Loaded(check)) => {
+                    assert_eq!(config.schema_version, SCHEMA_VERSION, "{name}");
+                    check(&config);
+
+                    // The file on disk is now current and loads the same way
+                    let reloaded = manager.load().unwrap();
+                    assert_eq!(reloaded.schema_version, SCHEMA_VERSION, "{name}");
+                    check(&reloaded);
+                }
+                (Err(e), Expect::Rejected(message)) => {
+                    assert!(e.to_string().contains(message), "{name}: {e}");
+
+                    // Rejected files are neither rewritten nor backed up
+                    assert_eq!(
+                        std::fs::read_to_string(manager.config_path()).unwrap(),
+                        *content,
+                        "{name}"
+                    );
+                    assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+                }
+                (Ok(_), Expect::Rejected(_)) => panic!("{name}: expected an error"),
+                (Err(e), Expect::Loaded(_)) => panic!("{name}: {e}"),
+            }
+        }
+    }
+
+    fn add_region_default(table: &mut toml::Table) -> Result<()> {
+        if let Some(aliases) = table.get_mut("aliases").and_then(|a| a.as_array_mut()) {
+            for alias in aliases.iter_mut().filter_map(|a| a.as_table_mut()) {
+                alias.entry("region").or_insert_with(|| "us-west-2".into());
+            }
+        }
+        Ok(())
+    }
+
+    const TEST_MIGRATIONS: &[Migration] = &[Migration {
+        from: 1,
+        apply: add_region_default,
+    }];
+
+    #[test]
+    fn test_migration_writes_backup_and_upgrades_in_place() {
+        let (manager, temp_dir) = temp_config_manager();
+        let original = "schema_version = 1\n\n[[aliases]]\nname = \"local\"\nendpoint = \"http://localhost:9000\"\naccess_key = \"a\"\nsecret_key = \"s\"\n";
+        std::fs::write(manager.config_path(), original).unwrap();
+
+        let config = manager.load_with(2, TEST_MIGRATIONS).unwrap();
+        assert_eq!(config.schema_version, 2);
+        assert_eq!(config.aliases[0].region, "us-west-2");
+
+        let backup = temp_dir.path().join("config.toml.v1.bak");
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), original);
+
+        let upgraded = std::fs::read_to_string(manager.config_path()).unwrap();
+        assert!(upgraded.contains("schema_version = 2"));
+        assert!(upgraded.contains("us-west-2"));
+
+        // A second load finds the upgraded file and does nothing
+        std::fs::remove_file(&backup).unwrap();
+        manager.load_with(2, TEST_MIGRATIONS).unwrap();
+        assert!(!backup.exists());
+    }
+
+    #[test]
+    fn test_failed_migration_keeps_original() {
+        let (manager, _temp_dir) = temp_config_manager();
+        let original = include_str!("migrations/fixtures/v1_full.toml");
+        std::fs::write(manager.config_path(), original).unwrap();
+
+        let err = manager.load_with(3, TEST_MIGRATIONS).unwrap_err();
+        assert!(err.to_string().contains("No migration"));
+        assert_eq!(
+            std::fs::read_to_string(manager.config_path()).unwrap(),
+            original
+        );
+    }
 }
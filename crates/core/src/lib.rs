@@ -13,13 +13,20 @@ pub mod admin;
 pub mod alias;
 pub mod config;
 pub mod error;
+mod migrations;
 pub mod path;
 pub mod retry;
+pub mod rewind;
 pub mod traits;
 
-pub use alias::{Alias, AliasManager};
-pub use config::{Config, ConfigManager};
+pub use alias::{Alias, AliasManager, RetryConfig, TimeoutConfig};
+pub use config::{Config, ConfigManager, Defaults};
 pub use error::{Error, Result};
 pub use path::{ParsedPath, RemotePath, parse_path};
 pub use retry::{RetryBuilder, is_retryable_error, retry_with_backoff};
-pub use traits::{Capabilities, ListOptions, ListResult, ObjectInfo, ObjectStore, ObjectVersion};
+pub use traits::{
+    ByteReader, Capabilities, CreateBucketOptions, DefaultRetention, DeleteOptions, DeleteTarget,
+    ListOptions, ListResult, MultipartUploadInfo, ObjectInfo, ObjectLockConfig, ObjectRetention,
+    ObjectStore, ObjectStream, ObjectVersion, RetentionMode, RetentionValidity, SelectCompression,
+    SelectFormat, SelectOptions, VersionMarker, VersionPage,
+};
@@ -0,0 +1,159 @@
+//! Configuration schema migrations
+//!
+//! Migrations operate on the raw TOML table rather than [`Config`], so a file
+//! written by an older release can be upgraded even when it no longer
+//! deserializes into the current structure.
+//!
+//! To bump [`SCHEMA_VERSION`] to N:
+//! 1. Add a `vN_minus_1_to_vN` function (or module) that rewrites the table
+//! 2. Register it in [`MIGRATIONS`]
+//! 3. Add a `fixtures/vN_minus_1*.toml` file and a row in the fixture tests
+//!
+//! [`Config`]: crate::config::Config
+//! [`SCHEMA_VERSION`]: crate::config::SCHEMA_VERSION
+
+use toml::Table;
+
+use crate::error::{Error, Result};
+
+/// A single schema upgrade from version `from` to `from + 1`
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Migration {
+    /// Schema version this migration upgrades from
+    pub from: u32,
+
+    /// Rewrite the table in place; `schema_version` is updated by the caller
+    pub apply: fn(&mut Table) -> Result<()>,
+}
+
+/// Registered migrations, one per schema version bump
+///
+/// Version 1 is the first released schema, so nothing is registered yet.
+pub(crate) const MIGRATIONS: &[Migration] = &[];
+
+/// Read `schema_version` from a raw config table
+pub(crate) fn schema_version(table: &Table) -> Result<u32> {
+    let value = table.get("schema_version").ok_or_else(|| {
+        Error::Config("Configuration file is missing 'schema_version'".to_string())
+    })?;
+
+    value
+        .as_integer()
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or_else(|| Error::Config(format!("Invalid schema_version '{value}'")))
+}
+
+/// Upgrade `table` from version `from` to version `to`, one step at a time
+pub(crate) fn migrate(
+    table: &mut Table,
+    from: u32,
+    to: u32,
+    migrations: &[Migration],
+) -> Result<()> {
+    for version in from..to {
+        let migration = migrations
+            .iter()
+            .find(|m| m.from == version)
+            .ok_or_else(|| {
+                Error::Config(format!(
+                    "No migration from configuration version {version} to {}",
+                    version + 1
+                ))
+            })?;
+
+        (migration.apply)(table)?;
+        table.insert("schema_version".to_string(), i64::from(version + 1).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename_output(table: &mut Table) -> Result<()> {
+        if let Some(defaults) = table.get_mut("defaults").and_then(|d| d.as_table_mut())
+            && let Some(format) = defaults.remove("format")
+        {
+            defaults.insert("output".to_string(), format);
+        }
+        Ok(())
+    }
+
+    fn add_aliases(table: &mut Table) -> Result<()> {
+        table
+            .entry("aliases")
+            .or_insert_with(|| toml::Value::Array(Vec::new()));
+        Ok(())
+    }
+
+    const TEST_MIGRATIONS: &[Migration] = &[
+        Migration {
+            from: 1,
+            apply: rename_output,
+        },
+        Migration {
+            from: 2,
+            apply: add_aliases,
+        },
+    ];
+
+    #[test]
+    fn test_migrate_runs_each_step_in_order() {
+        let mut table: Table = "schema_version = 1\n[defaults]\nformat = \"json\"\n"
+            .parse()
+            .unwrap();
+
+        migrate(&mut table, 1, 3, TEST_MIGRATIONS).unwrap();
+
+        assert_eq!(schema_version(&table).unwrap(), 3);
+        assert_eq!(table["defaults"]["output"].as_str(), Some("json"));
+        assert!(table["defaults"].get("format").is_none());
+        assert!(table["aliases"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_migrate_starts_from_file_version() {
+        let mut table: Table = "schema_version = 2\n".parse().unwrap();
+        migrate(&mut table, 2, 3, TEST_MIGRATIONS).unwrap();
+        assert_eq!(schema_version(&table).unwrap(), 3);
+        assert!(table.get("defaults").is_none());
+    }
+
+    #[test]
+    fn test_migrate_missing_step() {
+        let mut table: Table = "schema_version = 1\n".parse().unwrap();
+        let err = migrate(&mut table, 1, 4, TEST_MIGRATIONS).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("No migration from configuration version 3 to 4")
+        );
+    }
+
+    #[test]
+    fn test_registered_migrations_cover_every_version() {
+        use crate::config::SCHEMA_VERSION;
+
+        // One migration per version bump, registered in order
+        assert_eq!(MIGRATIONS.len() as u32, SCHEMA_VERSION - 1);
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.from, index as u32 + 1);
+        }
+    }
+
+    #[test]
+    fn test_schema_version() {
+        let table: Table = "schema_version = 1\n".parse().unwrap();
+        assert_eq!(schema_version(&table).unwrap(), 1);
+
+        let table: Table = "[defaults]\n".parse().unwrap();
+        assert!(schema_version(&table).is_err());
+
+        let table: Table = "schema_version = \"one\"\n".parse().unwrap();
+        assert!(schema_version(&table).is_err());
+
+        let table: Table = "schema_version = -1\n".parse().unwrap();
+        assert!(schema_version(&table).is_err());
+    }
+}
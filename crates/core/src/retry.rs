@@ -88,6 +88,7 @@ pub fn is_retryable_error(error: &Error) -> bool {
             msg_lower.contains("timeout")
                 || msg_lower.contains("connection reset")
                 || msg_lower.contains("connection refused")
+                || msg_lower.contains("connection failed")
                 || msg_lower.contains("503")
                 || msg_lower.contains("service unavailable")
                 || msg_lower.contains("too many requests")
@@ -219,6 +220,9 @@ mod tests {
         assert!(is_retryable_error(&Error::Network(
             "429 Too Many Requests".to_string()
         )));
+        assert!(is_retryable_error(&Error::Network(
+            "Connection failed: error sending request".to_string()
+        )));
 
         // Auth errors are not retryable
         assert!(!is_retryable_error(&Error::Auth(
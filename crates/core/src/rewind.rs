@@ -0,0 +1,193 @@
+//! Point-in-time version resolution
+//!
+//! Maps a `--rewind` value, either a duration such as `2d` or an RFC 3339
+//! time, to the object versions that were current at that moment.
+
+use std::collections::BTreeMap;
+
+use jiff::Timestamp;
+
+use crate::error::{Error, Result};
+use crate::path::RemotePath;
+use crate::traits::{ObjectStore, ObjectVersion};
+
+/// Parse a duration string (e.g., "30s", "1h", "7d", "2w") into the timestamp that far before `now`
+///
+/// A bare number is taken as seconds.
+pub fn parse_duration_ago(s: &str, now: Timestamp) -> Result<Timestamp> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(Error::General("Duration cannot be empty".to_string()));
+    }
+
+    let (num_str, suffix) = if s.ends_with(|c: char| c.is_ascii_alphabetic()) {
+        let idx = s.len() - 1;
+        (&s[..idx], &s[idx..])
+    } else {
+        (s, "s") // Default to seconds
+    };
+
+    let num: i64 = num_str
+        .parse()
+        .map_err(|_| Error::General(format!("Invalid duration number: {num_str}")))?;
+
+    let seconds = match suffix.to_lowercase().as_str() {
+        "s" => num,
+        "m" => num * 60,
+        "h" => num * 3600,
+        "d" => num * 86400,
+        "w" => num * 604800,
+        _ => return Err(Error::General(format!("Unknown duration suffix: {suffix}"))),
+    };
+
+    let duration = jiff::Span::new().seconds(seconds);
+    now.checked_sub(duration)
+        .map_err(|e| Error::General(format!("Duration overflow: {e}")))
+}
+
+/// Parse a `--rewind` value: an RFC 3339 time or a duration before `now`
+pub fn parse_rewind(s: &str, now: Timestamp) -> Result<Timestamp> {
+    if let Ok(at) = s.trim().parse::<Timestamp>() {
+        return Ok(at);
+    }
+    parse_duration_ago(s, now).map_err(|e| {
+        Error::General(format!(
+            "Invalid rewind '{s}': {e}. Expected a duration (e.g., 2d) or an RFC 3339 time"
+        ))
+    })
+}
+
+/// Pick the version of each key that was current at `at`
+///
+/// Keys whose current version at that time was a delete marker, or that did
+/// not exist yet, are left out. The result is sorted by key.
+pub fn versions_at(versions: Vec<ObjectVersion>, at: Timestamp) -> Vec<ObjectVersion> {
+    let mut current: BTreeMap<String, ObjectVersion> = BTreeMap::new();
+
+    for version in versions {
+        let Some(modified) = version.last_modified else {
+            continue;
+        };
+        if modified > at {
+            continue;
+        }
+        match current.get(&version.key) {
+            Some(existing) if existing.last_modified >= Some(modified) => {}
+            _ => {
+                current.insert(version.key.clone(), version);
+            }
+        }
+    }
+
+    current
+        .into_values()
+        .filter(|v| !v.is_delete_marker)
+        .collect()
+}
+
+/// Resolve the versions of every object under `path` as of `at`
+pub async fn resolve_versions(
+    store: &(impl ObjectStore + ?Sized),
+    path: &RemotePath,
+    at: Timestamp,
+) -> Result<Vec<ObjectVersion>> {
+    let versions = store.list_object_versions(path, None).await?;
+    Ok(versions_at(versions, at))
+}
+
+/// Resolve the version ID of a single object from `--version-id` or a rewind time
+///
+/// Returns `None` when neither is given, meaning the current version.
+pub async fn select_version(
+    store: &(impl ObjectStore + ?Sized),
+    path: &RemotePath,
+    version_id: Option<&str>,
+    at: Option<Timestamp>,
+) -> Result<Option<String>> {
+    if let Some(version_id) = version_id {
+        return Ok(Some(version_id.to_string()));
+    }
+    let Some(at) = at else {
+        return Ok(None);
+    };
+
+    let versions: Vec<ObjectVersion> = store
+        .list_object_versions(path, None)
+        .await?
+        .into_iter()
+        .filter(|v| v.key == path.key)
+        .collect();
+
+    versions_at(versions, at)
+        .into_iter()
+        .next()
+        .map(|v| Some(v.version_id))
+        .ok_or_else(|| Error::NotFound(format!("{path} did not exist at {at}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(key: &str, id: &str, secs: i64, is_delete_marker: bool) -> ObjectVersion {
+        ObjectVersion {
+            key: key.to_string(),
+            version_id: id.to_string(),
+            is_latest: false,
+            is_delete_marker,
+            last_modified: Timestamp::from_second(secs).ok(),
+            size_bytes: None,
+            etag: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_duration_ago() {
+        let now = Timestamp::from_second(1_000_000).unwrap();
+        assert_eq!(
+            parse_duration_ago("2d", now).unwrap(),
+            Timestamp::from_second(1_000_000 - 2 * 86400).unwrap()
+        );
+        assert_eq!(
+            parse_duration_ago("90", now).unwrap(),
+            Timestamp::from_second(1_000_000 - 90).unwrap()
+        );
+        assert!(parse_duration_ago("", now).is_err());
+        assert!(parse_duration_ago("5x", now).is_err());
+        assert!(parse_duration_ago("abc", now).is_err());
+    }
+
+    #[test]
+    fn test_parse_rewind() {
+        let now = Timestamp::from_second(1_000_000).unwrap();
+        assert_eq!(
+            parse_rewind("2024-01-02T03:04:05Z", now).unwrap(),
+            "2024-01-02T03:04:05Z".parse::<Timestamp>().unwrap()
+        );
+        assert_eq!(
+            parse_rewind("1h", now).unwrap(),
+            Timestamp::from_second(1_000_000 - 3600).unwrap()
+        );
+        assert!(parse_rewind("yesterday", now).is_err());
+    }
+
+    #[test]
+    fn test_versions_at() {
+        let versions = vec![
+            version("a", "a1", 100, false),
+            version("a", "a2", 200, false),
+            version("a", "a3", 300, false),
+            version("b", "b1", 100, false),
+            version("b", "b2", 150, true),
+            version("c", "c1", 250, false),
+        ];
+
+        let at = Timestamp::from_second(200).unwrap();
+        let result = versions_at(versions, at);
+
+        // "b" was deleted and "c" did not exist yet
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].key, "a");
+        assert_eq!(result[0].version_id, "a2");
+    }
+}
@@ -3,9 +3,13 @@
 //! This trait defines the interface for S3-compatible storage operations.
 //! It allows the CLI to be decoupled from the specific S3 SDK implementation.
 
+use std::path::Path;
+use std::pin::Pin;
+
 use async_trait::async_trait;
 use jiff::Timestamp;
 use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncWrite};
 
 use crate::error::Result;
 use crate::path::RemotePath;
@@ -38,6 +42,40 @@ pub struct ObjectVersion {
     pub etag: Option<String>,
 }
 
+/// Position to resume a version listing from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VersionMarker {
+    /// Key to continue after
+    pub key: String,
+
+    /// Version of `key` to continue after
+    pub version_id: Option<String>,
+}
+
+/// One page of a version listing
+#[derive(Debug, Clone, Default)]
+pub struct VersionPage {
+    /// Versions and delete markers in this page
+    pub versions: Vec<ObjectVersion>,
+
+    /// Marker for the next page, `None` when the listing is complete
+    pub next: Option<VersionMarker>,
+}
+
+/// An incomplete multipart upload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultipartUploadInfo {
+    /// Object key the upload targets
+    pub key: String,
+
+    /// Upload ID
+    pub upload_id: String,
+
+    /// When the upload was initiated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initiated: Option<Timestamp>,
+}
+
 /// Metadata for an object or bucket
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ObjectInfo {
@@ -116,6 +154,18 @@ impl ObjectInfo {
     }
 }
 
+/// Boxed async reader used to stream object content without buffering it in memory
+pub type ByteReader = Pin<Box<dyn AsyncRead + Send>>;
+
+/// Streaming object content returned by [`ObjectStore::get_object_stream`]
+pub struct ObjectStream {
+    /// Object metadata taken from the GET response
+    pub info: ObjectInfo,
+
+    /// Reader over the object body
+    pub body: ByteReader,
+}
+
 /// Result of a list operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListResult {
@@ -149,6 +199,204 @@ pub struct ListOptions {
     pub recursive: bool,
 }
 
+/// Options for delete operations
+#[derive(Debug, Clone, Default)]
+pub struct DeleteOptions {
+    /// Permanently delete this version instead of the current object
+    pub version_id: Option<String>,
+
+    /// Bypass governance-mode object lock retention
+    pub bypass_governance: bool,
+}
+
+/// An object, or one specific version of it, to remove in a batch delete
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteTarget {
+    /// Object key
+    pub key: String,
+
+    /// Version to delete permanently; `None` deletes the current object
+    pub version_id: Option<String>,
+}
+
+impl DeleteTarget {
+    /// Target a specific version of an object
+    pub fn version(key: impl Into<String>, version_id: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            version_id: Some(version_id.into()),
+        }
+    }
+}
+
+impl From<String> for DeleteTarget {
+    fn from(key: String) -> Self {
+        Self {
+            key,
+            version_id: None,
+        }
+    }
+}
+
+/// Options for creating a bucket
+#[derive(Debug, Clone, Default)]
+pub struct CreateBucketOptions {
+    /// Location constraint; `None` creates the bucket in the default region
+    pub region: Option<String>,
+
+    /// Create the bucket with object lock enabled (which also enables versioning)
+    pub object_lock: bool,
+
+    /// Enable versioning right after the bucket is created
+    pub versioning: bool,
+}
+
+/// Data format of S3 Select input or output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SelectFormat {
+    /// Comma-separated values
+    Csv,
+
+    /// JSON documents
+    Json,
+
+    /// Apache Parquet (input only)
+    Parquet,
+}
+
+/// Compression of S3 Select input
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SelectCompression {
+    /// Uncompressed
+    #[default]
+    None,
+
+    /// gzip
+    Gzip,
+
+    /// bzip2
+    Bzip2,
+}
+
+/// Options for an S3 Select query
+#[derive(Debug, Clone)]
+pub struct SelectOptions {
+    /// SQL expression, e.g. `select * from S3Object s`
+    pub expression: String,
+
+    /// Format of the queried objects
+    pub input_format: SelectFormat,
+
+    /// Compression of the queried objects
+    pub compression: SelectCompression,
+
+    /// Whether the first CSV line holds column names
+    pub csv_header: bool,
+
+    /// Field delimiter of CSV input, e.g. `\t` for TSV
+    pub csv_delimiter: char,
+
+    /// Whether JSON input has one document per line rather than a single document
+    pub json_lines: bool,
+
+    /// Format of the returned records (CSV or JSON)
+    pub output_format: SelectFormat,
+}
+
+/// Object lock retention mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum RetentionMode {
+    /// Users with the bypass permission can shorten or remove the retention
+    Governance,
+
+    /// Nobody can shorten or remove the retention until it expires
+    Compliance,
+}
+
+impl RetentionMode {
+    /// Mode name as used by the S3 API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RetentionMode::Governance => "GOVERNANCE",
+            RetentionMode::Compliance => "COMPLIANCE",
+        }
+    }
+}
+
+impl std::fmt::Display for RetentionMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for RetentionMode {
+    type Err = crate::error::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_uppercase().as_str() {
+            "GOVERNANCE" => Ok(RetentionMode::Governance),
+            "COMPLIANCE" => Ok(RetentionMode::Compliance),
+            _ => Err(crate::error::Error::General(format!(
+                "Invalid retention mode '{s}'. Expected GOVERNANCE or COMPLIANCE"
+            ))),
+        }
+    }
+}
+
+/// Retention applied to a single object version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObjectRetention {
+    /// Retention mode
+    pub mode: RetentionMode,
+
+    /// Time until which the object version is locked
+    pub retain_until: Timestamp,
+}
+
+/// How long the default retention of a bucket lasts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RetentionValidity {
+    /// Number of days
+    Days(u32),
+
+    /// Number of years
+    Years(u32),
+}
+
+impl std::fmt::Display for RetentionValidity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RetentionValidity::Days(n) => write!(f, "{n}d"),
+            RetentionValidity::Years(n) => write!(f, "{n}y"),
+        }
+    }
+}
+
+/// Default retention applied to new objects in a bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DefaultRetention {
+    /// Retention mode
+    pub mode: RetentionMode,
+
+    /// Retention period
+    pub validity: RetentionValidity,
+}
+
+/// Object lock configuration of a bucket
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObjectLockConfig {
+    /// Whether object lock is enabled
+    pub enabled: bool,
+
+    /// Default retention for new objects, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_retention: Option<DefaultRetention>,
+}
+
 /// Backend capability information
 #[derive(Debug, Clone, Default)]
 pub struct Capabilities {
@@ -186,7 +434,7 @@ pub trait ObjectStore: Send + Sync {
     async fn bucket_exists(&self, bucket: &str) -> Result<bool>;
 
     /// Create a bucket
-    async fn create_bucket(&self, bucket: &str) -> Result<()>;
+    async fn create_bucket(&self, bucket: &str, options: &CreateBucketOptions) -> Result<()>;
 
     /// Delete a bucket
     async fn delete_bucket(&self, bucket: &str) -> Result<()>;
@@ -197,6 +445,12 @@ pub trait ObjectStore: Send + Sync {
     /// Get object content as bytes
     async fn get_object(&self, path: &RemotePath) -> Result<Vec<u8>>;
 
+    /// Get object content as a stream
+    ///
+    /// Unlike [`ObjectStore::get_object`], the body is not collected into memory,
+    /// so objects of any size can be processed with bounded memory.
+    async fn get_object_stream(&self, path: &RemotePath) -> Result<ObjectStream>;
+
     /// Upload object from bytes
     async fn put_object(
         &self,
@@ -205,11 +459,42 @@ pub trait ObjectStore: Send + Sync {
         content_type: Option<&str>,
     ) -> Result<ObjectInfo>;
 
-    /// Delete an object
-    async fn delete_object(&self, path: &RemotePath) -> Result<()>;
+    /// Upload object from a local file, streaming its content from disk
+    async fn put_object_file(
+        &self,
+        path: &RemotePath,
+        file: &Path,
+        content_type: Option<&str>,
+    ) -> Result<ObjectInfo>;
+
+    /// Upload object from a reader of unknown length
+    ///
+    /// Implementations buffer at most one upload part at a time.
+    async fn put_object_stream(
+        &self,
+        path: &RemotePath,
+        body: ByteReader,
+        content_type: Option<&str>,
+    ) -> Result<ObjectInfo>;
+
+    /// Delete an object, or one of its versions when `options.version_id` is set
+    async fn delete_object(&self, path: &RemotePath, options: &DeleteOptions) -> Result<()>;
+
+    /// Delete multiple objects or object versions (batch delete, up to 1000)
+    ///
+    /// Returns the objects and versions that were deleted.
+    async fn delete_objects(
+        &self,
+        bucket: &str,
+        targets: Vec<DeleteTarget>,
+        bypass_governance: bool,
+    ) -> Result<Vec<DeleteTarget>>;
+
+    /// List incomplete multipart uploads whose keys start with the path's key
+    async fn list_multipart_uploads(&self, path: &RemotePath) -> Result<Vec<MultipartUploadInfo>>;
 
-    /// Delete multiple objects (batch delete)
-    async fn delete_objects(&self, bucket: &str, keys: Vec<String>) -> Result<Vec<String>>;
+    /// Abort an incomplete multipart upload, discarding its uploaded parts
+    async fn abort_multipart_upload(&self, path: &RemotePath, upload_id: &str) -> Result<()>;
 
     /// Copy object within S3 (server-side copy)
     async fn copy_object(&self, src: &RemotePath, dst: &RemotePath) -> Result<ObjectInfo>;
@@ -233,13 +518,45 @@ pub trait ObjectStore: Send + Sync {
     /// Set bucket versioning status
     async fn set_versioning(&self, bucket: &str, enabled: bool) -> Result<()>;
 
-    /// List object versions
+    /// List object versions and delete markers
+    ///
+    /// Pages through the listing until `max_keys` entries are collected, or
+    /// all of them when `max_keys` is `None`.
     async fn list_object_versions(
         &self,
         path: &RemotePath,
         max_keys: Option<i32>,
     ) -> Result<Vec<ObjectVersion>>;
 
+    /// List a single page of object versions and delete markers
+    ///
+    /// Returns at most `max_keys` entries starting after `marker`, in the
+    /// order the backend lists them.
+    async fn list_object_versions_page(
+        &self,
+        path: &RemotePath,
+        marker: Option<&VersionMarker>,
+        max_keys: i32,
+    ) -> Result<VersionPage>;
+
+    /// Get metadata of a specific object version
+    async fn head_object_version(&self, path: &RemotePath, version_id: &str) -> Result<ObjectInfo>;
+
+    /// Stream the content of a specific object version
+    async fn get_object_version_stream(
+        &self,
+        path: &RemotePath,
+        version_id: &str,
+    ) -> Result<ObjectStream>;
+
+    /// Copy a specific object version to `dst` (server-side)
+    async fn copy_object_version(
+        &self,
+        src: &RemotePath,
+        version_id: &str,
+        dst: &RemotePath,
+    ) -> Result<ObjectInfo>;
+
     /// Get object tags
     async fn get_object_tags(
         &self,
@@ -271,6 +588,63 @@ pub trait ObjectStore: Send + Sync {
 
     /// Delete bucket tags
     async fn delete_bucket_tags(&self, bucket: &str) -> Result<()>;
+
+    /// Get the retention of an object, or of one of its versions
+    ///
+    /// Returns `None` when no retention is set.
+    async fn get_object_retention(
+        &self,
+        path: &RemotePath,
+        version_id: Option<&str>,
+    ) -> Result<Option<ObjectRetention>>;
+
+    /// Set the retention of an object, or clear it when `retention` is `None`
+    ///
+    /// Shortening or clearing a governance retention needs `bypass_governance`.
+    async fn put_object_retention(
+        &self,
+        path: &RemotePath,
+        version_id: Option<&str>,
+        retention: Option<&ObjectRetention>,
+        bypass_governance: bool,
+    ) -> Result<()>;
+
+    /// Get the object lock configuration of a bucket
+    ///
+    /// Returns `None` when object lock is not configured on the bucket.
+    async fn get_object_lock_config(&self, bucket: &str) -> Result<Option<ObjectLockConfig>>;
+
+    /// Set or clear (`None`) the default retention of an object lock enabled bucket
+    async fn set_default_retention(
+        &self,
+        bucket: &str,
+        retention: Option<&DefaultRetention>,
+    ) -> Result<()>;
+
+    /// Run an S3 Select query on an object, writing the returned records to `output`
+    ///
+    /// Returns the number of bytes written.
+    async fn select_object_content(
+        &self,
+        path: &RemotePath,
+        options: &SelectOptions,
+        output: &mut (dyn AsyncWrite + Send + Unpin),
+    ) -> Result<u64>;
+
+    /// Whether a legal hold is placed on an object, or on one of its versions
+    async fn get_object_legal_hold(
+        &self,
+        path: &RemotePath,
+        version_id: Option<&str>,
+    ) -> Result<bool>;
+
+    /// Place (`true`) or lift (`false`) a legal hold on an object or object version
+    async fn set_object_legal_hold(
+        &self,
+        path: &RemotePath,
+        version_id: Option<&str>,
+        enabled: bool,
+    ) -> Result<()>;
     // async fn get_versioning(&self, bucket: &str) -> Result<bool>;
     // async fn set_versioning(&self, bucket: &str, enabled: bool) -> Result<()>;
     // async fn get_tags(&self, path: &RemotePath) -> Result<HashMap<String, String>>;
@@ -303,4 +677,19 @@ mod tests {
         assert_eq!(info.key, "my-bucket");
         assert!(info.is_dir);
     }
+
+    #[test]
+    fn test_retention_mode_parse() {
+        assert_eq!(
+            "governance".parse::<RetentionMode>().unwrap(),
+            RetentionMode::Governance
+        );
+        assert_eq!(
+            "COMPLIANCE".parse::<RetentionMode>().unwrap(),
+            RetentionMode::Compliance
+        );
+        assert!("legal".parse::<RetentionMode>().is_err());
+        assert_eq!(RetentionMode::Governance.to_string(), "GOVERNANCE");
+        assert_eq!(RetentionValidity::Years(1).to_string(), "1y");
+    }
 }
@@ -0,0 +1,232 @@
+//! Bucket addressing
+//!
+//! Decides between path-style (`host/bucket/key`) and virtual-hosted
+//! (`bucket.host/key`) requests for an alias, and moves the bucket into the
+//! host name for aliases with a custom `bucket_domain`.
+
+use std::net::IpAddr;
+
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::interceptors::context::BeforeTransmitInterceptorContextMut;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::ConfigBag;
+use rc_core::{Alias, Error, Result};
+
+/// Domains of providers that serve buckets as virtual hosts
+const VIRTUAL_HOST_DOMAINS: &[&str] = &[
+    "amazonaws.com",
+    "amazonaws.com.cn",
+    "aliyuncs.com",
+    "backblazeb2.com",
+    "digitaloceanspaces.com",
+    "googleapis.com",
+    "myqcloud.com",
+    "wasabisys.com",
+];
+
+/// Whether requests for `alias` use path-style addressing
+///
+/// `"auto"` picks virtual-hosted addressing when a `bucket_domain` is set or
+/// the endpoint belongs to a known cloud provider, and path style for IP
+/// addresses, `localhost`, single-label hosts, custom ports and any other host.
+pub(crate) fn use_path_style(alias: &Alias) -> Result<bool> {
+    match alias.bucket_lookup.as_str() {
+        "path" => Ok(true),
+        "dns" => Ok(false),
+        "auto" => {
+            if alias.bucket_domain.is_some() {
+                return Ok(false);
+            }
+            let url = url::Url::parse(&alias.endpoint).map_err(|e| {
+                Error::Config(format!("Invalid endpoint '{}': {e}", alias.endpoint))
+            })?;
+            Ok(!endpoint_supports_virtual_host(&url))
+        }
+        other => Err(Error::Config(format!(
+            "Unsupported bucket lookup '{other}' for alias '{}'. Expected 'auto', 'path' or 'dns'",
+            alias.name
+        ))),
+    }
+}
+
+/// Whether `auto` lookup should use virtual-hosted requests for `url`
+fn endpoint_supports_virtual_host(url: &url::Url) -> bool {
+    // `port()` is `None` when the URL uses the default port of its scheme
+    if url.port().is_some() {
+        return false;
+    }
+
+    let host = match url.host_str() {
+        Some(host) => host.trim_end_matches('.').to_ascii_lowercase(),
+        None => return false,
+    };
+
+    if host.trim_matches(['[', ']']).parse::<IpAddr>().is_ok()
+        || host == "localhost"
+        || host.ends_with(".localhost")
+        || !host.contains('.')
+    {
+        return false;
+    }
+
+    VIRTUAL_HOST_DOMAINS
+        .iter()
+        .any(|domain| host == *domain || host.ends_with(&format!(".{domain}")))
+}
+
+/// Whether `bucket` can be used as a host name label
+///
+/// Dotted names only work over plain HTTP, since they do not match a
+/// wildcard TLS certificate.
+fn is_dns_compatible(bucket: &str, https: bool) -> bool {
+    (3..=63).contains(&bucket.len())
+        && bucket
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'.')
+        && !bucket.starts_with(['-', '.'])
+        && !bucket.ends_with(['-', '.'])
+        && !(https && bucket.contains('.'))
+}
+
+/// Rewrite a path-style URI to address the bucket as `<bucket>.<base_domain>`
+///
+/// Returns `None` when the URI has no bucket or the bucket cannot be used as
+/// a host name, in which case the request stays path-style.
+pub(crate) fn virtual_host_uri(uri: &str, base_domain: &str) -> Option<String> {
+    let uri: http::Uri = uri.parse().ok()?;
+    let scheme = uri.scheme_str()?;
+    let path = uri.path().strip_prefix('/')?;
+
+    let (bucket, rest) = path.split_once('/').unwrap_or((path, ""));
+    if !is_dns_compatible(bucket, scheme == "https") {
+        return None;
+    }
+
+    let port = uri.port_u16().map(|p| format!(":{p}")).unwrap_or_default();
+    let query = uri.query().map(|q| format!("?{q}")).unwrap_or_default();
+    Some(format!(
+        "{scheme}://{bucket}.{base_domain}{port}/{rest}{query}"
+    ))
+}
+
+/// SDK interceptor that moves the bucket of path-style requests into the
+/// host name under a custom base domain
+///
+/// Runs before signing, so the signature covers the final host.
+#[derive(Debug, Clone)]
+pub(crate) struct VirtualHostInterceptor {
+    base_domain: String,
+}
+
+impl VirtualHostInterceptor {
+    pub(crate) fn new(base_domain: &str) -> Self {
+        Self {
+            base_domain: base_domain.trim_matches('.').to_string(),
+        }
+    }
+}
+
+impl Intercept for VirtualHostInterceptor {
+    fn name(&self) -> &'static str {
+        "VirtualHostInterceptor"
+    }
+
+    fn modify_before_signing(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> std::result::Result<(), BoxError> {
+        let request = context.request_mut();
+        if let Some(uri) = virtual_host_uri(request.uri(), &self.base_domain) {
+            request.set_uri(uri)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alias(endpoint: &str, bucket_lookup: &str) -> Alias {
+        let mut alias = Alias::new("test", endpoint, "access", "secret");
+        alias.bucket_lookup = bucket_lookup.to_string();
+        alias
+    }
+
+    #[test]
+    fn test_auto_lookup_detection() {
+        let cases = [
+            ("https://s3.amazonaws.com", false),
+            ("https://s3.us-west-2.amazonaws.com", false),
+            ("https://oss-cn-hangzhou.aliyuncs.com", false),
+            ("https://storage.googleapis.com", false),
+            ("http://localhost:9000", true),
+            ("http://localhost", true),
+            ("http://127.0.0.1", true),
+            ("http://[::1]:9000", true),
+            ("http://minio:9000", true),
+            ("https://s3.amazonaws.com:8443", true),
+            ("https://minio.example.com", true),
+        ];
+
+        for (endpoint, path_style) in cases {
+            assert_eq!(
+                use_path_style(&alias(endpoint, "auto")).unwrap(),
+                path_style,
+                "{endpoint}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_explicit_lookup() {
+        assert!(use_path_style(&alias("https://s3.amazonaws.com", "path")).unwrap());
+        assert!(!use_path_style(&alias("http://localhost:9000", "dns")).unwrap());
+        assert!(use_path_style(&alias("http://localhost:9000", "virtual")).is_err());
+
+        let mut custom = alias("https://gateway.corp:9000", "auto");
+        custom.bucket_domain = Some("s3.internal.corp".to_string());
+        assert!(!use_path_style(&custom).unwrap());
+    }
+
+    #[test]
+    fn test_virtual_host_uri() {
+        assert_eq!(
+            virtual_host_uri(
+                "https://gateway.corp:9000/photos/2024/cat.jpg?versionId=1",
+                "s3.internal.corp"
+            )
+            .as_deref(),
+            Some("https://photos.s3.internal.corp:9000/2024/cat.jpg?versionId=1")
+        );
+        assert_eq!(
+            virtual_host_uri(
+                "https://gateway.corp/photos?list-type=2",
+                "s3.internal.corp"
+            )
+            .as_deref(),
+            Some("https://photos.s3.internal.corp/?list-type=2")
+        );
+
+        // ListBuckets and names that are not valid host labels stay path-style
+        assert_eq!(
+            virtual_host_uri("https://gateway.corp/", "s3.internal.corp"),
+            None
+        );
+        assert_eq!(
+            virtual_host_uri("https://gateway.corp/my.bucket/key", "s3.internal.corp"),
+            None
+        );
+        assert_eq!(
+            virtual_host_uri("https://gateway.corp/My_Bucket/key", "s3.internal.corp"),
+            None
+        );
+        assert_eq!(
+            virtual_host_uri("http://gateway.corp/my.bucket/key", "s3.internal.corp").as_deref(),
+            Some("http://my.bucket.s3.internal.corp/key")
+        );
+    }
+}
@@ -4,23 +4,19 @@
 //! using HTTP requests with AWS SigV4 signing.
 
 use async_trait::async_trait;
-use aws_credential_types::Credentials;
-use aws_sigv4::http_request::{
-    SignableBody, SignableRequest, SignatureLocation, SigningSettings, sign,
-};
-use aws_sigv4::sign::v4;
 use rc_core::admin::{
     AdminApi, BucketQuota, ClusterInfo, CreateServiceAccountRequest, Group, GroupStatus,
     HealStartRequest, HealStatus, Policy, PolicyEntity, PolicyInfo, ServiceAccount,
     UpdateGroupMembersRequest, User, UserStatus,
 };
-use rc_core::{Alias, Error, Result};
-use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
+use rc_core::{Alias, Error, Result, RetryConfig, is_retryable_error, retry_with_backoff};
+use reqwest::header::{CONTENT_TYPE, HeaderMap};
 use reqwest::{Client, Method, StatusCode};
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::time::SystemTime;
+use std::time::Duration;
+
+use crate::signing;
 
 /// Admin API client for RustFS/MinIO-compatible servers
 pub struct AdminClient {
@@ -29,18 +25,23 @@ pub struct AdminClient {
     access_key: String,
     secret_key: String,
     region: String,
+    retry: RetryConfig,
 }
 
 impl AdminClient {
     /// Create a new AdminClient from an Alias
     pub fn new(alias: &Alias) -> Result<Self> {
+        let timeout = alias.timeout_config();
         let http_client = Client::builder()
             .danger_accept_invalid_certs(alias.insecure)
+            .connect_timeout(Duration::from_millis(timeout.connect_ms))
+            .read_timeout(Duration::from_millis(timeout.read_ms))
             .build()
             .map_err(|e| Error::Network(format!("Failed to create HTTP client: {e}")))?;
 
         Ok(Self {
             http_client,
+            retry: alias.retry_config(),
             endpoint: alias.endpoint.trim_end_matches('/').to_string(),
             access_key: alias.access_key.clone(),
             secret_key: alias.secret_key.clone(),
@@ -55,71 +56,26 @@ impl AdminClient {
 
     /// Calculate SHA256 hash of the body
     fn sha256_hash(body: &[u8]) -> String {
-        let mut hasher = Sha256::new();
-        hasher.update(body);
-        hex::encode(hasher.finalize())
+        signing::sha256_hex(body)
     }
 
     /// Sign a request using AWS SigV4
-    async fn sign_request(
+    fn sign_request(
         &self,
         method: &Method,
         url: &str,
         headers: &HeaderMap,
         body: &[u8],
     ) -> Result<HeaderMap> {
-        let credentials = Credentials::new(
+        signing::sign_v4(
             &self.access_key,
             &self.secret_key,
-            None,
-            None,
-            "admin-client",
-        );
-
-        let identity = credentials.into();
-        let mut signing_settings = SigningSettings::default();
-        signing_settings.signature_location = SignatureLocation::Headers;
-
-        let signing_params = v4::SigningParams::builder()
-            .identity(&identity)
-            .region(&self.region)
-            .name("s3")
-            .time(SystemTime::now())
-            .settings(signing_settings)
-            .build()
-            .map_err(|e| Error::Auth(format!("Failed to build signing params: {e}")))?;
-
-        // Convert headers to a vec of tuples
-        let header_pairs: Vec<(&str, &str)> = headers
-            .iter()
-            .filter_map(|(k, v)| v.to_str().ok().map(|v| (k.as_str(), v)))
-            .collect();
-
-        let signable_body = SignableBody::Bytes(body);
-
-        let signable_request = SignableRequest::new(
-            method.as_str(),
+            &self.region,
+            method,
             url,
-            header_pairs.into_iter(),
-            signable_body,
+            headers,
+            body,
         )
-        .map_err(|e| Error::Auth(format!("Failed to create signable request: {e}")))?;
-
-        let (signing_instructions, _signature) = sign(signable_request, &signing_params.into())
-            .map_err(|e| Error::Auth(format!("Failed to sign request: {e}")))?
-            .into_parts();
-
-        // Apply signing instructions to create new headers
-        let mut signed_headers = headers.clone();
-        for (name, value) in signing_instructions.headers() {
-            let header_name = HeaderName::try_from(&name.to_string())
-                .map_err(|e| Error::Auth(format!("Invalid header name: {e}")))?;
-            let header_value = HeaderValue::try_from(&value.to_string())
-                .map_err(|e| Error::Auth(format!("Invalid header value: {e}")))?;
-            signed_headers.insert(header_name, header_value);
-        }
-
-        Ok(signed_headers)
     }
 
     /// Make a signed request to the admin API
@@ -130,64 +86,7 @@ impl AdminClient {
         query: Option<&[(&str, &str)]>,
         body: Option<&[u8]>,
     ) -> Result<T> {
-        let mut url = self.admin_url(path);
-
-        if let Some(q) = query {
-            let query_string: String = q
-                .iter()
-                .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
-                .collect::<Vec<_>>()
-                .join("&");
-            if !query_string.is_empty() {
-                url.push('?');
-                url.push_str(&query_string);
-            }
-        }
-
-        let body_bytes = body.unwrap_or(&[]);
-        let content_hash = Self::sha256_hash(body_bytes);
-
-        let mut headers = HeaderMap::new();
-        headers.insert("x-amz-content-sha256", content_hash.parse().unwrap());
-        headers.insert("host", self.get_host().parse().unwrap());
-
-        if !body_bytes.is_empty() {
-            headers.insert(CONTENT_TYPE, "application/json".parse().unwrap());
-        }
-
-        let signed_headers = self
-            .sign_request(&method, &url, &headers, body_bytes)
-            .await?;
-
-        let mut request_builder = self.http_client.request(method.clone(), &url);
-
-        for (name, value) in signed_headers.iter() {
-            request_builder = request_builder.header(name, value);
-        }
-
-        if !body_bytes.is_empty() {
-            request_builder = request_builder.body(body_bytes.to_vec());
-        }
-
-        let response = request_builder
-            .send()
-            .await
-            .map_err(|e| Error::Network(format!("Request failed: {e}")))?;
-
-        let status = response.status();
-
-        if !status.is_success() {
-            let error_body = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(self.map_error(status, &error_body));
-        }
-
-        let text = response
-            .text()
-            .await
-            .map_err(|e| Error::Network(format!("Failed to read response: {e}")))?;
+        let text = self.send(method, path, query, body).await?;
 
         if text.is_empty() {
             // Return empty/default for empty responses
@@ -205,6 +104,21 @@ impl AdminClient {
         query: Option<&[(&str, &str)]>,
         body: Option<&[u8]>,
     ) -> Result<()> {
+        self.send(method, path, query, body).await.map(|_| ())
+    }
+
+    /// Send a signed request and return the response body
+    ///
+    /// Only GET, HEAD and DELETE requests are retried on transient failures.
+    /// A POST or PUT that timed out may already have been applied, so
+    /// repeating it could create a user or start a heal twice.
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<&[(&str, &str)]>,
+        body: Option<&[u8]>,
+    ) -> Result<String> {
         let mut url = self.admin_url(path);
 
         if let Some(q) = query {
@@ -220,6 +134,21 @@ impl AdminClient {
         }
 
         let body_bytes = body.unwrap_or(&[]);
+
+        if !is_idempotent(&method) {
+            return self.send_once(&method, &url, body_bytes).await;
+        }
+
+        retry_with_backoff(
+            &self.retry,
+            || self.send_once(&method, &url, body_bytes),
+            is_retryable_error,
+        )
+        .await
+    }
+
+    /// Sign and send a single request attempt
+    async fn send_once(&self, method: &Method, url: &str, body_bytes: &[u8]) -> Result<String> {
         let content_hash = Self::sha256_hash(body_bytes);
 
         let mut headers = HeaderMap::new();
@@ -230,11 +159,10 @@ impl AdminClient {
             headers.insert(CONTENT_TYPE, "application/json".parse().unwrap());
         }
 
-        let signed_headers = self
-            .sign_request(&method, &url, &headers, body_bytes)
-            .await?;
+        // Signed per attempt so retries carry a fresh x-amz-date
+        let signed_headers = self.sign_request(method, url, &headers, body_bytes)?;
 
-        let mut request_builder = self.http_client.request(method.clone(), &url);
+        let mut request_builder = self.http_client.request(method.clone(), url);
 
         for (name, value) in signed_headers.iter() {
             request_builder = request_builder.header(name, value);
@@ -244,10 +172,7 @@ impl AdminClient {
             request_builder = request_builder.body(body_bytes.to_vec());
         }
 
-        let response = request_builder
-            .send()
-            .await
-            .map_err(|e| Error::Network(format!("Request failed: {e}")))?;
+        let response = request_builder.send().await.map_err(transport_error)?;
 
         let status = response.status();
 
@@ -259,7 +184,13 @@ impl AdminClient {
             return Err(self.map_error(status, &error_body));
         }
 
-        Ok(())
+        response.text().await.map_err(|e| {
+            if e.is_timeout() {
+                Error::Network(format!("Timeout reading response: {e}"))
+            } else {
+                Error::Network(format!("Failed to read response: {e}"))
+            }
+        })
     }
 
     /// Extract host from endpoint
@@ -282,6 +213,22 @@ impl AdminClient {
     }
 }
 
+/// Whether repeating a request with `method` has no further effect
+fn is_idempotent(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::DELETE)
+}
+
+/// Map a failure to send a request so that `is_retryable_error` can classify it
+fn transport_error(e: reqwest::Error) -> Error {
+    if e.is_timeout() {
+        Error::Network(format!("Request timeout: {e}"))
+    } else if e.is_connect() {
+        Error::Network(format!("Connection failed: {e}"))
+    } else {
+        Error::Network(format!("Request failed: {e}"))
+    }
+}
+
 /// Response wrapper for user list
 #[derive(Debug, Deserialize)]
 struct UserListResponse(HashMap<String, UserInfo>);
@@ -684,7 +631,38 @@ impl AdminApi for AdminClient {
 
 #[cfg(test)]
 mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
     use super::*;
+    use rc_core::TimeoutConfig;
+
+    /// Answer one request per entry in `responses`, in order
+    async fn serve(listener: TcpListener, responses: Vec<&'static str>) {
+        for response in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+
+            socket.write_all(response.as_bytes()).await.unwrap();
+            socket.shutdown().await.unwrap();
+        }
+    }
+
+    fn stub_alias(endpoint: &str) -> Alias {
+        let mut alias = Alias::new("stub", endpoint, "access", "secret");
+        alias.retry = Some(RetryConfig {
+            max_attempts: 3,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 10,
+        });
+        alias
+    }
 
     #[test]
     fn test_admin_url_construction() {
@@ -733,4 +711,89 @@ mod tests {
             "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
         );
     }
+
+    #[tokio::test]
+    async fn test_request_retries_unavailable() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve(
+            listener,
+            vec![
+                "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}",
+            ],
+        ));
+
+        let client = AdminClient::new(&stub_alias(&endpoint)).unwrap();
+        let users = client.list_users().await.unwrap();
+
+        assert!(users.is_empty());
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_request_does_not_retry_non_idempotent() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve(
+            listener,
+            vec![
+                "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                "HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            ],
+        ));
+
+        // A retry would reach the second response and succeed
+        let client = AdminClient::new(&stub_alias(&endpoint)).unwrap();
+        assert!(client.heal_stop().await.is_err());
+        assert!(!server.is_finished());
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_request_does_not_retry_forbidden() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve(
+            listener,
+            vec!["HTTP/1.1 403 Forbidden\r\nContent-Length: 6\r\nConnection: close\r\n\r\ndenied"],
+        ));
+
+        let client = AdminClient::new(&stub_alias(&endpoint)).unwrap();
+        let result = client.list_users().await;
+
+        assert!(matches!(result, Err(Error::Auth(_))));
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_request_read_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        // Accept connections but never answer
+        let server = tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            loop {
+                let (socket, _) = listener.accept().await.unwrap();
+                sockets.push(socket);
+            }
+        });
+
+        let mut alias = stub_alias(&endpoint);
+        alias.retry = Some(RetryConfig {
+            max_attempts: 1,
+            ..RetryConfig::default()
+        });
+        alias.timeout = Some(TimeoutConfig {
+            connect_ms: 1000,
+            read_ms: 100,
+        });
+
+        let client = AdminClient::new(&alias).unwrap();
+        match client.list_users().await {
+            Err(Error::Network(msg)) => assert!(msg.contains("timeout"), "{msg}"),
+            other => panic!("Expected timeout error, got: {other:?}"),
+        }
+        server.abort();
+    }
 }
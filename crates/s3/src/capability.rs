@@ -4,54 +4,165 @@
 //! This module provides capability detection to gracefully handle
 //! unsupported features.
 
+use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
 use rc_core::{Capabilities, Error, Result};
 
 /// Detect capabilities of an S3 backend
 ///
 /// This function probes the backend to determine which features are supported.
-/// For optional commands, we use this to determine whether to proceed or
-/// return EXIT_UNSUPPORTED_FEATURE.
+/// Commands that need a single feature should use [`probe_capability`] instead,
+/// which sends one request rather than one per feature.
 pub async fn detect_capabilities(
     client: &aws_sdk_s3::Client,
     bucket: &str,
 ) -> Result<Capabilities> {
-    // Note: Object Lock and S3 Select detection would require additional probes
-    // that might have side effects. For now, we default to false and let users
-    // use --force if they know their backend supports these features.
     let caps = Capabilities {
-        versioning: check_versioning(client, bucket).await,
-        tagging: check_tagging(client, bucket).await,
-        ..Default::default()
+        versioning: check_versioning(client, bucket).await?,
+        object_lock: check_object_lock(client, bucket).await?,
+        tagging: check_tagging(client, bucket).await?,
+        select: check_select(client, bucket).await?,
+        notifications: check_notifications(client, bucket).await?,
     };
 
     Ok(caps)
 }
 
+/// Probe whether the backend supports a single `feature` on `bucket`
+///
+/// Accepts the same feature names as [`require_capability`]. A missing bucket
+/// or denied request is returned as an error rather than as "unsupported", so
+/// callers can report it accurately.
+pub async fn probe_capability(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    feature: &str,
+) -> Result<bool> {
+    match feature {
+        "versioning" => check_versioning(client, bucket).await,
+        "object_lock" | "retention" => check_object_lock(client, bucket).await,
+        "tagging" => check_tagging(client, bucket).await,
+        "select" | "sql" => check_select(client, bucket).await,
+        "notifications" | "watch" => check_notifications(client, bucket).await,
+        _ => Ok(false),
+    }
+}
+
+/// Turn a probe failure that says nothing about the feature into an error
+///
+/// Returns `None` when the backend answered and the probe should decide from
+/// the error code whether the feature exists.
+fn probe_error<E, R>(bucket: &str, error: &SdkError<E, R>) -> Option<Error>
+where
+    E: ProvideErrorMetadata,
+{
+    match error {
+        SdkError::TimeoutError(_) => Some(Error::Network("Request timeout".to_string())),
+        SdkError::DispatchFailure(e) => {
+            Some(Error::Network(format!("Network dispatch error: {e:?}")))
+        }
+        _ => match error.code() {
+            Some("NoSuchBucket") => Some(Error::NotFound(format!("Bucket not found: {bucket}"))),
+            Some("AccessDenied") => Some(Error::Auth(format!("Access denied to bucket: {bucket}"))),
+            _ => None,
+        },
+    }
+}
+
 /// Check if bucket versioning is supported
-async fn check_versioning(client: &aws_sdk_s3::Client, bucket: &str) -> bool {
+async fn check_versioning(client: &aws_sdk_s3::Client, bucket: &str) -> Result<bool> {
     // Try to get versioning configuration
     // If we get a successful response (even if versioning is not enabled),
     // the backend supports versioning
-    client
-        .get_bucket_versioning()
+    match client.get_bucket_versioning().bucket(bucket).send().await {
+        Ok(_) => Ok(true),
+        Err(e) => probe_error(bucket, &e).map_or(Ok(false), Err),
+    }
+}
+
+/// Check if object lock is supported
+async fn check_object_lock(client: &aws_sdk_s3::Client, bucket: &str) -> Result<bool> {
+    // Reading the object lock configuration has no side effects. A bucket
+    // created without object lock answers with a "not found" error, which
+    // still shows the backend implements the API.
+    match client
+        .get_object_lock_configuration()
         .bucket(bucket)
         .send()
         .await
-        .is_ok()
+    {
+        Ok(_) => Ok(true),
+        Err(e) => probe_error(bucket, &e).map_or_else(
+            || Ok(e.code() == Some("ObjectLockConfigurationNotFoundError")),
+            Err,
+        ),
+    }
 }
 
 /// Check if object tagging is supported
-async fn check_tagging(client: &aws_sdk_s3::Client, bucket: &str) -> bool {
+async fn check_tagging(client: &aws_sdk_s3::Client, bucket: &str) -> Result<bool> {
     // Try to get bucket tagging
     // Even if no tags are set, a supported backend will return a valid response
     // or a specific "no tags" error, not an unsupported operation error
     match client.get_bucket_tagging().bucket(bucket).send().await {
-        Ok(_) => true,
+        Ok(_) => Ok(true),
+        // NoSuchTagSet means tagging is supported, just no tags set
         Err(e) => {
-            let err = e.into_service_error();
-            // NoSuchTagSet means tagging is supported, just no tags set
-            // AccessDenied might mean we don't have permission but feature exists
-            !err.to_string().contains("NotImplemented")
+            probe_error(bucket, &e).map_or_else(|| Ok(e.code() != Some("NotImplemented")), Err)
+        }
+    }
+}
+
+/// Key queried by the S3 Select probe; it is not expected to exist
+const SELECT_PROBE_KEY: &str = ".rc-select-probe";
+
+/// Check if S3 Select is supported
+async fn check_select(client: &aws_sdk_s3::Client, bucket: &str) -> Result<bool> {
+    use aws_sdk_s3::types::{
+        CsvInput, CsvOutput, ExpressionType, InputSerialization, OutputSerialization,
+    };
+
+    // Querying a missing key has no side effects. A backend implementing
+    // S3 Select rejects it as a missing key rather than an unknown operation.
+    let result = client
+        .select_object_content()
+        .bucket(bucket)
+        .key(SELECT_PROBE_KEY)
+        .expression("SELECT * FROM S3Object")
+        .expression_type(ExpressionType::Sql)
+        .input_serialization(
+            InputSerialization::builder()
+                .csv(CsvInput::builder().build())
+                .build(),
+        )
+        .output_serialization(
+            OutputSerialization::builder()
+                .csv(CsvOutput::builder().build())
+                .build(),
+        )
+        .send()
+        .await;
+
+    match result {
+        Ok(_) => Ok(true),
+        Err(e) => probe_error(bucket, &e).map_or_else(|| Ok(e.code() == Some("NoSuchKey")), Err),
+    }
+}
+
+/// Check if bucket event notifications are supported
+///
+/// Some backends accept the configuration API without supporting the listen
+/// extension; `NotificationClient::listen` reports those when it connects.
+async fn check_notifications(client: &aws_sdk_s3::Client, bucket: &str) -> Result<bool> {
+    // An empty notification configuration is still a valid response
+    match client
+        .get_bucket_notification_configuration()
+        .bucket(bucket)
+        .send()
+        .await
+    {
+        Ok(_) => Ok(true),
+        Err(e) => {
+            probe_error(bucket, &e).map_or_else(|| Ok(e.code() != Some("NotImplemented")), Err)
         }
     }
 }
@@ -79,6 +190,41 @@ pub fn require_capability(caps: &Capabilities, feature: &str) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::S3Client;
+    use crate::stub::{StubResponse, StubServer};
+
+    async fn probe_object_lock(response: StubResponse) -> (Result<bool>, usize) {
+        let server = StubServer::start(move |_| response.clone()).await;
+        let client = S3Client::new(server.alias("stub")).await.unwrap();
+        let result = client.probe_capability("bucket", "object_lock").await;
+        (result, server.requests().len())
+    }
+
+    #[tokio::test]
+    async fn test_probe_object_lock_sends_one_request() {
+        let (result, requests) = probe_object_lock(StubResponse::error(
+            "404 Not Found",
+            "ObjectLockConfigurationNotFoundError",
+        ))
+        .await;
+        assert!(result.unwrap());
+        assert_eq!(requests, 1);
+
+        let (result, _) =
+            probe_object_lock(StubResponse::error("501 Not Implemented", "NotImplemented")).await;
+        assert!(!result.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_probe_reports_missing_bucket_and_denied_access() {
+        let (result, _) =
+            probe_object_lock(StubResponse::error("404 Not Found", "NoSuchBucket")).await;
+        assert!(matches!(result, Err(Error::NotFound(_))), "{result:?}");
+
+        let (result, _) =
+            probe_object_lock(StubResponse::error("403 Forbidden", "AccessDenied")).await;
+        assert!(matches!(result, Err(Error::Auth(_))), "{result:?}");
+    }
 
     #[test]
     fn test_require_capability_versioning() {
@@ -95,6 +241,16 @@ mod tests {
         assert!(require_capability(&caps, "versioning").is_err());
     }
 
+    #[test]
+    fn test_require_capability_retention() {
+        let caps = Capabilities {
+            object_lock: true,
+            ..Default::default()
+        };
+        assert!(require_capability(&caps, "retention").is_ok());
+        assert!(require_capability(&Capabilities::default(), "object_lock").is_err());
+    }
+
     #[test]
     fn test_require_capability_unknown() {
         let caps = Capabilities::default();
@@ -3,6 +3,11 @@
 //! Wraps aws-sdk-s3 and implements the ObjectStore trait from rc-core.
 
 use async_trait::async_trait;
+use aws_sdk_s3::config::{RequestChecksumCalculation, ResponseChecksumValidation};
+use aws_sdk_s3::error::ProvideErrorMetadata;
+use aws_sdk_s3::types::{
+    ObjectLockEnabled, ObjectLockLegalHold, ObjectLockLegalHoldStatus, ObjectLockRetentionMode,
+};
 use aws_smithy_runtime_api::client::http::{
     HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpConnector,
 };
@@ -14,9 +19,56 @@ use aws_smithy_types::body::SdkBody;
 use bytes::Bytes;
 use jiff::Timestamp;
 use rc_core::{
-    Alias, Capabilities, Error, ListOptions, ListResult, ObjectInfo, ObjectStore, ObjectVersion,
-    RemotePath, Result,
+    Alias, ByteReader, Capabilities, CreateBucketOptions, DefaultRetention, DeleteOptions,
+    DeleteTarget, Error, ListOptions, ListResult, MultipartUploadInfo, ObjectInfo,
+    ObjectLockConfig, ObjectRetention, ObjectStore, ObjectStream, ObjectVersion, RemotePath,
+    Result, RetentionMode, RetentionValidity, SelectCompression, SelectFormat, SelectOptions,
+    TimeoutConfig, VersionMarker, VersionPage,
 };
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::addressing::{VirtualHostInterceptor, use_path_style};
+use crate::multipart::{CompletedPart, DEFAULT_PART_SIZE};
+use crate::signing::{self, SigV2Interceptor};
+
+/// Build a reqwest client honouring the alias TLS settings
+///
+/// `read_timeout` bounds the wait for each chunk of a response; pass `None` for
+/// long-lived streams that may stay silent for a while.
+pub(crate) async fn build_http_client(
+    insecure: bool,
+    ca_bundle: Option<&str>,
+    connect_timeout: Duration,
+    read_timeout: Option<Duration>,
+) -> Result<reqwest::Client> {
+    // NOTE: When `insecure = true`, `danger_accept_invalid_certs` disables all TLS
+    // certificate verification. Any CA bundle provided will still be added to the
+    // trust store but is rendered ineffective for this connection.
+    let mut builder = reqwest::Client::builder()
+        .danger_accept_invalid_certs(insecure)
+        .connect_timeout(connect_timeout);
+
+    if let Some(read_timeout) = read_timeout {
+        builder = builder.read_timeout(read_timeout);
+    }
+
+    if let Some(bundle_path) = ca_bundle {
+        // Use tokio::fs::read to avoid blocking the async runtime thread.
+        let pem = tokio::fs::read(bundle_path).await.map_err(|e| {
+            Error::Network(format!("Failed to read CA bundle '{bundle_path}': {e}"))
+        })?;
+        let cert = reqwest::Certificate::from_pem(&pem)
+            .map_err(|e| Error::Network(format!("Invalid CA bundle '{bundle_path}': {e}")))?;
+        builder = builder.add_root_certificate(cert);
+    }
+
+    builder
+        .build()
+        .map_err(|e| Error::Network(format!("Failed to build HTTP client: {e}")))
+}
 
 /// Custom HTTP connector using reqwest, supporting insecure TLS (skip cert verification)
 /// and custom CA bundles. Used when `alias.insecure = true` or `alias.ca_bundle.is_some()`.
@@ -26,25 +78,14 @@ struct ReqwestConnector {
 }
 
 impl ReqwestConnector {
-    async fn new(insecure: bool, ca_bundle: Option<&str>) -> Result<Self> {
-        // NOTE: When `insecure = true`, `danger_accept_invalid_certs` disables all TLS
-        // certificate verification. Any CA bundle provided will still be added to the
-        // trust store but is rendered ineffective for this connection.
-        let mut builder = reqwest::Client::builder().danger_accept_invalid_certs(insecure);
-
-        if let Some(bundle_path) = ca_bundle {
-            // Use tokio::fs::read to avoid blocking the async runtime thread.
-            let pem = tokio::fs::read(bundle_path).await.map_err(|e| {
-                Error::Network(format!("Failed to read CA bundle '{bundle_path}': {e}"))
-            })?;
-            let cert = reqwest::Certificate::from_pem(&pem)
-                .map_err(|e| Error::Network(format!("Invalid CA bundle '{bundle_path}': {e}")))?;
-            builder = builder.add_root_certificate(cert);
-        }
-
-        let client = builder
-            .build()
-            .map_err(|e| Error::Network(format!("Failed to build HTTP client: {e}")))?;
+    async fn new(insecure: bool, ca_bundle: Option<&str>, timeout: &TimeoutConfig) -> Result<Self> {
+        let client = build_http_client(
+            insecure,
+            ca_bundle,
+            Duration::from_millis(timeout.connect_ms),
+            Some(Duration::from_millis(timeout.read_ms)),
+        )
+        .await?;
         Ok(Self { client })
     }
 }
@@ -58,19 +99,11 @@ impl HttpConnector for ReqwestConnector {
             let method_str = request.method().to_string();
             let headers = request.headers().clone();
 
-            // Try to get the body as buffered in-memory bytes.
-            // For streaming bodies (e.g., large file uploads), bytes() returns None and we
-            // return a clear error rather than silently sending an empty body, which would
-            // cause signature mismatches or server-side failures.
-            let body_bytes = match request.body().bytes() {
-                Some(b) => Bytes::copy_from_slice(b),
-                None => {
-                    return Err(ConnectorError::user(
-                        "Streaming request bodies are not supported in insecure/ca_bundle TLS mode; \
-                         use in-memory data for uploads with this connector"
-                            .into(),
-                    ));
-                }
+            // In-memory bodies are sent as-is; streaming bodies (e.g. file uploads) are
+            // forwarded chunk by chunk so large uploads never get buffered here.
+            let body = match request.body().bytes() {
+                Some(b) => reqwest::Body::from(Bytes::copy_from_slice(b)),
+                None => reqwest::Body::wrap(request.into_body()),
             };
 
             // Build reqwest method
@@ -99,7 +132,7 @@ impl HttpConnector for ReqwestConnector {
             }
 
             // Set body
-            *req.body_mut() = Some(reqwest::Body::from(body_bytes));
+            *req.body_mut() = Some(body);
 
             // Execute
             let resp = client
@@ -111,12 +144,11 @@ impl HttpConnector for ReqwestConnector {
             let status = StatusCode::try_from(resp.status().as_u16())
                 .map_err(|e| ConnectorError::other(Box::new(e), None))?;
             let resp_headers = resp.headers().clone();
-            let body = resp
-                .bytes()
-                .await
-                .map_err(|e| ConnectorError::io(Box::new(e)))?;
 
-            let mut sdk_response = Response::new(status, SdkBody::from(body));
+            // Hand the response body to the SDK as a stream so downloads stay bounded
+            let body = SdkBody::from_body_1_x(reqwest::Body::from(resp));
+
+            let mut sdk_response = Response::new(status, body);
             for (name, value) in &resp_headers {
                 match value.to_str() {
                     Ok(value_str) => {
@@ -141,19 +173,57 @@ impl HttpClient for ReqwestConnector {
         _settings: &HttpConnectorSettings,
         _components: &RuntimeComponents,
     ) -> SharedHttpConnector {
-        // NOTE: `ReqwestConnector` is preconfigured (e.g., insecure/CA-bundle options) when it
-        // is constructed, and does not currently apply `HttpConnectorSettings`. This means
-        // behavior in this mode may differ from the default connector w.r.t. SDK HTTP settings.
-        // If alignment is required, map relevant fields from `HttpConnectorSettings` onto the
-        // internal `reqwest::Client` when constructing the connector.
+        // NOTE: `ReqwestConnector` is preconfigured (insecure/CA-bundle options and the alias
+        // connect/read timeouts) when it is constructed, and does not apply
+        // `HttpConnectorSettings`. The SDK timeout config carries the same alias values, so
+        // both connectors end up with equivalent timeouts.
         SharedHttpConnector::new(self.clone())
     }
 }
 
+/// Domain under which buckets of an alias are addressed as virtual hosts
+///
+/// This is the `bucket_domain` when set, otherwise the endpoint host name.
+fn virtual_host_base(alias: &Alias) -> String {
+    if let Some(domain) = &alias.bucket_domain {
+        return domain.trim_matches('.').to_string();
+    }
+    url::Url::parse(&alias.endpoint)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Object metadata from a HEAD response
+fn head_object_info(
+    path: &RemotePath,
+    response: &aws_sdk_s3::operation::head_object::HeadObjectOutput,
+) -> ObjectInfo {
+    let size = response.content_length().unwrap_or(0);
+    let mut info = ObjectInfo::file(&path.key, size);
+
+    if let Some(modified) = response.last_modified() {
+        info.last_modified = jiff::Timestamp::from_second(modified.secs()).ok();
+    }
+
+    if let Some(etag) = response.e_tag() {
+        info.etag = Some(etag.trim_matches('"').to_string());
+    }
+
+    if let Some(ct) = response.content_type() {
+        info.content_type = Some(ct.to_string());
+    }
+
+    if let Some(sc) = response.storage_class() {
+        info.storage_class = Some(sc.as_str().to_string());
+    }
+
+    info
+}
+
 /// S3 client wrapper
 pub struct S3Client {
     inner: aws_sdk_s3::Client,
-    #[allow(dead_code)]
     alias: Alias,
 }
 
@@ -180,22 +250,66 @@ impl S3Client {
             .region(aws_config::Region::new(region))
             .endpoint_url(&endpoint);
 
+        // Retries use the SDK standard strategy: exponential backoff with full jitter,
+        // applied only to transient failures (throttling, 5xx, timeouts, I/O errors).
+        let retry = alias.retry_config();
+        let timeout = alias.timeout_config();
+        config_loader = config_loader
+            .retry_config(
+                aws_config::retry::RetryConfig::standard()
+                    .with_max_attempts(retry.max_attempts.max(1))
+                    .with_initial_backoff(Duration::from_millis(retry.initial_backoff_ms))
+                    .with_max_backoff(Duration::from_millis(retry.max_backoff_ms)),
+            )
+            .timeout_config(
+                aws_config::timeout::TimeoutConfig::builder()
+                    .connect_timeout(Duration::from_millis(timeout.connect_ms))
+                    .read_timeout(Duration::from_millis(timeout.read_ms))
+                    .build(),
+            );
+
         // When insecure mode is enabled or a custom CA bundle is provided, use the reqwest
         // connector which supports danger_accept_invalid_certs and custom root certificates.
         if alias.insecure || alias.ca_bundle.is_some() {
             let connector =
-                ReqwestConnector::new(alias.insecure, alias.ca_bundle.as_deref()).await?;
+                ReqwestConnector::new(alias.insecure, alias.ca_bundle.as_deref(), &timeout).await?;
             config_loader = config_loader.http_client(connector);
         }
 
         let config = config_loader.load().await;
 
-        // Build S3 client with path-style addressing for compatibility
-        let s3_config = aws_sdk_s3::config::Builder::from(&config)
-            .force_path_style(alias.bucket_lookup == "path" || alias.bucket_lookup == "auto")
-            .build();
+        // With a custom bucket domain the SDK builds path-style requests and the
+        // interceptor moves the bucket into the host name
+        let path_style = use_path_style(&alias)?;
+        let mut s3_config = aws_sdk_s3::config::Builder::from(&config)
+            .force_path_style(path_style || alias.bucket_domain.is_some());
 
-        let client = aws_sdk_s3::Client::from_conf(s3_config);
+        if !path_style && let Some(domain) = &alias.bucket_domain {
+            s3_config = s3_config.interceptor(VirtualHostInterceptor::new(domain));
+        }
+
+        match alias.signature.as_str() {
+            "v4" => {}
+            "v2" => {
+                // SigV2 backends predate flexible checksums, so only send them when required
+                s3_config = s3_config
+                    .interceptor(SigV2Interceptor::new(
+                        &alias.access_key,
+                        &alias.secret_key,
+                        &virtual_host_base(&alias),
+                    ))
+                    .request_checksum_calculation(RequestChecksumCalculation::WhenRequired)
+                    .response_checksum_validation(ResponseChecksumValidation::WhenRequired);
+            }
+            other => {
+                return Err(Error::Config(format!(
+                    "Unsupported signature version '{other}' for alias '{}'. Expected 'v4' or 'v2'",
+                    alias.name
+                )));
+            }
+        }
+
+        let client = aws_sdk_s3::Client::from_conf(s3_config.build());
 
         Ok(Self {
             inner: client,
@@ -208,6 +322,51 @@ impl S3Client {
         &self.inner
     }
 
+    /// Probe the capabilities of the backend using `bucket`
+    ///
+    /// Unlike [`ObjectStore::capabilities`], this sends requests to detect
+    /// bucket-level features such as object lock.
+    pub async fn bucket_capabilities(&self, bucket: &str) -> Result<Capabilities> {
+        crate::capability::detect_capabilities(&self.inner, bucket).await
+    }
+
+    /// Probe whether the backend supports a single `feature` using `bucket`
+    ///
+    /// Sends one request, so commands that depend on one feature do not pay
+    /// for a full [`S3Client::bucket_capabilities`] probe.
+    pub async fn probe_capability(&self, bucket: &str, feature: &str) -> Result<bool> {
+        crate::capability::probe_capability(&self.inner, bucket, feature).await
+    }
+
+    /// Return a presigned URL in the signature version of the alias
+    ///
+    /// The SDK always presigns with SigV4; for SigV2 aliases the URL is
+    /// re-signed with the same expiry.
+    fn finish_presign(
+        &self,
+        method: &str,
+        presigned_v4: &str,
+        content_type: Option<&str>,
+        expires_secs: u64,
+    ) -> Result<String> {
+        if self.alias.signature != "v2" {
+            return Ok(presigned_v4.to_string());
+        }
+
+        let expires = Timestamp::now()
+            .checked_add(jiff::SignedDuration::from_secs(expires_secs as i64))
+            .map_err(|e| Error::General(format!("Invalid presign expiry: {e}")))?;
+        signing::presign_v2(
+            &self.alias.access_key,
+            &self.alias.secret_key,
+            method,
+            presigned_v4,
+            &virtual_host_base(&self.alias),
+            content_type,
+            expires,
+        )
+    }
+
     /// Format AWS SDK error into a detailed error message
     fn format_sdk_error<E: std::fmt::Display>(error: &aws_sdk_s3::error::SdkError<E>) -> String {
         match error {
@@ -236,355 +395,385 @@ impl S3Client {
             _ => error.to_string(),
         }
     }
-}
-
-fn build_tagging(
-    tags: std::collections::HashMap<String, String>,
-) -> Result<aws_sdk_s3::types::Tagging> {
-    use aws_sdk_s3::types::{Tag, Tagging};
-
-    let mut tag_set = Vec::with_capacity(tags.len());
-    for (key, value) in tags {
-        let tag = Tag::builder()
-            .key(key)
-            .value(value)
-            .build()
-            .map_err(|e| Error::General(format!("invalid tag: {e}")))?;
-        tag_set.push(tag);
-    }
-
-    Tagging::builder()
-        .set_tag_set(Some(tag_set))
-        .build()
-        .map_err(|e| Error::General(format!("invalid tagging payload: {e}")))
-}
 
-#[async_trait]
-impl ObjectStore for S3Client {
-    async fn list_buckets(&self) -> Result<Vec<ObjectInfo>> {
-        let response = self
+    /// Stream a byte range `[start, end)` of an object, or one of its versions
+    ///
+    /// When `etag` is given the request fails if the object has changed since.
+    pub async fn get_object_range(
+        &self,
+        path: &RemotePath,
+        version_id: Option<&str>,
+        start: u64,
+        end: u64,
+        etag: Option<&str>,
+    ) -> Result<ByteReader> {
+        let mut request = self
             .inner
-            .list_buckets()
-            .send()
-            .await
-            .map_err(|e| Error::Network(e.to_string()))?;
-
-        let buckets = response
-            .buckets()
-            .iter()
-            .map(|b| {
-                let mut info = ObjectInfo::bucket(b.name().unwrap_or_default());
-                if let Some(creation_date) = b.creation_date() {
-                    info.last_modified = jiff::Timestamp::from_second(creation_date.secs()).ok();
-                }
-                info
-            })
-            .collect();
-
-        Ok(buckets)
-    }
-
-    async fn list_objects(&self, path: &RemotePath, options: ListOptions) -> Result<ListResult> {
-        let mut request = self.inner.list_objects_v2().bucket(&path.bucket);
-
-        // Set prefix
-        let prefix = if path.key.is_empty() {
-            options.prefix.clone()
-        } else if let Some(p) = &options.prefix {
-            Some(format!("{}{}", path.key, p))
-        } else {
-            Some(path.key.clone())
-        };
-
-        if let Some(p) = prefix {
-            request = request.prefix(p);
-        }
-
-        // Set delimiter (for non-recursive listing)
-        if !options.recursive {
-            request = request.delimiter(options.delimiter.as_deref().unwrap_or("/"));
-        }
-
-        // Set max keys
-        if let Some(max) = options.max_keys {
-            request = request.max_keys(max);
-        }
+            .get_object()
+            .bucket(&path.bucket)
+            .key(&path.key)
+            .set_version_id(version_id.map(str::to_string))
+            .range(format!("bytes={start}-{}", end.saturating_sub(1)));
 
-        // Set continuation token
-        if let Some(token) = &options.continuation_token {
-            request = request.continuation_token(token);
+        if let Some(etag) = etag {
+            request = request.if_match(format!("\"{}\"", etag.trim_matches('"')));
         }
 
         let response = request.send().await.map_err(|e| {
             let err_str = Self::format_sdk_error(&e);
-            if err_str.contains("NotFound") || err_str.contains("NoSuchBucket") {
-                Error::NotFound(format!("Bucket not found: {}", path.bucket))
+            if err_str.contains("NotFound") || err_str.contains("NoSuchKey") {
+                Error::NotFound(path.to_string())
+            } else if err_str.contains("PreconditionFailed") {
+                Error::Conflict(format!("{path} changed during download"))
             } else {
                 Error::Network(err_str)
             }
         })?;
 
-        let mut items = Vec::new();
+        Ok(Box::pin(response.body.into_async_read()))
+    }
 
-        // Add common prefixes (directories)
-        for prefix in response.common_prefixes() {
-            if let Some(p) = prefix.prefix() {
-                items.push(ObjectInfo::dir(p));
-            }
-        }
+    /// Read the metadata of an object, or one of its versions, along with its
+    /// content type, user metadata and tags
+    ///
+    /// Uses a single HEAD request, plus a tagging request for tagged objects.
+    pub async fn head_object_with_attributes(
+        &self,
+        path: &RemotePath,
+        version_id: Option<&str>,
+    ) -> Result<(ObjectInfo, ObjectAttributes)> {
+        let response = self.head_response(path, version_id).await?;
 
-        // Add objects
-        for object in response.contents() {
-            let key = object.key().unwrap_or_default().to_string();
-            let size = object.size().unwrap_or(0);
-            let mut info = ObjectInfo::file(&key, size);
+        let tags = if response.tag_count().unwrap_or(0) > 0 {
+            self.tags(path, version_id).await?
+        } else {
+            HashMap::new()
+        };
 
-            if let Some(modified) = object.last_modified() {
-                info.last_modified = jiff::Timestamp::from_second(modified.secs()).ok();
-            }
+        let attributes = ObjectAttributes {
+            content_type: response.content_type().map(|ct| ct.to_string()),
+            metadata: response.metadata().cloned().unwrap_or_default(),
+            tags,
+        };
+        Ok((head_object_info(path, &response), attributes))
+    }
 
-            if let Some(etag) = object.e_tag() {
-                info.etag = Some(etag.trim_matches('"').to_string());
-            }
+    /// Upload an in-memory object with the given attributes
+    pub async fn put_object_with_attributes(
+        &self,
+        path: &RemotePath,
+        data: Bytes,
+        attributes: &ObjectAttributes,
+    ) -> Result<ObjectInfo> {
+        let size = data.len() as i64;
 
-            if let Some(sc) = object.storage_class() {
-                info.storage_class = Some(sc.as_str().to_string());
-            }
+        let response = self
+            .inner
+            .put_object()
+            .bucket(&path.bucket)
+            .key(&path.key)
+            .set_content_type(attributes.content_type.clone())
+            .set_metadata(attributes.metadata_opt())
+            .set_tagging(attributes.tagging_header())
+            .body(aws_sdk_s3::primitives::ByteStream::from(data))
+            .send()
+            .await
+            .map_err(|e| Error::Network(Self::format_sdk_error(&e)))?;
 
-            items.push(info);
+        let mut info = ObjectInfo::file(&path.key, size);
+        if let Some(etag) = response.e_tag() {
+            info.etag = Some(etag.trim_matches('"').to_string());
         }
+        info.last_modified = Some(jiff::Timestamp::now());
 
-        Ok(ListResult {
-            items,
-            truncated: response.is_truncated().unwrap_or(false),
-            continuation_token: response.next_continuation_token().map(|s| s.to_string()),
-        })
+        Ok(info)
     }
 
-    async fn head_object(&self, path: &RemotePath) -> Result<ObjectInfo> {
+    /// Start a multipart upload and return its upload ID
+    pub async fn create_multipart_upload(
+        &self,
+        path: &RemotePath,
+        attributes: &ObjectAttributes,
+    ) -> Result<String> {
+        let request = self
+            .inner
+            .create_multipart_upload()
+            .bucket(&path.bucket)
+            .key(&path.key)
+            .set_content_type(attributes.content_type.clone())
+            .set_metadata(attributes.metadata_opt())
+            .set_tagging(attributes.tagging_header());
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::Network(Self::format_sdk_error(&e)))?;
+
+        response
+            .upload_id()
+            .map(|id| id.to_string())
+            .ok_or_else(|| Error::General("create_multipart_upload: missing upload ID".into()))
+    }
+
+    /// Upload a single part and return its ETag
+    pub async fn upload_part(
+        &self,
+        path: &RemotePath,
+        upload_id: &str,
+        part_number: i32,
+        data: Bytes,
+    ) -> Result<String> {
         let response = self
             .inner
-            .head_object()
+            .upload_part()
             .bucket(&path.bucket)
             .key(&path.key)
+            .upload_id(upload_id)
+            .part_number(part_number)
+            .content_length(data.len() as i64)
+            .body(aws_sdk_s3::primitives::ByteStream::from(data))
             .send()
             .await
-            .map_err(|e| {
-                let err_str = e.to_string();
-                if err_str.contains("NotFound") || err_str.contains("NoSuchKey") {
-                    Error::NotFound(path.to_string())
-                } else {
-                    Error::Network(err_str)
-                }
-            })?;
+            .map_err(|e| Error::Network(Self::format_sdk_error(&e)))?;
 
-        let size = response.content_length().unwrap_or(0);
-        let mut info = ObjectInfo::file(&path.key, size);
+        response
+            .e_tag()
+            .map(|etag| etag.to_string())
+            .ok_or_else(|| {
+                Error::General(format!("upload_part: missing ETag for part {part_number}"))
+            })
+    }
 
-        if let Some(modified) = response.last_modified() {
-            info.last_modified = jiff::Timestamp::from_second(modified.secs()).ok();
-        }
+    /// Complete a multipart upload from its uploaded parts
+    pub async fn complete_multipart_upload(
+        &self,
+        path: &RemotePath,
+        upload_id: &str,
+        parts: &[CompletedPart],
+    ) -> Result<ObjectInfo> {
+        use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart as SdkCompletedPart};
+
+        let mut parts = parts.to_vec();
+        parts.sort_by_key(|p| p.part_number);
+
+        let completed = CompletedMultipartUpload::builder()
+            .set_parts(Some(
+                parts
+                    .iter()
+                    .map(|p| {
+                        SdkCompletedPart::builder()
+                            .part_number(p.part_number)
+                            .e_tag(&p.etag)
+                            .build()
+                    })
+                    .collect(),
+            ))
+            .build();
+
+        let response = self
+            .inner
+            .complete_multipart_upload()
+            .bucket(&path.bucket)
+            .key(&path.key)
+            .upload_id(upload_id)
+            .multipart_upload(completed)
+            .send()
+            .await
+            .map_err(|e| Error::Network(Self::format_sdk_error(&e)))?;
 
+        let mut info = ObjectInfo::file(&path.key, 0);
+        info.size_bytes = None;
+        info.size_human = None;
         if let Some(etag) = response.e_tag() {
             info.etag = Some(etag.trim_matches('"').to_string());
         }
-
-        if let Some(ct) = response.content_type() {
-            info.content_type = Some(ct.to_string());
-        }
-
-        if let Some(sc) = response.storage_class() {
-            info.storage_class = Some(sc.as_str().to_string());
-        }
+        info.last_modified = Some(jiff::Timestamp::now());
 
         Ok(info)
     }
 
-    async fn bucket_exists(&self, bucket: &str) -> Result<bool> {
-        match self.inner.head_bucket().bucket(bucket).send().await {
-            Ok(_) => Ok(true),
-            Err(e) => {
-                let err_str = e.to_string();
-                if err_str.contains("NotFound") || err_str.contains("NoSuchBucket") {
-                    Ok(false)
+    /// List the parts already uploaded for a multipart upload
+    pub async fn list_parts(
+        &self,
+        path: &RemotePath,
+        upload_id: &str,
+    ) -> Result<Vec<CompletedPart>> {
+        let mut parts = Vec::new();
+        let mut marker: Option<String> = None;
+
+        loop {
+            let mut request = self
+                .inner
+                .list_parts()
+                .bucket(&path.bucket)
+                .key(&path.key)
+                .upload_id(upload_id);
+
+            if let Some(m) = &marker {
+                request = request.part_number_marker(m);
+            }
+
+            let response = request.send().await.map_err(|e| {
+                let err_str = Self::format_sdk_error(&e);
+                if err_str.contains("NoSuchUpload") {
+                    Error::NotFound(format!("Upload {upload_id} for {path}"))
                 } else {
-                    Err(Error::Network(err_str))
+                    Error::Network(err_str)
+                }
+            })?;
+
+            for part in response.parts() {
+                if let (Some(part_number), Some(etag)) = (part.part_number(), part.e_tag()) {
+                    parts.push(CompletedPart {
+                        part_number,
+                        etag: etag.to_string(),
+                    });
+                }
+            }
+
+            if response.is_truncated() == Some(true) {
+                marker = response.next_part_number_marker().map(|m| m.to_string());
+                if marker.is_none() {
+                    break;
                 }
+            } else {
+                break;
             }
         }
+
+        Ok(parts)
     }
 
-    async fn create_bucket(&self, bucket: &str) -> Result<()> {
-        self.inner
-            .create_bucket()
-            .bucket(bucket)
-            .send()
-            .await
-            .map_err(|e| Error::Network(e.to_string()))?;
-
-        Ok(())
+    /// HEAD an object, or one of its versions
+    async fn head(&self, path: &RemotePath, version_id: Option<&str>) -> Result<ObjectInfo> {
+        let response = self.head_response(path, version_id).await?;
+        Ok(head_object_info(path, &response))
     }
 
-    async fn delete_bucket(&self, bucket: &str) -> Result<()> {
+    /// Send a HEAD request for an object, or one of its versions
+    async fn head_response(
+        &self,
+        path: &RemotePath,
+        version_id: Option<&str>,
+    ) -> Result<aws_sdk_s3::operation::head_object::HeadObjectOutput> {
         self.inner
-            .delete_bucket()
-            .bucket(bucket)
-            .send()
-            .await
-            .map_err(|e| {
-                let err_str = e.to_string();
-                if err_str.contains("NotFound") || err_str.contains("NoSuchBucket") {
-                    Error::NotFound(format!("Bucket not found: {bucket}"))
-                } else {
-                    Error::Network(err_str)
-                }
-            })?;
-
-        Ok(())
-    }
-
-    async fn capabilities(&self) -> Result<Capabilities> {
-        // For now, return conservative defaults
-        // In Phase 5, we'll implement actual capability detection
-        Ok(Capabilities {
-            versioning: true,
-            object_lock: false,
-            tagging: true,
-            select: false,
-            notifications: false,
-        })
-    }
-
-    async fn get_object(&self, path: &RemotePath) -> Result<Vec<u8>> {
-        let response = self
-            .inner
-            .get_object()
+            .head_object()
             .bucket(&path.bucket)
             .key(&path.key)
+            .set_version_id(version_id.map(str::to_string))
             .send()
             .await
             .map_err(|e| {
                 let err_str = e.to_string();
-                if err_str.contains("NotFound") || err_str.contains("NoSuchKey") {
+                if err_str.contains("NotFound")
+                    || err_str.contains("NoSuchKey")
+                    || err_str.contains("NoSuchVersion")
+                {
                     Error::NotFound(path.to_string())
                 } else {
                     Error::Network(err_str)
                 }
-            })?;
-
-        let data = response
-            .body
-            .collect()
-            .await
-            .map_err(|e| Error::Network(e.to_string()))?
-            .into_bytes()
-            .to_vec();
-
-        Ok(data)
+            })
     }
 
-    async fn put_object(
+    /// Read the tags of an object, or one of its versions
+    async fn tags(
         &self,
         path: &RemotePath,
-        data: Vec<u8>,
-        content_type: Option<&str>,
-    ) -> Result<ObjectInfo> {
-        let size = data.len() as i64;
-        let body = aws_sdk_s3::primitives::ByteStream::from(data);
-
-        let mut request = self
+        version_id: Option<&str>,
+    ) -> Result<HashMap<String, String>> {
+        let response = match self
             .inner
-            .put_object()
+            .get_object_tagging()
             .bucket(&path.bucket)
             .key(&path.key)
-            .body(body);
-
-        if let Some(ct) = content_type {
-            request = request.content_type(ct);
-        }
-
-        let response = request
+            .set_version_id(version_id.map(str::to_string))
             .send()
             .await
-            .map_err(|e| Error::Network(e.to_string()))?;
+        {
+            Ok(response) => response,
+            Err(e) => {
+                if e.to_string().contains("NoSuchTagSet") {
+                    return Ok(HashMap::new());
+                }
+                return Err(Error::General(format!("get_object_tags: {e}")));
+            }
+        };
 
-        let mut info = ObjectInfo::file(&path.key, size);
-        if let Some(etag) = response.e_tag() {
-            info.etag = Some(etag.trim_matches('"').to_string());
+        let mut tags = HashMap::new();
+        for tag in response.tag_set() {
+            let key = tag.key();
+            let value = tag.value();
+            tags.insert(key.to_string(), value.to_string());
         }
-        info.last_modified = Some(jiff::Timestamp::now());
 
-        Ok(info)
+        Ok(tags)
     }
 
-    async fn delete_object(&self, path: &RemotePath) -> Result<()> {
-        self.inner
-            .delete_object()
+    /// Start streaming an object, or one of its versions
+    async fn get_stream(
+        &self,
+        path: &RemotePath,
+        version_id: Option<&str>,
+    ) -> Result<ObjectStream> {
+        let response = self
+            .inner
+            .get_object()
             .bucket(&path.bucket)
             .key(&path.key)
+            .set_version_id(version_id.map(str::to_string))
             .send()
             .await
             .map_err(|e| {
                 let err_str = e.to_string();
-                if err_str.contains("NotFound") || err_str.contains("NoSuchKey") {
+                if err_str.contains("NotFound")
+                    || err_str.contains("NoSuchKey")
+                    || err_str.contains("NoSuchVersion")
+                {
                     Error::NotFound(path.to_string())
                 } else {
                     Error::Network(err_str)
                 }
             })?;
 
-        Ok(())
-    }
-
-    async fn delete_objects(&self, bucket: &str, keys: Vec<String>) -> Result<Vec<String>> {
-        use aws_sdk_s3::types::{Delete, ObjectIdentifier};
+        let size = response.content_length().unwrap_or(0);
+        let mut info = ObjectInfo::file(&path.key, size);
 
-        if keys.is_empty() {
-            return Ok(vec![]);
+        if let Some(modified) = response.last_modified() {
+            info.last_modified = jiff::Timestamp::from_second(modified.secs()).ok();
         }
 
-        let objects: Vec<ObjectIdentifier> = keys
-            .iter()
-            .map(|k| ObjectIdentifier::builder().key(k).build().unwrap())
-            .collect();
-
-        let delete = Delete::builder()
-            .set_objects(Some(objects))
-            .build()
-            .map_err(|e| Error::General(e.to_string()))?;
-
-        let response = self
-            .inner
-            .delete_objects()
-            .bucket(bucket)
-            .delete(delete)
-            .send()
-            .await
-            .map_err(|e| Error::Network(e.to_string()))?;
-
-        // Collect deleted keys
-        let deleted: Vec<String> = response
-            .deleted()
-            .iter()
-            .filter_map(|d| d.key().map(|k| k.to_string()))
-            .collect();
+        if let Some(etag) = response.e_tag() {
+            info.etag = Some(etag.trim_matches('"').to_string());
+        }
 
-        // Check for errors
-        if !response.errors().is_empty() {
-            let error_keys: Vec<String> = response
-                .errors()
-                .iter()
-                .filter_map(|e| e.key().map(|k| k.to_string()))
-                .collect();
-            tracing::warn!("Failed to delete some objects: {:?}", error_keys);
+        if let Some(ct) = response.content_type() {
+            info.content_type = Some(ct.to_string());
         }
 
-        Ok(deleted)
+        Ok(ObjectStream {
+            info,
+            body: Box::pin(response.body.into_async_read()),
+        })
     }
 
-    async fn copy_object(&self, src: &RemotePath, dst: &RemotePath) -> Result<ObjectInfo> {
-        // Build copy source: bucket/key
-        let copy_source = format!("{}/{}", src.bucket, src.key);
+    /// Server-side copy of an object, or one of its versions
+    async fn copy(
+        &self,
+        src: &RemotePath,
+        version_id: Option<&str>,
+        dst: &RemotePath,
+    ) -> Result<ObjectInfo> {
+        // Build copy source: bucket/key[?versionId=...]
+        let copy_source = match version_id {
+            Some(v) => format!(
+                "{}/{}?versionId={}",
+                src.bucket,
+                src.key,
+                urlencoding::encode(v)
+            ),
+            None => format!("{}/{}", src.bucket, src.key),
+        };
 
         let response = self
             .inner
@@ -617,73 +806,658 @@ impl ObjectStore for S3Client {
         Ok(result)
     }
 
-    async fn presign_get(&self, path: &RemotePath, expires_secs: u64) -> Result<String> {
-        let config = aws_sdk_s3::presigning::PresigningConfig::builder()
-            .expires_in(std::time::Duration::from_secs(expires_secs))
-            .build()
-            .map_err(|e| Error::General(format!("presign_get config: {e}")))?;
-
-        let request = self
-            .inner
-            .get_object()
-            .bucket(&path.bucket)
-            .key(&path.key)
-            .presigned(config)
-            .await
-            .map_err(|e| Error::General(format!("presign_get: {e}")))?;
+    /// Upload the remainder of a stream as parts of an existing multipart upload
+    async fn upload_stream_parts(
+        &self,
+        path: &RemotePath,
+        upload_id: &str,
+        first_part: Vec<u8>,
+        body: &mut ByteReader,
+    ) -> Result<(Vec<CompletedPart>, u64)> {
+        let mut parts = Vec::new();
+        let mut total = 0u64;
+        let mut chunk = first_part;
+        let mut part_number = 1;
+
+        while !chunk.is_empty() {
+            total += chunk.len() as u64;
+            let etag = self
+                .upload_part(path, upload_id, part_number, Bytes::from(chunk))
+                .await?;
+            parts.push(CompletedPart { part_number, etag });
+
+            part_number += 1;
+            chunk = read_chunk(body, DEFAULT_PART_SIZE as usize).await?;
+        }
 
-        Ok(request.uri().to_string())
+        Ok((parts, total))
     }
+}
 
-    async fn presign_put(
-        &self,
-        path: &RemotePath,
-        expires_secs: u64,
-        content_type: Option<&str>,
-    ) -> Result<String> {
-        let config = aws_sdk_s3::presigning::PresigningConfig::builder()
-            .expires_in(std::time::Duration::from_secs(expires_secs))
-            .build()
-            .map_err(|e| Error::General(format!("presign_put config: {e}")))?;
+/// Object attributes set when an object is written
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ObjectAttributes {
+    /// Content type
+    pub content_type: Option<String>,
 
-        let mut builder = self.inner.put_object().bucket(&path.bucket).key(&path.key);
+    /// User-defined metadata (without the `x-amz-meta-` prefix)
+    pub metadata: HashMap<String, String>,
 
-        if let Some(ct) = content_type {
-            builder = builder.content_type(ct);
+    /// Object tags
+    pub tags: HashMap<String, String>,
+}
+
+impl ObjectAttributes {
+    /// Attributes with only a content type
+    pub fn with_content_type(content_type: Option<&str>) -> Self {
+        Self {
+            content_type: content_type.map(|ct| ct.to_string()),
+            ..Default::default()
         }
+    }
 
-        let request = builder
-            .presigned(config)
-            .await
-            .map_err(|e| Error::General(format!("presign_put: {e}")))?;
+    fn metadata_opt(&self) -> Option<HashMap<String, String>> {
+        (!self.metadata.is_empty()).then(|| self.metadata.clone())
+    }
 
-        Ok(request.uri().to_string())
+    /// Tags encoded for the `x-amz-tagging` header
+    fn tagging_header(&self) -> Option<String> {
+        if self.tags.is_empty() {
+            return None;
+        }
+
+        let mut pairs: Vec<_> = self.tags.iter().collect();
+        pairs.sort();
+        Some(
+            pairs
+                .into_iter()
+                .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+                .collect::<Vec<_>>()
+                .join("&"),
+        )
     }
+}
 
-    async fn get_versioning(&self, bucket: &str) -> Result<Option<bool>> {
+/// Read up to `size` bytes, stopping early only at end of stream
+async fn read_chunk(reader: &mut ByteReader, size: usize) -> Result<Vec<u8>> {
+    let mut buf = Vec::with_capacity(size);
+    reader
+        .as_mut()
+        .take(size as u64)
+        .read_to_end(&mut buf)
+        .await?;
+    Ok(buf)
+}
+
+fn build_tagging(
+    tags: std::collections::HashMap<String, String>,
+) -> Result<aws_sdk_s3::types::Tagging> {
+    use aws_sdk_s3::types::{Tag, Tagging};
+
+    let mut tag_set = Vec::with_capacity(tags.len());
+    for (key, value) in tags {
+        let tag = Tag::builder()
+            .key(key)
+            .value(value)
+            .build()
+            .map_err(|e| Error::General(format!("invalid tag: {e}")))?;
+        tag_set.push(tag);
+    }
+
+    Tagging::builder()
+        .set_tag_set(Some(tag_set))
+        .build()
+        .map_err(|e| Error::General(format!("invalid tagging payload: {e}")))
+}
+
+#[async_trait]
+impl ObjectStore for S3Client {
+    async fn list_buckets(&self) -> Result<Vec<ObjectInfo>> {
         let response = self
             .inner
-            .get_bucket_versioning()
-            .bucket(bucket)
+            .list_buckets()
             .send()
             .await
-            .map_err(|e| Error::General(format!("get_versioning: {e}")))?;
+            .map_err(|e| Error::Network(e.to_string()))?;
 
-        Ok(response
-            .status()
-            .map(|s| *s == aws_sdk_s3::types::BucketVersioningStatus::Enabled))
+        let buckets = response
+            .buckets()
+            .iter()
+            .map(|b| {
+                let mut info = ObjectInfo::bucket(b.name().unwrap_or_default());
+                if let Some(creation_date) = b.creation_date() {
+                    info.last_modified = jiff::Timestamp::from_second(creation_date.secs()).ok();
+                }
+                info
+            })
+            .collect();
+
+        Ok(buckets)
     }
 
-    async fn set_versioning(&self, bucket: &str, enabled: bool) -> Result<()> {
-        use aws_sdk_s3::types::{BucketVersioningStatus, VersioningConfiguration};
+    async fn list_objects(&self, path: &RemotePath, options: ListOptions) -> Result<ListResult> {
+        let mut request = self.inner.list_objects_v2().bucket(&path.bucket);
 
-        let status = if enabled {
-            BucketVersioningStatus::Enabled
+        // Set prefix
+        let prefix = if path.key.is_empty() {
+            options.prefix.clone()
+        } else if let Some(p) = &options.prefix {
+            Some(format!("{}{}", path.key, p))
         } else {
-            BucketVersioningStatus::Suspended
+            Some(path.key.clone())
         };
 
-        let config = VersioningConfiguration::builder().status(status).build();
+        if let Some(p) = prefix {
+            request = request.prefix(p);
+        }
+
+        // Set delimiter (for non-recursive listing)
+        if !options.recursive {
+            request = request.delimiter(options.delimiter.as_deref().unwrap_or("/"));
+        }
+
+        // Set max keys
+        if let Some(max) = options.max_keys {
+            request = request.max_keys(max);
+        }
+
+        // Set continuation token
+        if let Some(token) = &options.continuation_token {
+            request = request.continuation_token(token);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            let err_str = Self::format_sdk_error(&e);
+            if err_str.contains("NotFound") || err_str.contains("NoSuchBucket") {
+                Error::NotFound(format!("Bucket not found: {}", path.bucket))
+            } else {
+                Error::Network(err_str)
+            }
+        })?;
+
+        let mut items = Vec::new();
+
+        // Add common prefixes (directories)
+        for prefix in response.common_prefixes() {
+            if let Some(p) = prefix.prefix() {
+                items.push(ObjectInfo::dir(p));
+            }
+        }
+
+        // Add objects
+        for object in response.contents() {
+            let key = object.key().unwrap_or_default().to_string();
+            let size = object.size().unwrap_or(0);
+            let mut info = ObjectInfo::file(&key, size);
+
+            if let Some(modified) = object.last_modified() {
+                info.last_modified = jiff::Timestamp::from_second(modified.secs()).ok();
+            }
+
+            if let Some(etag) = object.e_tag() {
+                info.etag = Some(etag.trim_matches('"').to_string());
+            }
+
+            if let Some(sc) = object.storage_class() {
+                info.storage_class = Some(sc.as_str().to_string());
+            }
+
+            items.push(info);
+        }
+
+        Ok(ListResult {
+            items,
+            truncated: response.is_truncated().unwrap_or(false),
+            continuation_token: response.next_continuation_token().map(|s| s.to_string()),
+        })
+    }
+
+    async fn head_object(&self, path: &RemotePath) -> Result<ObjectInfo> {
+        self.head(path, None).await
+    }
+
+    async fn bucket_exists(&self, bucket: &str) -> Result<bool> {
+        match self.inner.head_bucket().bucket(bucket).send().await {
+            Ok(_) => Ok(true),
+            Err(e) => {
+                let err_str = e.to_string();
+                if err_str.contains("NotFound") || err_str.contains("NoSuchBucket") {
+                    Ok(false)
+                } else {
+                    Err(Error::Network(err_str))
+                }
+            }
+        }
+    }
+
+    async fn create_bucket(&self, bucket: &str, options: &CreateBucketOptions) -> Result<()> {
+        use aws_sdk_s3::types::{BucketLocationConstraint, CreateBucketConfiguration};
+
+        // us-east-1 is the default location and must not be sent as a constraint
+        let configuration = options
+            .region
+            .as_deref()
+            .filter(|region| *region != "us-east-1")
+            .map(|region| {
+                CreateBucketConfiguration::builder()
+                    .location_constraint(BucketLocationConstraint::from(region))
+                    .build()
+            });
+
+        let mut request = self
+            .inner
+            .create_bucket()
+            .bucket(bucket)
+            .set_create_bucket_configuration(configuration);
+
+        if options.object_lock {
+            request = request.object_lock_enabled_for_bucket(true);
+        }
+
+        request.send().await.map_err(|e| match e.code() {
+            Some(code @ ("BucketAlreadyExists" | "BucketAlreadyOwnedByYou")) => {
+                Error::Conflict(format!("{code}: {bucket}"))
+            }
+            Some("AccessDenied") => Error::Auth(format!("AccessDenied: {bucket}")),
+            _ => Error::Network(Self::format_sdk_error(&e)),
+        })?;
+
+        // Object lock turns versioning on by itself
+        if options.versioning && !options.object_lock {
+            self.set_versioning(bucket, true).await.map_err(|e| {
+                Error::General(format!(
+                    "Bucket '{bucket}' was created but enabling versioning failed: {e}"
+                ))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    async fn delete_bucket(&self, bucket: &str) -> Result<()> {
+        self.inner
+            .delete_bucket()
+            .bucket(bucket)
+            .send()
+            .await
+            .map_err(|e| {
+                let err_str = e.to_string();
+                if err_str.contains("NotFound") || err_str.contains("NoSuchBucket") {
+                    Error::NotFound(format!("Bucket not found: {bucket}"))
+                } else {
+                    Error::Network(err_str)
+                }
+            })?;
+
+        Ok(())
+    }
+
+    async fn capabilities(&self) -> Result<Capabilities> {
+        // For now, return conservative defaults
+        // In Phase 5, we'll implement actual capability detection
+        Ok(Capabilities {
+            versioning: true,
+            object_lock: false,
+            tagging: true,
+            select: false,
+            notifications: false,
+        })
+    }
+
+    async fn get_object(&self, path: &RemotePath) -> Result<Vec<u8>> {
+        let response = self
+            .inner
+            .get_object()
+            .bucket(&path.bucket)
+            .key(&path.key)
+            .send()
+            .await
+            .map_err(|e| {
+                let err_str = e.to_string();
+                if err_str.contains("NotFound") || err_str.contains("NoSuchKey") {
+                    Error::NotFound(path.to_string())
+                } else {
+                    Error::Network(err_str)
+                }
+            })?;
+
+        let data = response
+            .body
+            .collect()
+            .await
+            .map_err(|e| Error::Network(e.to_string()))?
+            .into_bytes()
+            .to_vec();
+
+        Ok(data)
+    }
+
+    async fn get_object_stream(&self, path: &RemotePath) -> Result<ObjectStream> {
+        self.get_stream(path, None).await
+    }
+
+    async fn put_object(
+        &self,
+        path: &RemotePath,
+        data: Vec<u8>,
+        content_type: Option<&str>,
+    ) -> Result<ObjectInfo> {
+        let size = data.len() as i64;
+        let body = aws_sdk_s3::primitives::ByteStream::from(data);
+
+        let mut request = self
+            .inner
+            .put_object()
+            .bucket(&path.bucket)
+            .key(&path.key)
+            .body(body);
+
+        if let Some(ct) = content_type {
+            request = request.content_type(ct);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::Network(e.to_string()))?;
+
+        let mut info = ObjectInfo::file(&path.key, size);
+        if let Some(etag) = response.e_tag() {
+            info.etag = Some(etag.trim_matches('"').to_string());
+        }
+        info.last_modified = Some(jiff::Timestamp::now());
+
+        Ok(info)
+    }
+
+    async fn put_object_file(
+        &self,
+        path: &RemotePath,
+        file: &Path,
+        content_type: Option<&str>,
+    ) -> Result<ObjectInfo> {
+        let size = tokio::fs::metadata(file).await?.len() as i64;
+        let body = aws_sdk_s3::primitives::ByteStream::from_path(file)
+            .await
+            .map_err(|e| Error::General(format!("Failed to read {}: {e}", file.display())))?;
+
+        let mut request = self
+            .inner
+            .put_object()
+            .bucket(&path.bucket)
+            .key(&path.key)
+            .content_length(size)
+            .body(body);
+
+        if let Some(ct) = content_type {
+            request = request.content_type(ct);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::Network(e.to_string()))?;
+
+        let mut info = ObjectInfo::file(&path.key, size);
+        if let Some(etag) = response.e_tag() {
+            info.etag = Some(etag.trim_matches('"').to_string());
+        }
+        info.last_modified = Some(jiff::Timestamp::now());
+
+        Ok(info)
+    }
+
+    async fn put_object_stream(
+        &self,
+        path: &RemotePath,
+        mut body: ByteReader,
+        content_type: Option<&str>,
+    ) -> Result<ObjectInfo> {
+        // Streams that fit in a single part are sent with a plain PUT
+        let first_part = read_chunk(&mut body, DEFAULT_PART_SIZE as usize).await?;
+        if (first_part.len() as u64) < DEFAULT_PART_SIZE {
+            return self.put_object(path, first_part, content_type).await;
+        }
+
+        let attributes = ObjectAttributes::with_content_type(content_type);
+        let upload_id = self.create_multipart_upload(path, &attributes).await?;
+
+        match self
+            .upload_stream_parts(path, &upload_id, first_part, &mut body)
+            .await
+        {
+            Ok((parts, total)) => {
+                let completed = self
+                    .complete_multipart_upload(path, &upload_id, &parts)
+                    .await?;
+                let mut info = ObjectInfo::file(&path.key, total as i64);
+                info.etag = completed.etag;
+                info.last_modified = completed.last_modified;
+                Ok(info)
+            }
+            Err(e) => {
+                if let Err(abort_err) = self.abort_multipart_upload(path, &upload_id).await {
+                    tracing::warn!("Failed to abort multipart upload {upload_id}: {abort_err}");
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn delete_object(&self, path: &RemotePath, options: &DeleteOptions) -> Result<()> {
+        let mut request = self
+            .inner
+            .delete_object()
+            .bucket(&path.bucket)
+            .key(&path.key)
+            .set_version_id(options.version_id.clone());
+
+        if options.bypass_governance {
+            request = request.bypass_governance_retention(true);
+        }
+
+        request.send().await.map_err(|e| {
+            let err_str = e.to_string();
+            if err_str.contains("NotFound") || err_str.contains("NoSuchKey") {
+                Error::NotFound(path.to_string())
+            } else {
+                Error::Network(err_str)
+            }
+        })?;
+
+        Ok(())
+    }
+
+    async fn delete_objects(
+        &self,
+        bucket: &str,
+        targets: Vec<DeleteTarget>,
+        bypass_governance: bool,
+    ) -> Result<Vec<DeleteTarget>> {
+        use aws_sdk_s3::types::{Delete, ObjectIdentifier};
+
+        if targets.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let objects = targets
+            .into_iter()
+            .map(|t| {
+                ObjectIdentifier::builder()
+                    .key(t.key)
+                    .set_version_id(t.version_id)
+                    .build()
+                    .map_err(|e| Error::General(e.to_string()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let delete = Delete::builder()
+            .set_objects(Some(objects))
+            .build()
+            .map_err(|e| Error::General(e.to_string()))?;
+
+        let mut request = self.inner.delete_objects().bucket(bucket).delete(delete);
+        if bypass_governance {
+            request = request.bypass_governance_retention(true);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::Network(e.to_string()))?;
+
+        // Collect deleted keys and versions
+        let deleted: Vec<DeleteTarget> = response
+            .deleted()
+            .iter()
+            .filter_map(|d| {
+                d.key().map(|k| DeleteTarget {
+                    key: k.to_string(),
+                    version_id: d.version_id().map(|v| v.to_string()),
+                })
+            })
+            .collect();
+
+        // Check for errors
+        if !response.errors().is_empty() {
+            let error_keys: Vec<String> = response
+                .errors()
+                .iter()
+                .filter_map(|e| e.key().map(|k| k.to_string()))
+                .collect();
+            tracing::warn!("Failed to delete some objects: {:?}", error_keys);
+        }
+
+        Ok(deleted)
+    }
+
+    async fn list_multipart_uploads(&self, path: &RemotePath) -> Result<Vec<MultipartUploadInfo>> {
+        let mut uploads = Vec::new();
+        let mut key_marker: Option<String> = None;
+        let mut upload_id_marker: Option<String> = None;
+
+        loop {
+            let response = self
+                .inner
+                .list_multipart_uploads()
+                .bucket(&path.bucket)
+                .set_prefix((!path.key.is_empty()).then(|| path.key.clone()))
+                .set_key_marker(key_marker.take())
+                .set_upload_id_marker(upload_id_marker.take())
+                .send()
+                .await
+                .map_err(|e| Error::Network(Self::format_sdk_error(&e)))?;
+
+            for upload in response.uploads() {
+                if let (Some(key), Some(upload_id)) = (upload.key(), upload.upload_id()) {
+                    uploads.push(MultipartUploadInfo {
+                        key: key.to_string(),
+                        upload_id: upload_id.to_string(),
+                        initiated: upload
+                            .initiated()
+                            .and_then(|dt| Timestamp::from_second(dt.secs()).ok()),
+                    });
+                }
+            }
+
+            if response.is_truncated() != Some(true) {
+                break;
+            }
+
+            key_marker = response.next_key_marker().map(|m| m.to_string());
+            upload_id_marker = response.next_upload_id_marker().map(|m| m.to_string());
+            if key_marker.is_none() {
+                break;
+            }
+        }
+
+        Ok(uploads)
+    }
+
+    async fn abort_multipart_upload(&self, path: &RemotePath, upload_id: &str) -> Result<()> {
+        self.inner
+            .abort_multipart_upload()
+            .bucket(&path.bucket)
+            .key(&path.key)
+            .upload_id(upload_id)
+            .send()
+            .await
+            .map_err(|e| Error::Network(Self::format_sdk_error(&e)))?;
+
+        Ok(())
+    }
+
+    async fn copy_object(&self, src: &RemotePath, dst: &RemotePath) -> Result<ObjectInfo> {
+        self.copy(src, None, dst).await
+    }
+
+    async fn presign_get(&self, path: &RemotePath, expires_secs: u64) -> Result<String> {
+        let config = aws_sdk_s3::presigning::PresigningConfig::builder()
+            .expires_in(std::time::Duration::from_secs(expires_secs))
+            .build()
+            .map_err(|e| Error::General(format!("presign_get config: {e}")))?;
+
+        let request = self
+            .inner
+            .get_object()
+            .bucket(&path.bucket)
+            .key(&path.key)
+            .presigned(config)
+            .await
+            .map_err(|e| Error::General(format!("presign_get: {e}")))?;
+
+        self.finish_presign("GET", request.uri(), None, expires_secs)
+    }
+
+    async fn presign_put(
+        &self,
+        path: &RemotePath,
+        expires_secs: u64,
+        content_type: Option<&str>,
+    ) -> Result<String> {
+        let config = aws_sdk_s3::presigning::PresigningConfig::builder()
+            .expires_in(std::time::Duration::from_secs(expires_secs))
+            .build()
+            .map_err(|e| Error::General(format!("presign_put config: {e}")))?;
+
+        let mut builder = self.inner.put_object().bucket(&path.bucket).key(&path.key);
+
+        if let Some(ct) = content_type {
+            builder = builder.content_type(ct);
+        }
+
+        let request = builder
+            .presigned(config)
+            .await
+            .map_err(|e| Error::General(format!("presign_put: {e}")))?;
+
+        self.finish_presign("PUT", request.uri(), content_type, expires_secs)
+    }
+
+    async fn get_versioning(&self, bucket: &str) -> Result<Option<bool>> {
+        let response = self
+            .inner
+            .get_bucket_versioning()
+            .bucket(bucket)
+            .send()
+            .await
+            .map_err(|e| Error::General(format!("get_versioning: {e}")))?;
+
+        Ok(response
+            .status()
+            .map(|s| *s == aws_sdk_s3::types::BucketVersioningStatus::Enabled))
+    }
+
+    async fn set_versioning(&self, bucket: &str, enabled: bool) -> Result<()> {
+        use aws_sdk_s3::types::{BucketVersioningStatus, VersioningConfiguration};
+
+        let status = if enabled {
+            BucketVersioningStatus::Enabled
+        } else {
+            BucketVersioningStatus::Suspended
+        };
+
+        let config = VersioningConfiguration::builder().status(status).build();
 
         self.inner
             .put_bucket_versioning()
@@ -701,14 +1475,60 @@ impl ObjectStore for S3Client {
         path: &RemotePath,
         max_keys: Option<i32>,
     ) -> Result<Vec<ObjectVersion>> {
-        let mut builder = self.inner.list_object_versions().bucket(&path.bucket);
+        let mut versions = Vec::new();
+        let mut marker: Option<VersionMarker> = None;
+
+        loop {
+            let page_size = match max_keys {
+                Some(max) => (max - versions.len() as i32).min(1000),
+                None => 1000,
+            };
+            let page = self
+                .list_object_versions_page(path, marker.as_ref(), page_size)
+                .await?;
+            versions.extend(page.versions);
+
+            let limit_reached = max_keys.is_some_and(|max| versions.len() as i32 >= max);
+            match page.next {
+                Some(next) if !limit_reached => marker = Some(next),
+                _ => break,
+            }
+        }
+
+        if let Some(max) = max_keys {
+            versions.truncate(max.max(0) as usize);
+        }
+
+        // Sort by key and then by last_modified (descending)
+        versions.sort_by(|a, b| {
+            a.key
+                .cmp(&b.key)
+                .then_with(|| b.last_modified.cmp(&a.last_modified))
+        });
+
+        Ok(versions)
+    }
+
+    async fn list_object_versions_page(
+        &self,
+        path: &RemotePath,
+        marker: Option<&VersionMarker>,
+        max_keys: i32,
+    ) -> Result<VersionPage> {
+        let mut builder = self
+            .inner
+            .list_object_versions()
+            .bucket(&path.bucket)
+            .max_keys(max_keys);
 
         if !path.key.is_empty() {
             builder = builder.prefix(&path.key);
         }
 
-        if let Some(max) = max_keys {
-            builder = builder.max_keys(max);
+        if let Some(marker) = marker {
+            builder = builder
+                .key_marker(&marker.key)
+                .set_version_id_marker(marker.version_id.clone());
         }
 
         let response = builder
@@ -748,129 +1568,483 @@ impl ObjectStore for S3Client {
             });
         }
 
-        // Sort by key and then by last_modified (descending)
-        versions.sort_by(|a, b| {
-            a.key
-                .cmp(&b.key)
-                .then_with(|| b.last_modified.cmp(&a.last_modified))
+        let next = if response.is_truncated() == Some(true) {
+            response.next_key_marker().map(|key| VersionMarker {
+                key: key.to_string(),
+                version_id: response.next_version_id_marker().map(str::to_string),
+            })
+        } else {
+            None
+        };
+
+        Ok(VersionPage { versions, next })
+    }
+
+    async fn head_object_version(&self, path: &RemotePath, version_id: &str) -> Result<ObjectInfo> {
+        self.head(path, Some(version_id)).await
+    }
+
+    async fn get_object_version_stream(
+        &self,
+        path: &RemotePath,
+        version_id: &str,
+    ) -> Result<ObjectStream> {
+        self.get_stream(path, Some(version_id)).await
+    }
+
+    async fn copy_object_version(
+        &self,
+        src: &RemotePath,
+        version_id: &str,
+        dst: &RemotePath,
+    ) -> Result<ObjectInfo> {
+        self.copy(src, Some(version_id), dst).await
+    }
+
+    async fn get_object_tags(
+        &self,
+        path: &RemotePath,
+    ) -> Result<std::collections::HashMap<String, String>> {
+        self.tags(path, None).await
+    }
+
+    async fn get_bucket_tags(
+        &self,
+        bucket: &str,
+    ) -> Result<std::collections::HashMap<String, String>> {
+        let response = match self.inner.get_bucket_tagging().bucket(bucket).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                if e.to_string().contains("NoSuchTagSet") {
+                    return Ok(std::collections::HashMap::new());
+                }
+                return Err(Error::General(format!("get_bucket_tags: {e}")));
+            }
+        };
+
+        let mut tags = std::collections::HashMap::new();
+        for tag in response.tag_set() {
+            let key = tag.key();
+            let value = tag.value();
+            tags.insert(key.to_string(), value.to_string());
+        }
+
+        Ok(tags)
+    }
+
+    async fn set_object_tags(
+        &self,
+        path: &RemotePath,
+        tags: std::collections::HashMap<String, String>,
+    ) -> Result<()> {
+        let tagging = build_tagging(tags)?;
+
+        self.inner
+            .put_object_tagging()
+            .bucket(&path.bucket)
+            .key(&path.key)
+            .tagging(tagging)
+            .send()
+            .await
+            .map_err(|e| Error::General(format!("set_object_tags: {e}")))?;
+
+        Ok(())
+    }
+
+    async fn set_bucket_tags(
+        &self,
+        bucket: &str,
+        tags: std::collections::HashMap<String, String>,
+    ) -> Result<()> {
+        let tagging = build_tagging(tags)?;
+
+        self.inner
+            .put_bucket_tagging()
+            .bucket(bucket)
+            .tagging(tagging)
+            .send()
+            .await
+            .map_err(|e| Error::General(format!("set_bucket_tags: {e}")))?;
+
+        Ok(())
+    }
+
+    async fn delete_object_tags(&self, path: &RemotePath) -> Result<()> {
+        self.inner
+            .delete_object_tagging()
+            .bucket(&path.bucket)
+            .key(&path.key)
+            .send()
+            .await
+            .map_err(|e| Error::General(format!("delete_object_tags: {e}")))?;
+
+        Ok(())
+    }
+
+    async fn delete_bucket_tags(&self, bucket: &str) -> Result<()> {
+        self.inner
+            .delete_bucket_tagging()
+            .bucket(bucket)
+            .send()
+            .await
+            .map_err(|e| Error::General(format!("delete_bucket_tags: {e}")))?;
+
+        Ok(())
+    }
+
+    async fn get_object_retention(
+        &self,
+        path: &RemotePath,
+        version_id: Option<&str>,
+    ) -> Result<Option<ObjectRetention>> {
+        let response = match self
+            .inner
+            .get_object_retention()
+            .bucket(&path.bucket)
+            .key(&path.key)
+            .set_version_id(version_id.map(str::to_string))
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                return match e.code() {
+                    Some("NoSuchObjectLockConfiguration") => Ok(None),
+                    Some("NoSuchKey" | "NoSuchVersion") => Err(Error::NotFound(path.to_string())),
+                    _ => Err(Error::General(format!(
+                        "get_object_retention: {}",
+                        e.message().unwrap_or(&e.to_string())
+                    ))),
+                };
+            }
+        };
+
+        let Some(retention) = response.retention() else {
+            return Ok(None);
+        };
+        let mode = match retention.mode() {
+            Some(ObjectLockRetentionMode::Governance) => RetentionMode::Governance,
+            Some(ObjectLockRetentionMode::Compliance) => RetentionMode::Compliance,
+            _ => return Ok(None),
+        };
+        let Some(retain_until) = retention
+            .retain_until_date()
+            .and_then(|dt| Timestamp::from_second(dt.secs()).ok())
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(ObjectRetention { mode, retain_until }))
+    }
+
+    async fn put_object_retention(
+        &self,
+        path: &RemotePath,
+        version_id: Option<&str>,
+        retention: Option<&ObjectRetention>,
+        bypass_governance: bool,
+    ) -> Result<()> {
+        // An empty retention element clears the retention
+        let lock_retention = match retention {
+            Some(retention) => aws_sdk_s3::types::ObjectLockRetention::builder()
+                .mode(retention_mode(retention.mode))
+                .retain_until_date(aws_smithy_types::DateTime::from_secs(
+                    retention.retain_until.as_second(),
+                ))
+                .build(),
+            None => aws_sdk_s3::types::ObjectLockRetention::builder().build(),
+        };
+
+        let mut request = self
+            .inner
+            .put_object_retention()
+            .bucket(&path.bucket)
+            .key(&path.key)
+            .set_version_id(version_id.map(str::to_string))
+            .retention(lock_retention);
+
+        if bypass_governance {
+            request = request.bypass_governance_retention(true);
+        }
+
+        request.send().await.map_err(|e| {
+            let message = e.message().map(str::to_string).unwrap_or(e.to_string());
+            match e.code() {
+                Some("NoSuchKey" | "NoSuchVersion") => Error::NotFound(path.to_string()),
+                Some("AccessDenied") => Error::Auth(format!("put_object_retention: {message}")),
+                _ => Error::General(format!("put_object_retention: {message}")),
+            }
+        })?;
+
+        Ok(())
+    }
+
+    async fn get_object_lock_config(&self, bucket: &str) -> Result<Option<ObjectLockConfig>> {
+        let response = match self
+            .inner
+            .get_object_lock_configuration()
+            .bucket(bucket)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                return match e.code() {
+                    Some("ObjectLockConfigurationNotFoundError") => Ok(None),
+                    Some("NoSuchBucket") => {
+                        Err(Error::NotFound(format!("Bucket not found: {bucket}")))
+                    }
+                    _ => Err(Error::General(format!(
+                        "get_object_lock_config: {}",
+                        e.message().unwrap_or(&e.to_string())
+                    ))),
+                };
+            }
+        };
+
+        let Some(config) = response.object_lock_configuration() else {
+            return Ok(None);
+        };
+
+        let default_retention = config
+            .rule()
+            .and_then(|rule| rule.default_retention())
+            .and_then(|retention| {
+                let mode = match retention.mode()? {
+                    ObjectLockRetentionMode::Governance => RetentionMode::Governance,
+                    ObjectLockRetentionMode::Compliance => RetentionMode::Compliance,
+                    _ => return None,
+                };
+                let validity = match (retention.days(), retention.years()) {
+                    (Some(days), _) => RetentionValidity::Days(days.max(0) as u32),
+                    (None, Some(years)) => RetentionValidity::Years(years.max(0) as u32),
+                    (None, None) => return None,
+                };
+                Some(DefaultRetention { mode, validity })
+            });
+
+        Ok(Some(ObjectLockConfig {
+            enabled: config.object_lock_enabled() == Some(&ObjectLockEnabled::Enabled),
+            default_retention,
+        }))
+    }
+
+    async fn set_default_retention(
+        &self,
+        bucket: &str,
+        retention: Option<&DefaultRetention>,
+    ) -> Result<()> {
+        use aws_sdk_s3::types::{ObjectLockConfiguration, ObjectLockRule};
+
+        let rule = retention.map(|retention| {
+            let mut default =
+                aws_sdk_s3::types::DefaultRetention::builder().mode(retention_mode(retention.mode));
+            default = match retention.validity {
+                RetentionValidity::Days(days) => default.days(days as i32),
+                RetentionValidity::Years(years) => default.years(years as i32),
+            };
+            ObjectLockRule::builder()
+                .default_retention(default.build())
+                .build()
         });
 
-        Ok(versions)
+        let config = ObjectLockConfiguration::builder()
+            .object_lock_enabled(ObjectLockEnabled::Enabled)
+            .set_rule(rule)
+            .build();
+
+        self.inner
+            .put_object_lock_configuration()
+            .bucket(bucket)
+            .object_lock_configuration(config)
+            .send()
+            .await
+            .map_err(|e| {
+                Error::General(format!(
+                    "set_default_retention: {}",
+                    e.message().unwrap_or(&e.to_string())
+                ))
+            })?;
+
+        Ok(())
+    }
+
+    async fn select_object_content(
+        &self,
+        path: &RemotePath,
+        options: &SelectOptions,
+        output: &mut (dyn AsyncWrite + Send + Unpin),
+    ) -> Result<u64> {
+        use aws_sdk_s3::types::{ExpressionType, SelectObjectContentEventStream};
+
+        let mut response = self
+            .inner
+            .select_object_content()
+            .bucket(&path.bucket)
+            .key(&path.key)
+            .expression(&options.expression)
+            .expression_type(ExpressionType::Sql)
+            .input_serialization(select_input(options))
+            .output_serialization(select_output(options))
+            .send()
+            .await
+            .map_err(|e| {
+                let message = e.message().map(str::to_string).unwrap_or(e.to_string());
+                match e.code() {
+                    Some("NoSuchKey") => Error::NotFound(path.to_string()),
+                    Some("NotImplemented" | "MethodNotAllowed") => Error::UnsupportedFeature(
+                        format!("The backend does not support S3 Select: {message}"),
+                    ),
+                    _ => Error::General(format!("select_object_content: {message}")),
+                }
+            })?;
+
+        let mut written = 0u64;
+        while let Some(event) = response.payload.recv().await.map_err(|e| {
+            Error::Network(format!(
+                "select_object_content: {}",
+                e.message().map(str::to_string).unwrap_or(e.to_string())
+            ))
+        })? {
+            if let SelectObjectContentEventStream::Records(records) = event
+                && let Some(payload) = records.payload()
+            {
+                output.write_all(payload.as_ref()).await?;
+                written += payload.as_ref().len() as u64;
+            }
+        }
+        output.flush().await?;
+
+        Ok(written)
     }
 
-    async fn get_object_tags(
+    async fn get_object_legal_hold(
         &self,
         path: &RemotePath,
-    ) -> Result<std::collections::HashMap<String, String>> {
+        version_id: Option<&str>,
+    ) -> Result<bool> {
         let response = match self
             .inner
-            .get_object_tagging()
+            .get_object_legal_hold()
             .bucket(&path.bucket)
             .key(&path.key)
+            .set_version_id(version_id.map(str::to_string))
             .send()
             .await
         {
             Ok(response) => response,
             Err(e) => {
-                if e.to_string().contains("NoSuchTagSet") {
-                    return Ok(std::collections::HashMap::new());
-                }
-                return Err(Error::General(format!("get_object_tags: {e}")));
-            }
-        };
-
-        let mut tags = std::collections::HashMap::new();
-        for tag in response.tag_set() {
-            let key = tag.key();
-            let value = tag.value();
-            tags.insert(key.to_string(), value.to_string());
-        }
-
-        Ok(tags)
-    }
-
-    async fn get_bucket_tags(
-        &self,
-        bucket: &str,
-    ) -> Result<std::collections::HashMap<String, String>> {
-        let response = match self.inner.get_bucket_tagging().bucket(bucket).send().await {
-            Ok(response) => response,
-            Err(e) => {
-                if e.to_string().contains("NoSuchTagSet") {
-                    return Ok(std::collections::HashMap::new());
-                }
-                return Err(Error::General(format!("get_bucket_tags: {e}")));
+                return match e.code() {
+                    // No legal hold has ever been placed on the object
+                    Some("NoSuchObjectLockConfiguration") => Ok(false),
+                    Some("NoSuchKey" | "NoSuchVersion") => Err(Error::NotFound(path.to_string())),
+                    _ => Err(Error::General(format!(
+                        "get_object_legal_hold: {}",
+                        e.message().unwrap_or(&e.to_string())
+                    ))),
+                };
             }
         };
 
-        let mut tags = std::collections::HashMap::new();
-        for tag in response.tag_set() {
-            let key = tag.key();
-            let value = tag.value();
-            tags.insert(key.to_string(), value.to_string());
-        }
-
-        Ok(tags)
+        Ok(response
+            .legal_hold()
+            .and_then(|hold| hold.status())
+            .is_some_and(|status| *status == ObjectLockLegalHoldStatus::On))
     }
 
-    async fn set_object_tags(
+    async fn set_object_legal_hold(
         &self,
         path: &RemotePath,
-        tags: std::collections::HashMap<String, String>,
+        version_id: Option<&str>,
+        enabled: bool,
     ) -> Result<()> {
-        let tagging = build_tagging(tags)?;
+        let status = if enabled {
+            ObjectLockLegalHoldStatus::On
+        } else {
+            ObjectLockLegalHoldStatus::Off
+        };
 
         self.inner
-            .put_object_tagging()
+            .put_object_legal_hold()
             .bucket(&path.bucket)
             .key(&path.key)
-            .tagging(tagging)
+            .set_version_id(version_id.map(str::to_string))
+            .legal_hold(ObjectLockLegalHold::builder().status(status).build())
             .send()
             .await
-            .map_err(|e| Error::General(format!("set_object_tags: {e}")))?;
+            .map_err(|e| {
+                let message = e.message().map(str::to_string).unwrap_or(e.to_string());
+                match e.code() {
+                    Some("NoSuchKey" | "NoSuchVersion") => Error::NotFound(path.to_string()),
+                    Some("AccessDenied") => {
+                        Error::Auth(format!("set_object_legal_hold: {message}"))
+                    }
+                    _ => Error::General(format!("set_object_legal_hold: {message}")),
+                }
+            })?;
 
         Ok(())
     }
+}
 
-    async fn set_bucket_tags(
-        &self,
-        bucket: &str,
-        tags: std::collections::HashMap<String, String>,
-    ) -> Result<()> {
-        let tagging = build_tagging(tags)?;
-
-        self.inner
-            .put_bucket_tagging()
-            .bucket(bucket)
-            .tagging(tagging)
-            .send()
-            .await
-            .map_err(|e| Error::General(format!("set_bucket_tags: {e}")))?;
-
-        Ok(())
+/// Input serialization for an S3 Select query
+fn select_input(options: &SelectOptions) -> aws_sdk_s3::types::InputSerialization {
+    use aws_sdk_s3::types::{
+        CompressionType, CsvInput, FileHeaderInfo, InputSerialization, JsonInput, JsonType,
+        ParquetInput,
+    };
+
+    let compression = match options.compression {
+        SelectCompression::None => CompressionType::None,
+        SelectCompression::Gzip => CompressionType::Gzip,
+        SelectCompression::Bzip2 => CompressionType::Bzip2,
+    };
+    let input = InputSerialization::builder().compression_type(compression);
+
+    match options.input_format {
+        SelectFormat::Csv => input.csv(
+            CsvInput::builder()
+                .file_header_info(if options.csv_header {
+                    FileHeaderInfo::Use
+                } else {
+                    FileHeaderInfo::None
+                })
+                .field_delimiter(options.csv_delimiter.to_string())
+                .build(),
+        ),
+        SelectFormat::Json => input.json(
+            JsonInput::builder()
+                .r#type(if options.json_lines {
+                    JsonType::Lines
+                } else {
+                    JsonType::Document
+                })
+                .build(),
+        ),
+        SelectFormat::Parquet => input.parquet(ParquetInput::builder().build()),
     }
+    .build()
+}
 
-    async fn delete_object_tags(&self, path: &RemotePath) -> Result<()> {
-        self.inner
-            .delete_object_tagging()
-            .bucket(&path.bucket)
-            .key(&path.key)
-            .send()
-            .await
-            .map_err(|e| Error::General(format!("delete_object_tags: {e}")))?;
-
-        Ok(())
+/// Output serialization for an S3 Select query
+fn select_output(options: &SelectOptions) -> aws_sdk_s3::types::OutputSerialization {
+    use aws_sdk_s3::types::{CsvOutput, JsonOutput, OutputSerialization};
+
+    match options.output_format {
+        SelectFormat::Json => OutputSerialization::builder()
+            .json(JsonOutput::builder().record_delimiter("\n").build())
+            .build(),
+        SelectFormat::Csv | SelectFormat::Parquet => OutputSerialization::builder()
+            .csv(CsvOutput::builder().build())
+            .build(),
     }
+}
 
-    async fn delete_bucket_tags(&self, bucket: &str) -> Result<()> {
-        self.inner
-            .delete_bucket_tagging()
-            .bucket(bucket)
-            .send()
-            .await
-            .map_err(|e| Error::General(format!("delete_bucket_tags: {e}")))?;
-
-        Ok(())
+/// Convert a retention mode to its SDK representation
+fn retention_mode(mode: RetentionMode) -> ObjectLockRetentionMode {
+    match mode {
+        RetentionMode::Governance => ObjectLockRetentionMode::Governance,
+        RetentionMode::Compliance => ObjectLockRetentionMode::Compliance,
     }
 }
 
@@ -878,6 +2052,22 @@ impl ObjectStore for S3Client {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_object_attributes_tagging_header() {
+        let mut attributes = ObjectAttributes::default();
+        assert_eq!(attributes.tagging_header(), None);
+        assert_eq!(attributes.metadata_opt(), None);
+
+        attributes
+            .tags
+            .insert("team".to_string(), "data eng".to_string());
+        attributes.tags.insert("env".to_string(), "a&b".to_string());
+        assert_eq!(
+            attributes.tagging_header().as_deref(),
+            Some("env=a%26b&team=data%20eng")
+        );
+    }
+
     #[test]
     fn test_object_info_creation() {
         let info = ObjectInfo::file("test.txt", 1024);
@@ -888,7 +2078,7 @@ mod tests {
     #[tokio::test]
     async fn reqwest_connector_insecure_without_ca_bundle_succeeds() {
         // When insecure is true and no CA bundle is provided, the connector should be created.
-        let connector = ReqwestConnector::new(true, None).await;
+        let connector = ReqwestConnector::new(true, None, &TimeoutConfig::default()).await;
         assert!(
             connector.is_ok(),
             "Expected insecure connector creation to succeed"
@@ -898,7 +2088,7 @@ mod tests {
     #[tokio::test]
     async fn reqwest_connector_invalid_ca_bundle_path_surfaces_error() {
         // Use an obviously invalid path (empty string) to trigger a read error.
-        let result = ReqwestConnector::new(false, Some("")).await;
+        let result = ReqwestConnector::new(false, Some(""), &TimeoutConfig::default()).await;
         match result {
             Err(Error::Network(msg)) => {
                 assert!(
@@ -909,4 +2099,596 @@ mod tests {
             other => panic!("Expected Error::Network for invalid path, got: {:?}", other),
         }
     }
+
+    /// A request received by the SigV2 stub server
+    #[derive(Debug)]
+    struct StubRequest {
+        method: String,
+        target: String,
+        signature_valid: bool,
+    }
+
+    /// Expected SigV2 string to sign, built from the rules in the AWS
+    /// documentation rather than with the `signing` module under test
+    fn expected_string_to_sign_v2(
+        method: &str,
+        target: &str,
+        headers: &[(String, String)],
+        expires: Option<&str>,
+    ) -> String {
+        const SUB_RESOURCES: &[&str] = &["acl", "tagging", "uploadId", "uploads", "versionId"];
+
+        let url = url::Url::parse(&format!("http://stub{target}")).unwrap();
+        let mut sub_resources: Vec<String> = url
+            .query_pairs()
+            .filter(|(name, _)| SUB_RESOURCES.contains(&name.as_ref()))
+            .map(|(name, value)| {
+                if value.is_empty() {
+                    name.into_owned()
+                } else {
+                    format!("{name}={value}")
+                }
+            })
+            .collect();
+        sub_resources.sort();
+        let mut resource = url.path().to_string();
+        if !sub_resources.is_empty() {
+            resource = format!("{resource}?{}", sub_resources.join("&"));
+        }
+
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map_or("", |(_, v)| v.as_str())
+        };
+        let mut amz_headers: Vec<String> = headers
+            .iter()
+            .filter(|(n, _)| n.to_ascii_lowercase().starts_with("x-amz-"))
+            .map(|(n, v)| format!("{}:{v}", n.to_ascii_lowercase()))
+            .collect();
+        amz_headers.sort();
+
+        // Presigned URLs sign Expires and leave headers other than Content-Type out
+        let (content_md5, date, amz_headers) = match expires {
+            Some(expires) => ("", expires, Vec::new()),
+            None => (header("content-md5"), header("date"), amz_headers),
+        };
+
+        let mut string_to_sign = format!(
+            "{method}\n{content_md5}\n{}\n{date}\n",
+            header("content-type")
+        );
+        for amz_header in amz_headers {
+            string_to_sign.push_str(&amz_header);
+            string_to_sign.push('\n');
+        }
+        string_to_sign.push_str(&resource);
+        string_to_sign
+    }
+
+    /// Check the SigV2 signature of a request, from headers or a presigned query
+    fn verify_v2(
+        method: &str,
+        target: &str,
+        headers: &[(String, String)],
+        access_key: &str,
+        secret_key: &str,
+    ) -> bool {
+        use base64::Engine as _;
+        use hmac::Mac as _;
+
+        let sign = |string_to_sign: &str| {
+            let mut mac = hmac::Hmac::<sha1::Sha1>::new_from_slice(secret_key.as_bytes()).unwrap();
+            mac.update(string_to_sign.as_bytes());
+            base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+        };
+
+        let url = url::Url::parse(&format!("http://stub{target}")).unwrap();
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.into_owned())
+        };
+
+        if let Some(signature) = param("Signature") {
+            let expires = param("Expires").unwrap_or_default();
+            let string_to_sign =
+                expected_string_to_sign_v2(method, target, headers, Some(&expires));
+            return param("AWSAccessKeyId").as_deref() == Some(access_key)
+                && sign(&string_to_sign) == signature;
+        }
+
+        // The SDK never sends x-amz-date alongside the SigV2 Date header
+        let authorization = headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case("authorization"))
+            .map(|(_, v)| v.as_str())
+            .unwrap_or_default();
+        let string_to_sign = expected_string_to_sign_v2(method, target, headers, None);
+        authorization == format!("AWS {access_key}:{}", sign(&string_to_sign))
+    }
+
+    /// Serve `count` requests, answering those with a valid SigV2 signature
+    /// from `respond` and the rest with 403 SignatureDoesNotMatch
+    async fn serve_v2(
+        listener: tokio::net::TcpListener,
+        count: usize,
+        respond: fn(&str, &str) -> (String, String),
+    ) -> Vec<StubRequest> {
+        let mut requests = Vec::new();
+
+        for _ in 0..count {
+            let (mut socket, _) = listener.accept().await.unwrap();
+
+            let mut data = Vec::new();
+            let mut buf = [0u8; 4096];
+            let head_end = loop {
+                if let Some(pos) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break pos;
+                }
+                let n = socket.read(&mut buf).await.unwrap();
+                data.extend_from_slice(&buf[..n]);
+            };
+
+            let head = String::from_utf8_lossy(&data[..head_end]).into_owned();
+            let mut lines = head.split("\r\n");
+            let mut request_line = lines.next().unwrap().split(' ');
+            let method = request_line.next().unwrap().to_string();
+            let target = request_line.next().unwrap().to_string();
+            let headers: Vec<(String, String)> = lines
+                .filter_map(|line| line.split_once(':'))
+                .map(|(n, v)| (n.trim().to_string(), v.trim().to_string()))
+                .collect();
+
+            // Drain the request body
+            let content_length: usize = headers
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case("content-length"))
+                .and_then(|(_, v)| v.parse().ok())
+                .unwrap_or(0);
+            let mut body_len = data.len() - head_end - 4;
+            while body_len < content_length {
+                body_len += socket.read(&mut buf).await.unwrap();
+            }
+
+            let signature_valid = verify_v2(&method, &target, &headers, "access", "secret");
+            let (status_headers, body) = if signature_valid {
+                respond(&method, &target)
+            } else {
+                (
+                    "403 Forbidden\r\nContent-Type: application/xml".to_string(),
+                    "<Error><Code>SignatureDoesNotMatch</Code><Message>bad signature</Message></Error>"
+                        .to_string(),
+                )
+            };
+            let response = format!(
+                "HTTP/1.1 {status_headers}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                if method == "HEAD" { "" } else { body.as_str() }
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            socket.shutdown().await.ok();
+
+            requests.push(StubRequest {
+                method,
+                target,
+                signature_valid,
+            });
+        }
+
+        requests
+    }
+
+    fn respond_ok(method: &str, target: &str) -> (String, String) {
+        match (method, target) {
+            ("GET", "/") | ("GET", "/?x-id=ListBuckets") => (
+                "200 OK\r\nContent-Type: application/xml".to_string(),
+                "<ListAllMyBucketsResult><Owner><ID>rc</ID></Owner><Buckets><Bucket><Name>legacy</Name><CreationDate>2024-01-01T00:00:00.000Z</CreationDate></Bucket></Buckets></ListAllMyBucketsResult>".to_string(),
+            ),
+            _ => (
+                "200 OK\r\nContent-Type: text/plain\r\nETag: \"abc\"\r\nLast-Modified: Mon, 01 Jan 2024 00:00:00 GMT".to_string(),
+                "hello".to_string(),
+            ),
+        }
+    }
+
+    fn v2_alias(endpoint: &str, secret_key: &str) -> Alias {
+        let mut alias = Alias::new("legacy", endpoint, "access", secret_key);
+        alias.signature = "v2".to_string();
+        alias.retry = Some(rc_core::RetryConfig {
+            max_attempts: 1,
+            ..Default::default()
+        });
+        alias
+    }
+
+    #[tokio::test]
+    async fn test_sigv2_requests_against_stub_server() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve_v2(listener, 4, respond_ok));
+
+        let client = S3Client::new(v2_alias(&endpoint, "secret")).await.unwrap();
+
+        let buckets = client.list_buckets().await.unwrap();
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].key, "legacy");
+
+        let path = RemotePath::new("legacy", "legacy", "dir/a b.txt");
+        let info = client.head_object(&path).await.unwrap();
+        assert_eq!(info.size_bytes, Some(5));
+
+        // versionId is a sub-resource and must be signed
+        client
+            .head_object_version(&path, "3HL4kqtJ+v/1")
+            .await
+            .unwrap();
+
+        let url = client.presign_get(&path, 300).await.unwrap();
+        assert!(url.contains("AWSAccessKeyId=access"));
+        assert!(!url.contains("X-Amz-"));
+        let body = reqwest::get(&url).await.unwrap().text().await.unwrap();
+        assert_eq!(body, "hello");
+
+        let requests = server.await.unwrap();
+        assert!(requests.iter().all(|r| r.signature_valid), "{requests:?}");
+        assert_eq!(requests[1].method, "HEAD");
+        assert!(requests[1].target.starts_with("/legacy/dir/a%20b.txt"));
+        assert!(
+            requests[2].target.contains("versionId=3HL4kqtJ%2Bv%2F1"),
+            "{}",
+            requests[2].target
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sigv2_wrong_secret_is_rejected_by_stub_server() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve_v2(listener, 1, respond_ok));
+
+        let client = S3Client::new(v2_alias(&endpoint, "wrong")).await.unwrap();
+        assert!(client.list_buckets().await.is_err());
+
+        let requests = server.await.unwrap();
+        assert!(!requests[0].signature_valid);
+    }
+
+    fn serve_versions(request: &crate::stub::StubRequest) -> crate::stub::StubResponse {
+        let body = match request.query("key-marker").as_deref() {
+            None => {
+                "<ListVersionsResult><IsTruncated>true</IsTruncated>\
+                 <NextKeyMarker>a.txt</NextKeyMarker><NextVersionIdMarker>v1</NextVersionIdMarker>\
+                 <Version><Key>a.txt</Key><VersionId>v2</VersionId><IsLatest>true</IsLatest></Version>\
+                 <Version><Key>a.txt</Key><VersionId>v1</VersionId><IsLatest>false</IsLatest></Version>\
+                 </ListVersionsResult>"
+            }
+            Some(_) => {
+                "<ListVersionsResult><IsTruncated>false</IsTruncated>\
+                 <DeleteMarker><Key>b.txt</Key><VersionId>m1</VersionId><IsLatest>true</IsLatest></DeleteMarker>\
+                 </ListVersionsResult>"
+            }
+        };
+        crate::stub::StubResponse::ok(body).header("Content-Type", "application/xml")
+    }
+
+    #[tokio::test]
+    async fn test_list_object_versions_page_follows_markers() {
+        let server = crate::stub::StubServer::start(serve_versions).await;
+        let client = S3Client::new(server.alias("stub")).await.unwrap();
+        let root = RemotePath::new("stub", "bucket", "");
+
+        let first = client
+            .list_object_versions_page(&root, None, 2)
+            .await
+            .unwrap();
+        assert_eq!(first.versions.len(), 2);
+        let next = first.next.unwrap();
+        assert_eq!(
+            next,
+            VersionMarker {
+                key: "a.txt".to_string(),
+                version_id: Some("v1".to_string()),
+            }
+        );
+
+        let second = client
+            .list_object_versions_page(&root, Some(&next), 2)
+            .await
+            .unwrap();
+        assert_eq!(second.versions.len(), 1);
+        assert!(second.versions[0].is_delete_marker);
+        assert!(second.next.is_none());
+
+        let requests = server.requests();
+        assert_eq!(requests[0].query("max-keys").as_deref(), Some("2"));
+        assert!(!requests[0].has_query("key-marker"));
+        assert_eq!(requests[1].query("key-marker").as_deref(), Some("a.txt"));
+        assert_eq!(
+            requests[1].query("version-id-marker").as_deref(),
+            Some("v1")
+        );
+
+        // The full listing walks both pages
+        let all = client.list_object_versions(&root, None).await.unwrap();
+        assert_eq!(all.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_copy_object_version_is_server_side() {
+        let server = crate::stub::StubServer::start(|_| {
+            crate::stub::StubResponse::ok(
+                "<CopyObjectResult><ETag>\"abc\"</ETag></CopyObjectResult>",
+            )
+            .header("Content-Type", "application/xml")
+        })
+        .await;
+        let client = S3Client::new(server.alias("stub")).await.unwrap();
+
+        let src = RemotePath::new("stub", "bucket", "a.txt");
+        let dst = RemotePath::new("stub", "bucket", "b.txt");
+        client.copy_object_version(&src, "v1", &dst).await.unwrap();
+
+        // One CopyObject call and a HEAD of the result; the body never passes through
+        let requests = server.requests();
+        assert!(requests.iter().all(|r| r.method != "GET"), "{requests:?}");
+        let copy = requests.iter().find(|r| r.method == "PUT").unwrap();
+        assert_eq!(copy.path(), "/bucket/b.txt");
+        assert_eq!(
+            copy.header("x-amz-copy-source"),
+            Some("bucket/a.txt?versionId=v1")
+        );
+    }
+
+    /// Create `bucket` against a stub that rejects versioning changes when
+    /// `versioning_fails` is set, returning the result and the requests sent
+    async fn create_bucket_requests(
+        options: CreateBucketOptions,
+        versioning_fails: bool,
+    ) -> (Result<()>, Vec<crate::stub::StubRequest>) {
+        let server = crate::stub::StubServer::start(move |request: &crate::stub::StubRequest| {
+            if versioning_fails && request.has_query("versioning") {
+                crate::stub::StubResponse::error("403 Forbidden", "AccessDenied")
+            } else {
+                crate::stub::StubResponse::ok(Vec::new())
+            }
+        })
+        .await;
+        let client = S3Client::new(server.alias("stub")).await.unwrap();
+
+        let result = client.create_bucket("bucket", &options).await;
+        (result, server.requests())
+    }
+
+    #[tokio::test]
+    async fn test_create_bucket_with_lock_and_region() {
+        let options = CreateBucketOptions {
+            region: Some("eu-west-1".to_string()),
+            object_lock: true,
+            versioning: true,
+        };
+        let (result, requests) = create_bucket_requests(options, false).await;
+        result.unwrap();
+
+        // Object lock enables versioning itself, so no follow-up request is sent
+        assert_eq!(requests.len(), 1, "{requests:?}");
+        let create = &requests[0];
+        assert_eq!(create.method, "PUT");
+        assert_eq!(create.path().trim_end_matches('/'), "/bucket");
+        assert_eq!(
+            create.header("x-amz-bucket-object-lock-enabled"),
+            Some("true")
+        );
+        let body = String::from_utf8_lossy(&create.body);
+        assert!(
+            body.contains("<LocationConstraint>eu-west-1</LocationConstraint>"),
+            "{body}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_bucket_with_versioning_in_us_east_1() {
+        let options = CreateBucketOptions {
+            region: Some("us-east-1".to_string()),
+            versioning: true,
+            ..Default::default()
+        };
+        let (result, requests) = create_bucket_requests(options, false).await;
+        result.unwrap();
+
+        assert_eq!(requests.len(), 2, "{requests:?}");
+        let create = &requests[0];
+        assert!(!create.has_query("versioning"));
+        assert_eq!(create.header("x-amz-bucket-object-lock-enabled"), None);
+        assert!(
+            !String::from_utf8_lossy(&create.body).contains("LocationConstraint"),
+            "us-east-1 must not be sent as a location constraint"
+        );
+
+        let versioning = &requests[1];
+        assert_eq!(versioning.method, "PUT");
+        assert!(versioning.has_query("versioning"));
+        assert!(String::from_utf8_lossy(&versioning.body).contains("<Status>Enabled</Status>"));
+    }
+
+    #[tokio::test]
+    async fn test_create_bucket_reports_failed_versioning() {
+        let options = CreateBucketOptions {
+            versioning: true,
+            ..Default::default()
+        };
+        let (result, requests) = create_bucket_requests(options, true).await;
+
+        assert_eq!(requests.len(), 2, "{requests:?}");
+        match result {
+            Err(Error::General(message)) => assert!(
+                message.contains("was created but enabling versioning failed"),
+                "{message}"
+            ),
+            other => panic!("expected a general error, got {other:?}"),
+        }
+    }
+
+    fn select_options(input_format: SelectFormat, output_format: SelectFormat) -> SelectOptions {
+        SelectOptions {
+            expression: "select * from S3Object s".to_string(),
+            input_format,
+            compression: SelectCompression::Gzip,
+            csv_header: true,
+            csv_delimiter: '\t',
+            json_lines: false,
+            output_format,
+        }
+    }
+
+    #[test]
+    fn test_select_serialization() {
+        use aws_sdk_s3::types::{CompressionType, FileHeaderInfo, JsonType};
+
+        let options = select_options(SelectFormat::Csv, SelectFormat::Json);
+        let input = select_input(&options);
+        assert_eq!(input.compression_type(), Some(&CompressionType::Gzip));
+        let csv = input.csv().unwrap();
+        assert_eq!(csv.file_header_info(), Some(&FileHeaderInfo::Use));
+        assert_eq!(csv.field_delimiter(), Some("\t"));
+        assert!(input.json().is_none());
+
+        let output = select_output(&options);
+        assert_eq!(output.json().unwrap().record_delimiter(), Some("\n"));
+        assert!(output.csv().is_none());
+
+        let options = select_options(SelectFormat::Json, SelectFormat::Csv);
+        let input = select_input(&options);
+        assert_eq!(input.json().unwrap().r#type(), Some(&JsonType::Document));
+        assert!(input.csv().is_none());
+        assert!(select_output(&options).csv().is_some());
+
+        let options = select_options(SelectFormat::Parquet, SelectFormat::Csv);
+        assert!(select_input(&options).parquet().is_some());
+    }
+
+    /// CRC-32 (IEEE) as used by the event stream framing
+    fn crc32(data: &[u8]) -> u32 {
+        let mut crc = !0u32;
+        for byte in data {
+            crc ^= u32::from(*byte);
+            for _ in 0..8 {
+                crc = if crc & 1 == 1 {
+                    (crc >> 1) ^ 0xEDB8_8320
+                } else {
+                    crc >> 1
+                };
+            }
+        }
+        !crc
+    }
+
+    /// Encode one event stream message with string headers
+    fn event_message(headers: &[(&str, &str)], payload: &[u8]) -> Vec<u8> {
+        let mut encoded_headers = Vec::new();
+        for (name, value) in headers {
+            encoded_headers.push(name.len() as u8);
+            encoded_headers.extend_from_slice(name.as_bytes());
+            encoded_headers.push(7);
+            encoded_headers.extend_from_slice(&(value.len() as u16).to_be_bytes());
+            encoded_headers.extend_from_slice(value.as_bytes());
+        }
+
+        let total = 12 + encoded_headers.len() + payload.len() + 4;
+        let mut message = Vec::with_capacity(total);
+        message.extend_from_slice(&(total as u32).to_be_bytes());
+        message.extend_from_slice(&(encoded_headers.len() as u32).to_be_bytes());
+        message.extend_from_slice(&crc32(&message).to_be_bytes());
+        message.extend_from_slice(&encoded_headers);
+        message.extend_from_slice(payload);
+        message.extend_from_slice(&crc32(&message).to_be_bytes());
+        message
+    }
+
+    fn serve_select(_: &crate::stub::StubRequest) -> crate::stub::StubResponse {
+        let event = |event_type, payload: &[u8]| {
+            event_message(
+                &[
+                    (":message-type", "event"),
+                    (":event-type", event_type),
+                    (":content-type", "application/octet-stream"),
+                ],
+                payload,
+            )
+        };
+
+        let mut body = event("Records", b"a\t1\n");
+        body.extend(event("Records", b"b\t2\n"));
+        body.extend(event_message(
+            &[(":message-type", "event"), (":event-type", "End")],
+            b"",
+        ));
+        crate::stub::StubResponse::ok(body)
+    }
+
+    #[tokio::test]
+    async fn test_select_object_content_decodes_records() {
+        let server = crate::stub::StubServer::start(serve_select).await;
+        let client = S3Client::new(server.alias("stub")).await.unwrap();
+        let path = RemotePath::new("stub", "bucket", "data.tsv");
+
+        let mut output = Vec::new();
+        let written = client
+            .select_object_content(
+                &path,
+                &select_options(SelectFormat::Csv, SelectFormat::Csv),
+                &mut output,
+            )
+            .await
+            .unwrap();
+        assert_eq!(output, b"a\t1\nb\t2\n");
+        assert_eq!(written, 8);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "POST");
+        assert!(requests[0].has_query("select"));
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(
+            body.contains("<FileHeaderInfo>USE</FileHeaderInfo>"),
+            "{body}"
+        );
+        assert!(
+            body.contains("<CompressionType>GZIP</CompressionType>"),
+            "{body}"
+        );
+        assert!(
+            body.contains("<FieldDelimiter>\t</FieldDelimiter>"),
+            "{body}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_signature_version_is_rejected() {
+        let mut alias = Alias::new("test", "http://localhost:9000", "access", "secret");
+        alias.signature = "v3".to_string();
+        assert!(matches!(S3Client::new(alias).await, Err(Error::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn test_presign_with_bucket_domain() {
+        let mut alias = Alias::new("corp", "https://gateway.corp:9443", "access", "secret");
+        alias.bucket_domain = Some("s3.internal.corp".to_string());
+        let client = S3Client::new(alias).await.unwrap();
+
+        let path = RemotePath::new("corp", "photos", "2024/cat.jpg");
+        let url = client.presign_get(&path, 300).await.unwrap();
+        assert!(
+            url.starts_with("https://photos.s3.internal.corp:9443/2024/cat.jpg?"),
+            "{url}"
+        );
+
+        // Bucket names that are not valid host labels stay path-style
+        let path = RemotePath::new("corp", "my.photos", "cat.jpg");
+        let url = client.presign_get(&path, 300).await.unwrap();
+        assert!(
+            url.starts_with("https://gateway.corp:9443/my.photos/cat.jpg?"),
+            "{url}"
+        );
+    }
 }
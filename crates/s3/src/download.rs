@@ -0,0 +1,416 @@
+//! Parallel ranged download support
+//!
+//! Downloads large objects as concurrent byte-range GETs written at their
+//! offsets into a temporary file, which is renamed over the destination once
+//! every range has been fetched. Progress is recorded next to the temporary
+//! file so an interrupted download can be resumed.
+
+use std::path::{Path, PathBuf};
+
+use futures::{StreamExt, TryStreamExt};
+use rc_core::{Error, ObjectInfo, RemotePath, Result};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+use crate::S3Client;
+use crate::multipart::{MultipartConfig, calculate_parts, part_byte_range};
+
+/// Suffix of the temporary file a download is written to
+pub const PARTIAL_SUFFIX: &str = ".rcpart";
+
+/// State of a ranged download (for resume)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DownloadState {
+    /// Source object path
+    pub source: String,
+
+    /// ETag of the object being downloaded
+    pub etag: Option<String>,
+
+    /// Total object size
+    pub total_size: u64,
+
+    /// Part size used
+    pub part_size: u64,
+
+    /// Part numbers already written to the temporary file
+    pub completed_parts: Vec<i32>,
+
+    /// Timestamp of last update
+    pub last_updated: jiff::Timestamp,
+}
+
+impl DownloadState {
+    /// Create a new download state
+    pub fn new(
+        source: impl Into<String>,
+        etag: Option<String>,
+        total_size: u64,
+        part_size: u64,
+    ) -> Self {
+        Self {
+            source: source.into(),
+            etag,
+            total_size,
+            part_size,
+            completed_parts: Vec::new(),
+            last_updated: jiff::Timestamp::now(),
+        }
+    }
+
+    /// Whether this state was recorded for the same, unchanged object
+    pub fn matches(&self, source: &str, etag: Option<&str>, total_size: u64) -> bool {
+        self.source == source
+            && etag.is_some()
+            && self.etag.as_deref() == etag
+            && self.total_size == total_size
+    }
+
+    /// Add a completed part
+    pub fn add_completed_part(&mut self, part_number: i32) {
+        self.completed_parts.push(part_number);
+        self.last_updated = jiff::Timestamp::now();
+    }
+
+    /// Bytes covered by the completed parts
+    pub fn completed_bytes(&self) -> u64 {
+        self.completed_parts
+            .iter()
+            .map(|n| {
+                let (start, end) = part_byte_range(*n, self.part_size, self.total_size);
+                end - start
+            })
+            .sum()
+    }
+
+    /// Temporary file a download to `dst` is written to
+    pub fn partial_path(dst: &Path) -> PathBuf {
+        let mut name = dst.as_os_str().to_owned();
+        name.push(PARTIAL_SUFFIX);
+        PathBuf::from(name)
+    }
+
+    /// State file path for a download to `dst`
+    pub fn state_file_path(dst: &Path) -> PathBuf {
+        let mut name = Self::partial_path(dst).into_os_string();
+        name.push(".json");
+        PathBuf::from(name)
+    }
+
+    /// Save state next to the temporary file
+    pub fn save(&self, dst: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(Self::state_file_path(dst), json)?;
+        Ok(())
+    }
+
+    /// Load state for a download to `dst`, if any
+    pub fn load(dst: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(Self::state_file_path(dst)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Delete the state file for a download to `dst`
+    pub fn delete(dst: &Path) -> Result<()> {
+        let path = Self::state_file_path(dst);
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Download an object into `dst` using concurrent ranged GETs
+///
+/// `info` must come from a HEAD of `src`; its ETag pins every range request
+/// to the same object version. Ranges are fetched `config.concurrency` at a
+/// time and `on_part` is called with the byte size of each completed range.
+/// A matching partial download left behind by an earlier run is resumed.
+pub async fn download_file(
+    client: &S3Client,
+    src: &RemotePath,
+    info: &ObjectInfo,
+    dst: &Path,
+    config: &MultipartConfig,
+    on_part: &(dyn Fn(u64) + Send + Sync),
+) -> Result<u64> {
+    let total_size = info.size_bytes.unwrap_or(0).max(0) as u64;
+    let source = src.to_string();
+    let etag = info.etag.as_deref();
+    let partial = DownloadState::partial_path(dst);
+
+    let existing = DownloadState::load(dst).filter(|state| {
+        state.matches(&source, etag, total_size)
+            && std::fs::metadata(&partial).is_ok_and(|m| m.len() == total_size)
+    });
+
+    let mut state = match existing {
+        Some(state) => {
+            tracing::debug!(
+                "Resuming download of {source} with {} parts done",
+                state.completed_parts.len()
+            );
+            on_part(state.completed_bytes());
+            state
+        }
+        None => {
+            let file = tokio::fs::File::create(&partial).await?;
+            file.set_len(total_size).await?;
+            let state = DownloadState::new(
+                &source,
+                etag.map(str::to_string),
+                total_size,
+                config.calculate_part_size(total_size),
+            );
+            state.save(dst)?;
+            state
+        }
+    };
+
+    download_parts(client, src, &partial, &mut state, dst, config, on_part).await?;
+
+    tokio::fs::rename(&partial, dst).await?;
+    DownloadState::delete(dst)?;
+
+    Ok(total_size)
+}
+
+/// Fetch all parts not yet recorded in `state`, updating it as parts complete
+async fn download_parts(
+    client: &S3Client,
+    src: &RemotePath,
+    partial: &Path,
+    state: &mut DownloadState,
+    dst: &Path,
+    config: &MultipartConfig,
+    on_part: &(dyn Fn(u64) + Send + Sync),
+) -> Result<()> {
+    let (part_size, total_size) = (state.part_size, state.total_size);
+    let etag = state.etag.clone();
+    let etag = etag.as_deref();
+    let part_count = calculate_parts(total_size, part_size) as i32;
+
+    let pending: Vec<i32> = (1..=part_count)
+        .filter(|n| !state.completed_parts.contains(n))
+        .collect();
+
+    let mut downloads = futures::stream::iter(pending)
+        .map(|part_number| async move {
+            let (start, end) = part_byte_range(part_number, part_size, total_size);
+            let mut body = client.get_object_range(src, None, start, end, etag).await?;
+
+            let mut file = tokio::fs::OpenOptions::new()
+                .write(true)
+                .open(partial)
+                .await?;
+            file.seek(std::io::SeekFrom::Start(start)).await?;
+            let written = tokio::io::copy(&mut body, &mut file).await?;
+            file.flush().await?;
+            // The part is recorded as done next, so its data must be on disk
+            // before a crash could leave the state ahead of the file
+            file.sync_data().await?;
+
+            if written != end - start {
+                return Err(Error::Network(format!(
+                    "Short read for part {part_number}: expected {} bytes, got {written}",
+                    end - start
+                )));
+            }
+            Ok::<_, Error>((part_number, written))
+        })
+        .buffer_unordered(config.concurrency);
+
+    while let Some((part_number, len)) = downloads.try_next().await? {
+        state.add_completed_part(part_number);
+        state.save(dst)?;
+        on_part(len);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multipart::MIN_PART_SIZE;
+    use crate::stub::{StubRequest, StubResponse, StubServer};
+
+    /// Size of the stub object: two full parts and a short third one
+    const OBJECT_SIZE: u64 = 2 * MIN_PART_SIZE + 1024;
+
+    fn object_byte(offset: u64) -> u8 {
+        (offset % 251) as u8
+    }
+
+    /// Serve byte ranges of the stub object, honouring If-Match
+    fn serve_range(request: &StubRequest) -> StubResponse {
+        if request.header("if-match") != Some("\"abc\"") {
+            return StubResponse::error("412 Precondition Failed", "PreconditionFailed");
+        }
+        let Some((start, end)) = request
+            .header("range")
+            .and_then(|r| r.strip_prefix("bytes="))
+            .and_then(|r| r.split_once('-'))
+            .and_then(|(s, e)| Some((s.parse::<u64>().ok()?, e.parse::<u64>().ok()?)))
+        else {
+            return StubResponse::error("400 Bad Request", "InvalidRange");
+        };
+
+        let body: Vec<u8> = (start..=end).map(object_byte).collect();
+        StubResponse::status("206 Partial Content", body)
+            .header(
+                "Content-Range",
+                &format!("bytes {start}-{end}/{OBJECT_SIZE}"),
+            )
+            .header("ETag", "\"abc\"")
+    }
+
+    fn object_info() -> ObjectInfo {
+        let mut info = ObjectInfo::file("key", OBJECT_SIZE as i64);
+        info.etag = Some("abc".to_string());
+        info
+    }
+
+    fn requested_ranges(server: &StubServer) -> Vec<String> {
+        let mut ranges: Vec<String> = server
+            .requests()
+            .iter()
+            .filter_map(|r| r.header("range").map(str::to_string))
+            .collect();
+        ranges.sort();
+        ranges
+    }
+
+    #[tokio::test]
+    async fn test_download_file_against_stub_server() {
+        let server = StubServer::start(serve_range).await;
+        let client = S3Client::new(server.alias("stub")).await.unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let dst = dir.path().join("file.bin");
+        let src = RemotePath::new("stub", "bucket", "key");
+
+        let progress = std::sync::atomic::AtomicU64::new(0);
+        let on_part = |n: u64| {
+            progress.fetch_add(n, std::sync::atomic::Ordering::Relaxed);
+        };
+        let size = download_file(
+            &client,
+            &src,
+            &object_info(),
+            &dst,
+            &MultipartConfig::new().part_size(MIN_PART_SIZE),
+            &on_part,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(size, OBJECT_SIZE);
+        assert_eq!(progress.into_inner(), OBJECT_SIZE);
+        let data = std::fs::read(&dst).unwrap();
+        assert_eq!(data.len() as u64, OBJECT_SIZE);
+        assert!(
+            data.iter()
+                .enumerate()
+                .all(|(i, b)| *b == object_byte(i as u64))
+        );
+        assert!(!DownloadState::partial_path(&dst).exists());
+        assert!(DownloadState::load(&dst).is_none());
+
+        let p = MIN_PART_SIZE;
+        let mut expected = vec![
+            format!("bytes=0-{}", p - 1),
+            format!("bytes={p}-{}", 2 * p - 1),
+            format!("bytes={}-{}", 2 * p, OBJECT_SIZE - 1),
+        ];
+        expected.sort();
+        assert_eq!(requested_ranges(&server), expected);
+    }
+
+    #[tokio::test]
+    async fn test_download_file_resumes_recorded_parts() {
+        let server = StubServer::start(serve_range).await;
+        let client = S3Client::new(server.alias("stub")).await.unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let dst = dir.path().join("file.bin");
+        let src = RemotePath::new("stub", "bucket", "key");
+
+        // An earlier run wrote the first part before it was interrupted
+        let mut partial = vec![0u8; OBJECT_SIZE as usize];
+        for (i, b) in partial.iter_mut().enumerate().take(MIN_PART_SIZE as usize) {
+            *b = object_byte(i as u64);
+        }
+        std::fs::write(DownloadState::partial_path(&dst), &partial).unwrap();
+        let mut state = DownloadState::new(
+            src.to_string(),
+            Some("abc".to_string()),
+            OBJECT_SIZE,
+            MIN_PART_SIZE,
+        );
+        state.add_completed_part(1);
+        state.save(&dst).unwrap();
+
+        download_file(
+            &client,
+            &src,
+            &object_info(),
+            &dst,
+            &MultipartConfig::new().part_size(MIN_PART_SIZE),
+            &|_| {},
+        )
+        .await
+        .unwrap();
+
+        let data = std::fs::read(&dst).unwrap();
+        assert!(
+            data.iter()
+                .enumerate()
+                .all(|(i, b)| *b == object_byte(i as u64))
+        );
+        assert_eq!(requested_ranges(&server).len(), 2);
+        assert!(
+            !requested_ranges(&server)
+                .iter()
+                .any(|r| r.starts_with("bytes=0-"))
+        );
+    }
+
+    #[test]
+    fn test_partial_paths() {
+        let dst = Path::new("/tmp/data/file.bin");
+        assert_eq!(
+            DownloadState::partial_path(dst),
+            PathBuf::from("/tmp/data/file.bin.rcpart")
+        );
+        assert_eq!(
+            DownloadState::state_file_path(dst),
+            PathBuf::from("/tmp/data/file.bin.rcpart.json")
+        );
+    }
+
+    #[test]
+    fn test_download_state_matches() {
+        let state = DownloadState::new("a/b/key", Some("etag".to_string()), 1000, 100);
+        assert!(state.matches("a/b/key", Some("etag"), 1000));
+        assert!(!state.matches("a/b/other", Some("etag"), 1000));
+        assert!(!state.matches("a/b/key", Some("changed"), 1000));
+        assert!(!state.matches("a/b/key", Some("etag"), 999));
+        assert!(!state.matches("a/b/key", None, 1000));
+    }
+
+    #[test]
+    fn test_download_state_save_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let dst = dir.path().join("file.bin");
+
+        let mut state = DownloadState::new("a/b/key", Some("etag".to_string()), 250, 100);
+        state.add_completed_part(1);
+        state.add_completed_part(3);
+        state.save(&dst).unwrap();
+
+        let loaded = DownloadState::load(&dst).unwrap();
+        assert_eq!(loaded.completed_parts, vec![1, 3]);
+        assert_eq!(loaded.completed_bytes(), 150);
+
+        DownloadState::delete(&dst).unwrap();
+        assert!(DownloadState::load(&dst).is_none());
+    }
+}
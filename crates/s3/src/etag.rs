@@ -0,0 +1,183 @@
+//! ETag computation for local files
+//!
+//! S3 ETags are the MD5 of the content for single-part uploads, and the MD5 of
+//! the concatenated part MD5s followed by `-<parts>` for multipart uploads.
+//! This module computes the same values for local files so they can be
+//! compared against remote objects by content.
+
+use std::io::Read;
+use std::path::Path;
+
+use md5::{Digest, Md5};
+
+const MIB: u64 = 1024 * 1024;
+
+/// Part sizes commonly used by S3 clients, tried in order when inferring
+const COMMON_PART_SIZES: [u64; 10] = [5, 8, 15, 16, 32, 64, 100, 128, 256, 512];
+
+/// Number of parts encoded in a multipart ETag (`<hex>-<parts>`), if any
+pub fn multipart_parts(etag: &str) -> Option<u64> {
+    let (_, parts) = etag.trim_matches('"').rsplit_once('-')?;
+    parts.parse().ok().filter(|&n| n > 0)
+}
+
+/// Infer the part size used to upload an object of `size` bytes in `parts` parts
+///
+/// Common client part sizes are tried first, then the smallest size rounded up
+/// to a whole MiB, then the exact minimum size.
+pub fn infer_part_size(size: u64, parts: u64) -> Option<u64> {
+    if parts == 0 {
+        return None;
+    }
+    if parts == 1 {
+        return Some(size.max(1));
+    }
+
+    let min = size.div_ceil(parts);
+    COMMON_PART_SIZES
+        .iter()
+        .map(|mib| mib * MIB)
+        .chain([min.div_ceil(MIB) * MIB, min])
+        .find(|&p| p >= min && size.div_ceil(p) == parts)
+}
+
+/// Compute the MD5 ETag of a file
+pub fn file_md5(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Md5::new();
+    hash_reader(&mut file, u64::MAX, &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Compute the multipart-style ETag of a file uploaded with `part_size` parts
+pub fn file_multipart_etag(path: &Path, part_size: u64) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut digests = Vec::new();
+    let mut parts = 0;
+
+    loop {
+        let mut hasher = Md5::new();
+        if hash_reader(&mut file, part_size, &mut hasher)? == 0 && parts > 0 {
+            break;
+        }
+        digests.extend_from_slice(&hasher.finalize());
+        parts += 1;
+    }
+
+    Ok(format!("{}-{parts}", hex::encode(Md5::digest(&digests))))
+}
+
+/// Compute the ETag of a local file in the same form as `remote_etag`
+///
+/// Multipart ETags are reproduced using the part size inferred from their
+/// part count; anything else is compared as a plain MD5.
+pub fn local_etag_like(
+    path: &Path,
+    size: u64,
+    remote_etag: Option<&str>,
+) -> std::io::Result<String> {
+    match remote_etag
+        .and_then(multipart_parts)
+        .and_then(|parts| infer_part_size(size, parts))
+    {
+        Some(part_size) => file_multipart_etag(path, part_size),
+        None => file_md5(path),
+    }
+}
+
+/// Whether two ETags are equal, ignoring quotes and hex case
+pub fn etags_match(a: &str, b: &str) -> bool {
+    a.trim_matches('"')
+        .eq_ignore_ascii_case(b.trim_matches('"'))
+}
+
+/// Feed up to `limit` bytes from `reader` into `hasher`, returning the bytes read
+fn hash_reader(reader: &mut impl Read, limit: u64, hasher: &mut Md5) -> std::io::Result<u64> {
+    let mut buf = vec![0u8; MIB as usize];
+    let mut total = 0u64;
+
+    while total < limit {
+        let want = (limit - total).min(buf.len() as u64) as usize;
+        let n = reader.read(&mut buf[..want])?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+        total += n as u64;
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multipart_parts() {
+        assert_eq!(
+            multipart_parts("\"d41d8cd98f00b204e9800998ecf8427e\""),
+            None
+        );
+        assert_eq!(multipart_parts("\"abc123-3\""), Some(3));
+        assert_eq!(multipart_parts("abc123-12"), Some(12));
+        assert_eq!(multipart_parts("abc123-x"), None);
+    }
+
+    #[test]
+    fn test_infer_part_size() {
+        // 100 MiB in 13 parts matches the common 8 MiB size
+        assert_eq!(infer_part_size(100 * MIB, 13), Some(8 * MIB));
+        // 100 MiB in 2 parts matches 64 MiB before 100 MiB
+        assert_eq!(infer_part_size(100 * MIB, 2), Some(64 * MIB));
+        // 1000 MiB in 3 parts needs a non-standard size
+        assert_eq!(infer_part_size(1000 * MIB, 3), Some(334 * MIB));
+        assert_eq!(infer_part_size(10, 1), Some(10));
+        assert_eq!(infer_part_size(10, 0), None);
+    }
+
+    #[test]
+    fn test_file_md5() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hello.txt");
+        std::fs::write(&path, b"hello").unwrap();
+        assert_eq!(file_md5(&path).unwrap(), "5d41402abc4b2a76b9719d911017c592");
+
+        let empty = dir.path().join("empty");
+        std::fs::write(&empty, b"").unwrap();
+        assert_eq!(
+            file_md5(&empty).unwrap(),
+            "d41d8cd98f00b204e9800998ecf8427e"
+        );
+    }
+
+    #[test]
+    fn test_file_multipart_etag() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.bin");
+        std::fs::write(&path, b"helloworld!").unwrap();
+
+        let mut digests = Vec::new();
+        for part in [&b"hello"[..], b"world", b"!"] {
+            digests.extend_from_slice(&Md5::digest(part));
+        }
+        let expected = format!("{}-3", hex::encode(Md5::digest(&digests)));
+
+        assert_eq!(file_multipart_etag(&path, 5).unwrap(), expected);
+        // 11 bytes in 3 parts infers the minimal 4-byte part size
+        assert_eq!(
+            local_etag_like(&path, 11, Some("\"0123-3\"")).unwrap(),
+            file_multipart_etag(&path, 4).unwrap()
+        );
+        assert_eq!(
+            local_etag_like(&path, 11, Some("\"0123\"")).unwrap(),
+            file_md5(&path).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_etags_match() {
+        assert!(etags_match("\"ABC\"", "abc"));
+        assert!(!etags_match("abc", "abd"));
+    }
+}
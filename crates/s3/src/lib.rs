@@ -4,11 +4,20 @@
 //! using the aws-sdk-s3 crate. It is the only crate that directly
 //! depends on the AWS SDK.
 
+mod addressing;
 pub mod admin;
 pub mod capability;
 pub mod client;
+pub mod download;
+pub mod etag;
 pub mod multipart;
+pub mod notification;
+mod signing;
+#[cfg(test)]
+mod stub;
+pub mod transfer;
 
 pub use admin::AdminClient;
-pub use client::S3Client;
+pub use client::{ObjectAttributes, S3Client};
+pub use download::DownloadState;
 pub use multipart::{MultipartConfig, UploadState};
@@ -4,7 +4,13 @@
 
 use std::path::{Path, PathBuf};
 
-use rc_core::Result;
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
+use rc_core::{Error, ObjectInfo, ObjectStore as _, RemotePath, Result};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+use crate::S3Client;
+use crate::client::ObjectAttributes;
 
 /// Default part size: 64 MiB
 pub const DEFAULT_PART_SIZE: u64 = 64 * 1024 * 1024;
@@ -18,6 +24,9 @@ pub const MAX_PART_SIZE: u64 = 5 * 1024 * 1024 * 1024;
 /// Maximum number of parts: 10,000 (S3 limit)
 pub const MAX_PARTS: usize = 10_000;
 
+/// Default size above which uploads switch to multipart: 64 MiB
+pub const DEFAULT_MULTIPART_THRESHOLD: u64 = 64 * 1024 * 1024;
+
 /// Multipart upload configuration
 #[derive(Debug, Clone)]
 pub struct MultipartConfig {
@@ -27,6 +36,9 @@ pub struct MultipartConfig {
     /// Number of concurrent uploads
     pub concurrency: usize,
 
+    /// Files larger than this are uploaded with multipart
+    pub threshold: u64,
+
     /// Path for state file (for resume support)
     pub state_dir: Option<PathBuf>,
 }
@@ -36,6 +48,7 @@ impl Default for MultipartConfig {
         Self {
             part_size: DEFAULT_PART_SIZE,
             concurrency: 4,
+            threshold: DEFAULT_MULTIPART_THRESHOLD,
             state_dir: None,
         }
     }
@@ -56,11 +69,21 @@ impl MultipartConfig {
         self
     }
 
+    pub fn threshold(mut self, size: u64) -> Self {
+        self.threshold = size;
+        self
+    }
+
     pub fn state_dir(mut self, path: impl Into<PathBuf>) -> Self {
         self.state_dir = Some(path.into());
         self
     }
 
+    /// Whether a file of the given size should use multipart upload
+    pub fn use_multipart(&self, file_size: u64) -> bool {
+        file_size > self.threshold
+    }
+
     /// Calculate appropriate part size for a file
     pub fn calculate_part_size(&self, file_size: u64) -> u64 {
         // If file fits in one part, use minimum
@@ -93,6 +116,10 @@ pub struct UploadState {
     /// Source file path (if local)
     pub source: Option<String>,
 
+    /// Modification time of the source file when the upload started
+    #[serde(default)]
+    pub source_modified: Option<jiff::Timestamp>,
+
     /// Total file size
     pub total_size: u64,
 
@@ -124,6 +151,7 @@ impl UploadState {
             upload_id: upload_id.into(),
             target: target.into(),
             source: None,
+            source_modified: None,
             total_size,
             part_size,
             completed_parts: Vec::new(),
@@ -137,6 +165,46 @@ impl UploadState {
         self
     }
 
+    /// Set source file modification time
+    pub fn with_source_modified(mut self, modified: Option<jiff::Timestamp>) -> Self {
+        self.source_modified = modified;
+        self
+    }
+
+    /// Whether this state was recorded for the given, unchanged source file
+    pub fn matches_source(
+        &self,
+        source: &str,
+        total_size: u64,
+        modified: Option<jiff::Timestamp>,
+    ) -> bool {
+        self.source.as_deref() == Some(source)
+            && self.total_size == total_size
+            && modified.is_some()
+            && self.source_modified == modified
+    }
+
+    /// Keep only completed parts that the server also reports with the same ETag
+    pub fn retain_uploaded(&mut self, uploaded: &[CompletedPart]) {
+        self.completed_parts.retain(|part| {
+            uploaded.iter().any(|u| {
+                u.part_number == part.part_number
+                    && u.etag.trim_matches('"') == part.etag.trim_matches('"')
+            })
+        });
+    }
+
+    /// Bytes covered by the completed parts
+    pub fn completed_bytes(&self) -> u64 {
+        self.completed_parts
+            .iter()
+            .map(|p| {
+                let (start, end) = part_byte_range(p.part_number, self.part_size, self.total_size);
+                end - start
+            })
+            .sum()
+    }
+
     /// Add a completed part
     pub fn add_completed_part(&mut self, part_number: i32, etag: String) {
         self.completed_parts
@@ -238,9 +306,201 @@ pub fn part_byte_range(part_number: i32, part_size: u64, total_size: u64) -> (u6
     (start, end)
 }
 
+/// Upload a local file using concurrent multipart upload
+///
+/// Parts are read from disk and uploaded `config.concurrency` at a time, so
+/// memory use is bounded by `concurrency * part_size`. `on_part` is called
+/// with the byte size of each part as it completes.
+///
+/// When `config.state_dir` is set, progress is persisted after every part and
+/// a pending upload of the same, unchanged file to the same target is resumed
+/// instead of started over. A failed upload is then left in place so it can be
+/// resumed; without a state directory it is aborted.
+pub async fn upload_file(
+    client: &S3Client,
+    target: &RemotePath,
+    file: &Path,
+    content_type: Option<&str>,
+    config: &MultipartConfig,
+    on_part: &(dyn Fn(u64) + Send + Sync),
+) -> Result<ObjectInfo> {
+    let metadata = tokio::fs::metadata(file).await?;
+    let total_size = metadata.len();
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| jiff::Timestamp::try_from(t).ok());
+    let source = source_id(file).await?;
+
+    let mut state =
+        match find_resumable(client, target, &source, total_size, modified, config).await? {
+            Some(state) => {
+                tracing::debug!(
+                    "Resuming upload {} at part {}",
+                    state.upload_id,
+                    state.next_part_number()
+                );
+                on_part(state.completed_bytes());
+                state
+            }
+            None => {
+                let part_size = config.calculate_part_size(total_size);
+                let attributes = ObjectAttributes::with_content_type(content_type);
+                let upload_id = client.create_multipart_upload(target, &attributes).await?;
+                let state = UploadState::new(upload_id, target.to_string(), total_size, part_size)
+                    .with_source(source)
+                    .with_source_modified(modified);
+                if let Some(dir) = &config.state_dir {
+                    state.save(dir)?;
+                }
+                state
+            }
+        };
+    let upload_id = state.upload_id.clone();
+
+    let result = match upload_parts(client, target, file, &mut state, config, on_part).await {
+        Ok(()) => {
+            client
+                .complete_multipart_upload(target, &upload_id, &state.completed_parts)
+                .await
+        }
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(completed) => {
+            if let Some(dir) = &config.state_dir {
+                UploadState::delete(dir, &upload_id)?;
+            }
+            let mut info = ObjectInfo::file(&target.key, total_size as i64);
+            info.etag = completed.etag;
+            info.last_modified = completed.last_modified;
+            Ok(info)
+        }
+        Err(e) => {
+            if config.state_dir.is_none()
+                && let Err(abort_err) = client.abort_multipart_upload(target, &upload_id).await
+            {
+                tracing::warn!("Failed to abort multipart upload {upload_id}: {abort_err}");
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Identity of a local source file recorded in the upload state
+///
+/// This is the canonical path, so the same file resumes no matter how it was
+/// named on the command line or which directory `rc` was started from.
+async fn source_id(file: &Path) -> Result<String> {
+    Ok(tokio::fs::canonicalize(file)
+        .await?
+        .to_string_lossy()
+        .to_string())
+}
+
+/// Find a persisted upload of `source` to `target` that can be resumed
+///
+/// Pending uploads to the same target that were started from a different or
+/// since-modified file are aborted and their state discarded, as is the state
+/// of an upload the server no longer knows. The completed parts of a matching
+/// upload are checked against the server's part list; if that check fails for
+/// any other reason the error is returned and the state kept for a later try.
+async fn find_resumable(
+    client: &S3Client,
+    target: &RemotePath,
+    source: &str,
+    total_size: u64,
+    modified: Option<jiff::Timestamp>,
+    config: &MultipartConfig,
+) -> Result<Option<UploadState>> {
+    let Some(dir) = &config.state_dir else {
+        return Ok(None);
+    };
+
+    let mut resumable = None;
+    for mut state in UploadState::find_pending(dir, &target.to_string())? {
+        if resumable.is_none() && state.matches_source(source, total_size, modified) {
+            match client.list_parts(target, &state.upload_id).await {
+                Ok(uploaded) => {
+                    state.retain_uploaded(&uploaded);
+                    resumable = Some(state);
+                }
+                Err(Error::NotFound(_)) => {
+                    tracing::debug!("Upload {} no longer exists", state.upload_id);
+                    UploadState::delete(dir, &state.upload_id)?;
+                }
+                Err(e) => return Err(e),
+            }
+            continue;
+        }
+
+        if let Err(e) = client
+            .abort_multipart_upload(target, &state.upload_id)
+            .await
+        {
+            tracing::debug!("Failed to abort stale upload {}: {e}", state.upload_id);
+        }
+        UploadState::delete(dir, &state.upload_id)?;
+    }
+
+    Ok(resumable)
+}
+
+/// Upload all parts not yet recorded in `state`, updating it as parts complete
+async fn upload_parts(
+    client: &S3Client,
+    target: &RemotePath,
+    file: &Path,
+    state: &mut UploadState,
+    config: &MultipartConfig,
+    on_part: &(dyn Fn(u64) + Send + Sync),
+) -> Result<()> {
+    let upload_id = state.upload_id.clone();
+    let (part_size, total_size) = (state.part_size, state.total_size);
+    let part_count = calculate_parts(total_size, part_size) as i32;
+
+    let pending: Vec<i32> = (1..=part_count)
+        .filter(|n| !state.completed_parts.iter().any(|p| p.part_number == *n))
+        .collect();
+
+    let upload_id = upload_id.as_str();
+    let mut uploads = futures::stream::iter(pending)
+        .map(|part_number| async move {
+            let (start, end) = part_byte_range(part_number, part_size, total_size);
+            let data = read_part(file, start, end - start).await?;
+            let etag = client
+                .upload_part(target, upload_id, part_number, data)
+                .await?;
+            Ok::<_, Error>((part_number, etag, end - start))
+        })
+        .buffer_unordered(config.concurrency);
+
+    while let Some((part_number, etag, len)) = uploads.try_next().await? {
+        state.add_completed_part(part_number, etag);
+        if let Some(dir) = &config.state_dir {
+            state.save(dir)?;
+        }
+        on_part(len);
+    }
+
+    Ok(())
+}
+
+/// Read `len` bytes of a file starting at `offset`
+async fn read_part(file: &Path, offset: u64, len: u64) -> Result<Bytes> {
+    let mut f = tokio::fs::File::open(file).await?;
+    f.seek(std::io::SeekFrom::Start(offset)).await?;
+
+    let mut buf = vec![0u8; len as usize];
+    f.read_exact(&mut buf).await?;
+    Ok(Bytes::from(buf))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::stub::{StubResponse, StubServer};
 
     #[test]
     fn test_default_config() {
@@ -311,6 +571,219 @@ mod tests {
         assert_eq!(state.progress_percent(), 20.0);
     }
 
+    #[test]
+    fn test_use_multipart_threshold() {
+        let config = MultipartConfig::default();
+        assert!(!config.use_multipart(DEFAULT_MULTIPART_THRESHOLD));
+        assert!(config.use_multipart(DEFAULT_MULTIPART_THRESHOLD + 1));
+
+        let config = MultipartConfig::new().threshold(0);
+        assert!(config.use_multipart(1));
+    }
+
+    #[test]
+    fn test_upload_state_matches_source() {
+        let modified = Some(jiff::Timestamp::from_second(1_700_000_000).unwrap());
+        let state = UploadState::new("upload-123", "a/b/key", 1000, 100)
+            .with_source("/tmp/file")
+            .with_source_modified(modified);
+
+        assert!(state.matches_source("/tmp/file", 1000, modified));
+        assert!(!state.matches_source("/tmp/other", 1000, modified));
+        assert!(!state.matches_source("/tmp/file", 999, modified));
+        assert!(!state.matches_source("/tmp/file", 1000, None));
+        assert!(!state.matches_source(
+            "/tmp/file",
+            1000,
+            Some(jiff::Timestamp::from_second(1_700_000_001).unwrap())
+        ));
+    }
+
+    #[test]
+    fn test_upload_state_retain_uploaded() {
+        let mut state = UploadState::new("upload-123", "a/b/key", 250, 100);
+        state.add_completed_part(1, "\"etag1\"".to_string());
+        state.add_completed_part(2, "\"etag2\"".to_string());
+        state.add_completed_part(3, "\"etag3\"".to_string());
+
+        let uploaded = vec![
+            CompletedPart {
+                part_number: 1,
+                etag: "etag1".to_string(),
+            },
+            CompletedPart {
+                part_number: 2,
+                etag: "\"different\"".to_string(),
+            },
+            CompletedPart {
+                part_number: 3,
+                etag: "\"etag3\"".to_string(),
+            },
+        ];
+        state.retain_uploaded(&uploaded);
+
+        let numbers: Vec<i32> = state
+            .completed_parts
+            .iter()
+            .map(|p| p.part_number)
+            .collect();
+        assert_eq!(numbers, vec![1, 3]);
+        assert_eq!(state.completed_bytes(), 150);
+    }
+
+    #[test]
+    fn test_upload_state_without_source_modified_deserializes() {
+        let json = r#"{
+            "upload_id": "upload-123",
+            "target": "a/b/key",
+            "source": "/tmp/file",
+            "total_size": 1000,
+            "part_size": 100,
+            "completed_parts": [],
+            "last_updated": "2026-01-01T00:00:00Z"
+        }"#;
+        let state: UploadState = serde_json::from_str(json).unwrap();
+        assert!(state.source_modified.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_part() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.bin");
+        std::fs::write(&path, b"0123456789").unwrap();
+
+        let part = read_part(&path, 3, 4).await.unwrap();
+        assert_eq!(&part[..], b"3456");
+    }
+
+    /// A pending upload of a 10-byte file, with its first part recorded
+    struct Pending {
+        _dir: tempfile::TempDir,
+        file: PathBuf,
+        target: RemotePath,
+        config: MultipartConfig,
+        modified: Option<jiff::Timestamp>,
+    }
+
+    impl Pending {
+        async fn new() -> Self {
+            let dir = tempfile::tempdir().unwrap();
+            let file = dir.path().join("data.bin");
+            std::fs::write(&file, b"0123456789").unwrap();
+            let modified = std::fs::metadata(&file)
+                .unwrap()
+                .modified()
+                .ok()
+                .and_then(|t| jiff::Timestamp::try_from(t).ok());
+
+            let target = RemotePath::new("stub", "bucket", "key");
+            let mut state = UploadState::new("upload-1", target.to_string(), 10, 5)
+                .with_source(source_id(&file).await.unwrap())
+                .with_source_modified(modified);
+            state.add_completed_part(1, "\"etag1\"".to_string());
+
+            let config = MultipartConfig::new().state_dir(dir.path().join("state"));
+            state.save(config.state_dir.as_ref().unwrap()).unwrap();
+
+            Self {
+                _dir: dir,
+                file,
+                target,
+                config,
+                modified,
+            }
+        }
+
+        async fn find(&self, client: &S3Client) -> Result<Option<UploadState>> {
+            let source = source_id(&self.file).await.unwrap();
+            find_resumable(
+                client,
+                &self.target,
+                &source,
+                10,
+                self.modified,
+                &self.config,
+            )
+            .await
+        }
+
+        fn pending_count(&self) -> usize {
+            let dir = self.config.state_dir.as_ref().unwrap();
+            UploadState::find_pending(dir, &self.target.to_string())
+                .unwrap()
+                .len()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_source_id_is_canonical() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        let file = dir.path().join("data.bin");
+        std::fs::write(&file, b"data").unwrap();
+
+        let indirect = dir.path().join("sub").join("..").join("data.bin");
+        assert_eq!(
+            source_id(&file).await.unwrap(),
+            source_id(&indirect).await.unwrap()
+        );
+        assert!(source_id(&dir.path().join("missing")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_find_resumable_resumes_matching_upload() {
+        let server = StubServer::start(|_| {
+            StubResponse::ok(
+                "<ListPartsResult><Bucket>bucket</Bucket><Key>key</Key><UploadId>upload-1</UploadId>\
+                 <IsTruncated>false</IsTruncated><Part><PartNumber>1</PartNumber>\
+                 <ETag>\"etag1\"</ETag><Size>5</Size></Part></ListPartsResult>",
+            )
+            .header("Content-Type", "application/xml")
+        })
+        .await;
+        let client = S3Client::new(server.alias("stub")).await.unwrap();
+        let pending = Pending::new().await;
+
+        let state = pending.find(&client).await.unwrap().unwrap();
+        assert_eq!(state.upload_id, "upload-1");
+        assert_eq!(state.completed_bytes(), 5);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].path(), "/bucket/key");
+        assert_eq!(requests[0].query("uploadId").as_deref(), Some("upload-1"));
+    }
+
+    #[tokio::test]
+    async fn test_find_resumable_keeps_state_on_transient_error() {
+        let server =
+            StubServer::start(|_| StubResponse::error("503 Service Unavailable", "SlowDown")).await;
+        let client = S3Client::new(server.alias("stub")).await.unwrap();
+        let pending = Pending::new().await;
+
+        assert!(pending.find(&client).await.is_err());
+
+        // Nothing was aborted and the state is kept for the next attempt
+        let requests = server.requests();
+        assert!(
+            requests
+                .iter()
+                .all(|r| r.method == "GET" && r.has_query("uploadId")),
+            "{requests:?}"
+        );
+        assert_eq!(pending.pending_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_find_resumable_discards_missing_upload() {
+        let server =
+            StubServer::start(|_| StubResponse::error("404 Not Found", "NoSuchUpload")).await;
+        let client = S3Client::new(server.alias("stub")).await.unwrap();
+        let pending = Pending::new().await;
+
+        assert!(pending.find(&client).await.unwrap().is_none());
+        assert_eq!(pending.pending_count(), 0);
+    }
+
     #[test]
     fn test_calculate_parts() {
         assert_eq!(calculate_parts(100, 10), 10);
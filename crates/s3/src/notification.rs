@@ -0,0 +1,504 @@
+//! Bucket event notifications
+//!
+//! Streams events from the ListenBucketNotification extension exposed by
+//! RustFS and MinIO: a long-lived signed GET on the bucket whose response body
+//! carries one JSON document per line, interleaved with blank keep-alive lines.
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::time::Duration;
+
+use futures::Stream;
+use jiff::Timestamp;
+use rc_core::{Alias, Error, Result};
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::client::build_http_client;
+use crate::signing;
+
+/// Event names matching every object creation
+pub const EVENT_PUT: &str = "s3:ObjectCreated:*";
+
+/// Event names matching every object removal
+pub const EVENT_DELETE: &str = "s3:ObjectRemoved:*";
+
+/// Event names matching every object access
+pub const EVENT_GET: &str = "s3:ObjectAccessed:*";
+
+/// A stream of bucket events
+pub type EventStream = Pin<Box<dyn Stream<Item = Result<BucketEvent>> + Send>>;
+
+/// Filters for a notification listener
+#[derive(Debug, Clone, Default)]
+pub struct ListenOptions {
+    /// Only report keys starting with this prefix
+    pub prefix: String,
+
+    /// Only report keys ending with this suffix
+    pub suffix: String,
+
+    /// S3 event names to report (e.g. `s3:ObjectCreated:*`)
+    pub events: Vec<String>,
+}
+
+/// A single object event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BucketEvent {
+    /// S3 event name, e.g. `s3:ObjectCreated:Put`
+    pub event_name: String,
+
+    /// When the event happened
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<Timestamp>,
+
+    /// Bucket name
+    pub bucket: String,
+
+    /// Object key
+    pub key: String,
+
+    /// Object size in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<i64>,
+
+    /// Object ETag
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+
+    /// Object version ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<String>,
+}
+
+/// Client for the bucket notification listener endpoint
+pub struct NotificationClient {
+    http_client: reqwest::Client,
+    endpoint: String,
+    access_key: String,
+    secret_key: String,
+    region: String,
+}
+
+impl NotificationClient {
+    /// Create a new notification client from an alias
+    pub async fn new(alias: &Alias) -> Result<Self> {
+        // No read timeout: the listener stays open while no events arrive
+        let timeout = alias.timeout_config();
+        let http_client = build_http_client(
+            alias.insecure,
+            alias.ca_bundle.as_deref(),
+            Duration::from_millis(timeout.connect_ms),
+            None,
+        )
+        .await?;
+
+        Ok(Self {
+            http_client,
+            endpoint: alias.endpoint.trim_end_matches('/').to_string(),
+            access_key: alias.access_key.clone(),
+            secret_key: alias.secret_key.clone(),
+            region: alias.region.clone(),
+        })
+    }
+
+    /// Build the listen URL for a bucket
+    fn listen_url(&self, bucket: &str, options: &ListenOptions) -> String {
+        let mut query: Vec<(&str, &str)> = options
+            .events
+            .iter()
+            .map(|event| ("events", event.as_str()))
+            .collect();
+        query.push(("prefix", &options.prefix));
+        query.push(("suffix", &options.suffix));
+
+        let query_string = query
+            .iter()
+            .map(|(k, v)| format!("{k}={}", urlencoding::encode(v)))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        format!(
+            "{}/{}?{query_string}",
+            self.endpoint,
+            urlencoding::encode(bucket)
+        )
+    }
+
+    /// Start listening for events on a bucket
+    ///
+    /// The returned stream yields events until the server closes the
+    /// connection or a read fails.
+    pub async fn listen(&self, bucket: &str, options: &ListenOptions) -> Result<EventStream> {
+        let url = self.listen_url(bucket, options);
+        let host = listen_host(&url)?;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-amz-content-sha256",
+            signing::sha256_hex(b"")
+                .parse()
+                .map_err(|e| Error::Auth(format!("Invalid content hash: {e}")))?,
+        );
+        headers.insert(
+            "host",
+            host.parse()
+                .map_err(|e| Error::General(format!("Invalid host '{host}': {e}")))?,
+        );
+
+        let signed_headers = signing::sign_v4(
+            &self.access_key,
+            &self.secret_key,
+            &self.region,
+            &Method::GET,
+            &url,
+            &headers,
+            b"",
+        )?;
+
+        let response = self
+            .http_client
+            .get(&url)
+            .headers(signed_headers)
+            .send()
+            .await
+            .map_err(|e| Error::Network(format!("Request failed: {e}")))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(map_error(status, bucket, &body));
+        }
+
+        // Backends without the extension ignore the query and answer with a
+        // bucket listing instead of an event stream
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        if content_type.contains("xml") {
+            return Err(Error::UnsupportedFeature(format!(
+                "The backend does not support listening for bucket notifications: \
+                 got a {content_type} response"
+            )));
+        }
+
+        let state = ListenState {
+            response: Some(response),
+            buffer: Vec::new(),
+            pending: VecDeque::new(),
+        };
+
+        Ok(Box::pin(futures::stream::unfold(state, next_event)))
+    }
+}
+
+/// Host header for a listen URL, including a non-default port
+fn listen_host(url: &str) -> Result<String> {
+    let url = url::Url::parse(url)
+        .map_err(|e| Error::Config(format!("Invalid endpoint URL '{url}': {e}")))?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| Error::Config(format!("Endpoint URL '{url}' has no host")))?;
+
+    Ok(match url.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+    })
+}
+
+/// Progress through a listener response body
+struct ListenState {
+    response: Option<reqwest::Response>,
+    buffer: Vec<u8>,
+    pending: VecDeque<BucketEvent>,
+}
+
+/// Yield the next event, reading more of the body as needed
+async fn next_event(mut state: ListenState) -> Option<(Result<BucketEvent>, ListenState)> {
+    loop {
+        if let Some(event) = state.pending.pop_front() {
+            return Some((Ok(event), state));
+        }
+
+        let line = match state.buffer.iter().position(|b| *b == b'\n') {
+            Some(end) => state.buffer.drain(..=end).collect::<Vec<u8>>(),
+            None => {
+                let response = state.response.as_mut()?;
+                match response.chunk().await {
+                    Ok(Some(chunk)) => {
+                        state.buffer.extend_from_slice(&chunk);
+                        continue;
+                    }
+                    // The server closed the stream; parse whatever is left
+                    Ok(None) => {
+                        state.response = None;
+                        std::mem::take(&mut state.buffer)
+                    }
+                    Err(e) => {
+                        state.response = None;
+                        return Some((
+                            Err(Error::Network(format!("Event stream failed: {e}"))),
+                            state,
+                        ));
+                    }
+                }
+            }
+        };
+
+        match parse_event_line(&String::from_utf8_lossy(&line)) {
+            Ok(events) => state.pending.extend(events),
+            Err(e) => return Some((Err(e), state)),
+        }
+    }
+}
+
+/// Notification document sent for each batch of events
+#[derive(Debug, Deserialize)]
+struct NotificationMessage {
+    #[serde(rename = "Records", default)]
+    records: Option<Vec<EventRecord>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EventRecord {
+    event_name: String,
+    #[serde(default)]
+    event_time: Option<String>,
+    s3: EventEntity,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventEntity {
+    bucket: EventBucket,
+    object: EventObject,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventBucket {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EventObject {
+    key: String,
+    #[serde(default)]
+    size: Option<i64>,
+    #[serde(default, rename = "eTag")]
+    etag: Option<String>,
+    #[serde(default)]
+    version_id: Option<String>,
+}
+
+/// Parse one line of a listener response into its events
+///
+/// Blank keep-alive lines yield no events. Keys arrive URL-encoded with
+/// spaces as `+`, as the servers encode them with Go's `url.QueryEscape`.
+pub fn parse_event_line(line: &str) -> Result<Vec<BucketEvent>> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let message: NotificationMessage = serde_json::from_str(line)?;
+
+    Ok(message
+        .records
+        .unwrap_or_default()
+        .into_iter()
+        .map(|record| {
+            let raw_key = record.s3.object.key.replace('+', " ");
+            let key = urlencoding::decode(&raw_key)
+                .map(|key| key.into_owned())
+                .unwrap_or(raw_key);
+
+            BucketEvent {
+                event_name: record.event_name,
+                time: record.event_time.and_then(|t| t.parse().ok()),
+                bucket: record.s3.bucket.name,
+                key,
+                size: record.s3.object.size,
+                etag: record.s3.object.etag.filter(|etag| !etag.is_empty()),
+                version_id: record.s3.object.version_id.filter(|id| !id.is_empty()),
+            }
+        })
+        .collect())
+}
+
+/// Map a failed listen response to an error
+fn map_error(status: StatusCode, bucket: &str, body: &str) -> Error {
+    match status {
+        StatusCode::NOT_FOUND if body.contains("NoSuchBucket") => {
+            Error::NotFound(format!("Bucket not found: {bucket}"))
+        }
+        StatusCode::FORBIDDEN | StatusCode::UNAUTHORIZED => Error::Auth(body.to_string()),
+        StatusCode::NOT_FOUND | StatusCode::NOT_IMPLEMENTED | StatusCode::METHOD_NOT_ALLOWED => {
+            Error::UnsupportedFeature(format!(
+                "The backend does not support listening for bucket notifications: {body}"
+            ))
+        }
+        _ => Error::Network(format!("HTTP {}: {body}", status.as_u16())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    const PUT_EVENT: &str = r#"{"Records":[{"eventVersion":"2.0","eventSource":"rustfs:s3","eventTime":"2024-05-01T10:00:00.000Z","eventName":"s3:ObjectCreated:Put","s3":{"bucket":{"name":"photos"},"object":{"key":"2024%2Fcat+pic.jpg","size":1024,"eTag":"abc123","versionId":""}}}]}"#;
+
+    const DELETE_EVENT: &str = r#"{"Records":[{"eventName":"s3:ObjectRemoved:Delete","s3":{"bucket":{"name":"photos"},"object":{"key":"old.jpg"}}}]}"#;
+
+    #[test]
+    fn test_parse_event_line() {
+        let events = parse_event_line(PUT_EVENT).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_name, "s3:ObjectCreated:Put");
+        assert_eq!(events[0].bucket, "photos");
+        assert_eq!(events[0].key, "2024/cat pic.jpg");
+        assert_eq!(events[0].size, Some(1024));
+        assert_eq!(events[0].version_id, None);
+        assert_eq!(
+            events[0].time,
+            Some("2024-05-01T10:00:00Z".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_event_line_keepalive() {
+        assert!(parse_event_line(" ").unwrap().is_empty());
+        assert!(parse_event_line("{\"Records\":null}").unwrap().is_empty());
+        assert!(parse_event_line("not json").is_err());
+    }
+
+    /// Serve one listen request, answering with `body`, and return the request head
+    async fn serve_once(listener: TcpListener, body: String) -> String {
+        let (mut socket, _) = listener.accept().await.unwrap();
+
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = socket.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+        }
+
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nConnection: close\r\n\r\n{body}"
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+        socket.shutdown().await.unwrap();
+
+        String::from_utf8_lossy(&request).into_owned()
+    }
+
+    #[tokio::test]
+    async fn test_listen_against_stub_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let body = format!(" \n{PUT_EVENT}\n \n{DELETE_EVENT}\n");
+        let server = tokio::spawn(serve_once(listener, body));
+
+        let alias = Alias::new("stub", &endpoint, "access", "secret");
+        let client = NotificationClient::new(&alias).await.unwrap();
+        let options = ListenOptions {
+            prefix: "2024/".to_string(),
+            suffix: ".jpg".to_string(),
+            events: vec![EVENT_PUT.to_string(), EVENT_DELETE.to_string()],
+        };
+
+        let events: Vec<BucketEvent> = client
+            .listen("photos", &options)
+            .await
+            .unwrap()
+            .map(|event| event.unwrap())
+            .collect()
+            .await;
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].key, "2024/cat pic.jpg");
+        assert_eq!(events[1].event_name, "s3:ObjectRemoved:Delete");
+
+        let request = server.await.unwrap();
+        let request_line = request.lines().next().unwrap();
+        assert!(request_line.starts_with("GET /photos?events=s3%3AObjectCreated%3A%2A"));
+        assert!(request_line.contains("prefix=2024%2F"));
+        assert!(request_line.contains("suffix=.jpg"));
+        assert!(
+            request
+                .to_lowercase()
+                .contains("authorization: aws4-hmac-sha256")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_listen_missing_bucket() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await.unwrap();
+            let body = "<Error><Code>NoSuchBucket</Code></Error>";
+            let response = format!(
+                "HTTP/1.1 404 Not Found\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let alias = Alias::new("stub", &endpoint, "access", "secret");
+        let client = NotificationClient::new(&alias).await.unwrap();
+        let result = client.listen("missing", &ListenOptions::default()).await;
+
+        assert!(matches!(result, Err(Error::NotFound(_))));
+        server.await.unwrap();
+    }
+
+    #[test]
+    fn test_listen_host() {
+        assert_eq!(
+            listen_host("https://gw.corp/s3/photos?prefix=").unwrap(),
+            "gw.corp"
+        );
+        assert_eq!(
+            listen_host("http://127.0.0.1:9000/photos").unwrap(),
+            "127.0.0.1:9000"
+        );
+        assert_eq!(
+            listen_host("https://s3.local:443/photos").unwrap(),
+            "s3.local"
+        );
+        assert!(listen_host("not a url").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_listen_bucket_listing_is_unsupported() {
+        // Backends without the extension answer the listen GET with ListObjects
+        let server = crate::stub::StubServer::start(|_| {
+            crate::stub::StubResponse::ok(
+                "<ListBucketResult><Name>photos</Name><KeyCount>0</KeyCount></ListBucketResult>",
+            )
+            .header("Content-Type", "application/xml")
+        })
+        .await;
+
+        let client = NotificationClient::new(&server.alias("stub"))
+            .await
+            .unwrap();
+        let result = client.listen("photos", &ListenOptions::default()).await;
+
+        assert!(
+            matches!(result, Err(Error::UnsupportedFeature(_))),
+            "{:?}",
+            result.err()
+        );
+    }
+}
@@ -0,0 +1,247 @@
+//! Stub S3 server for tests
+//!
+//! Accepts plain HTTP/1.1 requests on a local port, answers each one from a
+//! handler function and records what it received. Every response closes its
+//! connection, so concurrent requests from the SDK arrive on separate sockets.
+
+use std::sync::{Arc, Mutex};
+
+use rc_core::{Alias, RetryConfig};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// A request received by the stub server
+#[derive(Debug, Clone)]
+pub(crate) struct StubRequest {
+    pub method: String,
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl StubRequest {
+    /// Request path without the query string
+    pub fn path(&self) -> &str {
+        self.target.split('?').next().unwrap_or_default()
+    }
+
+    /// Decoded value of a query parameter
+    pub fn query(&self, name: &str) -> Option<String> {
+        let url = url::Url::parse(&format!("http://stub{}", self.target)).ok()?;
+        url.query_pairs()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.into_owned())
+    }
+
+    /// Whether the query string contains `name`, with or without a value
+    pub fn has_query(&self, name: &str) -> bool {
+        self.query(name).is_some()
+    }
+
+    /// Value of a request header
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// A response returned by a stub handler
+#[derive(Debug, Clone)]
+pub(crate) struct StubResponse {
+    status: &'static str,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl StubResponse {
+    /// 200 OK with `body`
+    pub fn ok(body: impl Into<Vec<u8>>) -> Self {
+        Self::status("200 OK", body)
+    }
+
+    /// Response with the given status line and body
+    pub fn status(status: &'static str, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// S3 error document with `code`
+    pub fn error(status: &'static str, code: &str) -> Self {
+        Self::status(
+            status,
+            format!("<Error><Code>{code}</Code><Message>{code}</Message></Error>"),
+        )
+        .header("Content-Type", "application/xml")
+    }
+
+    /// Add a response header
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+type Handler = dyn Fn(&StubRequest) -> StubResponse + Send + Sync;
+
+/// A running stub server
+pub(crate) struct StubServer {
+    endpoint: String,
+    requests: Arc<Mutex<Vec<StubRequest>>>,
+}
+
+impl StubServer {
+    /// Start a server answering every request with `handler`
+    pub async fn start(
+        handler: impl Fn(&StubRequest) -> StubResponse + Send + Sync + 'static,
+    ) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let recorded = Arc::clone(&requests);
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let handler = Arc::clone(&handler);
+                let recorded = Arc::clone(&recorded);
+                tokio::spawn(async move {
+                    if let Some(request) = serve(socket, handler.as_ref()).await {
+                        recorded.lock().unwrap().push(request);
+                    }
+                });
+            }
+        });
+
+        Self { endpoint, requests }
+    }
+
+    /// Path-style alias for the server that does not retry
+    pub fn alias(&self, name: &str) -> Alias {
+        let mut alias = Alias::new(name, &self.endpoint, "access", "secret");
+        alias.bucket_lookup = "path".to_string();
+        alias.retry = Some(RetryConfig {
+            max_attempts: 1,
+            ..Default::default()
+        });
+        alias
+    }
+
+    /// Requests answered so far, in completion order
+    pub fn requests(&self) -> Vec<StubRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Read one request from `socket` and answer it
+async fn serve(mut socket: TcpStream, handler: &Handler) -> Option<StubRequest> {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 8192];
+    let head_end = loop {
+        if let Some(pos) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        let n = socket.read(&mut buf).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        data.extend_from_slice(&buf[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&data[..head_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split(' ');
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?.to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(n, v)| (n.trim().to_string(), v.trim().to_string()))
+        .collect();
+    let mut request = StubRequest {
+        method,
+        target,
+        headers,
+        body: data[head_end + 4..].to_vec(),
+    };
+
+    let chunked = request
+        .header("transfer-encoding")
+        .is_some_and(|v| v.eq_ignore_ascii_case("chunked"));
+    if chunked {
+        while !request.body.ends_with(b"0\r\n\r\n") {
+            let n = socket.read(&mut buf).await.ok()?;
+            if n == 0 {
+                break;
+            }
+            request.body.extend_from_slice(&buf[..n]);
+        }
+        request.body = decode_chunked(&request.body);
+    } else {
+        let content_length: usize = request
+            .header("content-length")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+        while request.body.len() < content_length {
+            let n = socket.read(&mut buf).await.ok()?;
+            if n == 0 {
+                break;
+            }
+            request.body.extend_from_slice(&buf[..n]);
+        }
+    }
+
+    // Bodies sent with flexible checksums use aws-chunked framing
+    if request
+        .header("content-encoding")
+        .is_some_and(|v| v.contains("aws-chunked"))
+    {
+        request.body = decode_chunked(&request.body);
+    }
+
+    let response = handler(&request);
+    let mut head = format!("HTTP/1.1 {}\r\nConnection: close\r\n", response.status);
+    // HEAD handlers set the length of the object they describe
+    if !response
+        .headers
+        .iter()
+        .any(|(n, _)| n.eq_ignore_ascii_case("content-length"))
+    {
+        head.push_str(&format!("Content-Length: {}\r\n", response.body.len()));
+    }
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("\r\n");
+
+    socket.write_all(head.as_bytes()).await.ok()?;
+    if request.method != "HEAD" {
+        socket.write_all(&response.body).await.ok()?;
+    }
+    socket.shutdown().await.ok();
+
+    Some(request)
+}
+
+/// Decode a chunked body (`<hex size>[;ext]\r\n<data>\r\n ... 0\r\n`)
+fn decode_chunked(mut data: &[u8]) -> Vec<u8> {
+    let mut body = Vec::new();
+    while let Some(line_end) = data.windows(2).position(|w| w == b"\r\n") {
+        let line = String::from_utf8_lossy(&data[..line_end]);
+        let size_hex = line.split(';').next().unwrap_or_default().trim();
+        let Ok(size) = usize::from_str_radix(size_hex, 16) else {
+            break;
+        };
+        if size == 0 {
+            break;
+        }
+        let start = line_end + 2;
+        let end = (start + size).min(data.len());
+        body.extend_from_slice(&data[start..end]);
+        data = &data[(end + 2).min(data.len())..];
+    }
+    body
+}
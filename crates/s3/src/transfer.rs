@@ -0,0 +1,388 @@
+//! Object transfer between S3 clients
+//!
+//! Copies objects between two endpoints (for example two aliases) by streaming
+//! data through this process. Server-side copy only works within a single
+//! endpoint, so this is used whenever source and destination differ.
+
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
+use rc_core::{Error, ObjectInfo, ObjectStore as _, RemotePath, Result};
+use tokio::io::AsyncReadExt;
+
+use crate::S3Client;
+use crate::client::ObjectAttributes;
+use crate::multipart::{CompletedPart, MultipartConfig, calculate_parts, part_byte_range};
+
+/// Source object of a cross-client copy
+///
+/// `info` and `attributes` come from
+/// [`S3Client::head_object_with_attributes`] on `client`, so the copy itself
+/// does not HEAD the source again.
+pub struct CopySource<'a> {
+    /// Client for the source endpoint
+    pub client: &'a S3Client,
+
+    /// Source object path
+    pub path: &'a RemotePath,
+
+    /// Version of the source object to copy, or the latest when `None`
+    pub version_id: Option<&'a str>,
+
+    /// Size and ETag of the source object
+    pub info: &'a ObjectInfo,
+
+    /// Content type, user metadata and tags to carry over
+    pub attributes: &'a ObjectAttributes,
+}
+
+/// Copy an object from one client to another without touching local disk
+///
+/// Content type, user metadata and tags are carried over. Objects above
+/// `config.threshold` are copied as ranged GETs feeding a concurrent multipart
+/// upload, so at most `concurrency` parts are held in memory. `on_part` is
+/// called with the byte size of each copied part.
+pub async fn copy_object(
+    source: &CopySource<'_>,
+    dst_client: &S3Client,
+    dst: &RemotePath,
+    config: &MultipartConfig,
+    on_part: &(dyn Fn(u64) + Send + Sync),
+) -> Result<ObjectInfo> {
+    let total_size = source.info.size_bytes.unwrap_or(0).max(0) as u64;
+
+    if !config.use_multipart(total_size) {
+        let mut stream = match source.version_id {
+            Some(version_id) => {
+                source
+                    .client
+                    .get_object_version_stream(source.path, version_id)
+                    .await?
+            }
+            None => source.client.get_object_stream(source.path).await?,
+        };
+        let mut data = Vec::with_capacity(total_size as usize);
+        stream.body.read_to_end(&mut data).await?;
+
+        let result = dst_client
+            .put_object_with_attributes(dst, Bytes::from(data), source.attributes)
+            .await?;
+        on_part(total_size);
+        return Ok(result);
+    }
+
+    let upload_id = dst_client
+        .create_multipart_upload(dst, source.attributes)
+        .await?;
+    let parts = PartCopy {
+        source,
+        dst_client,
+        dst,
+        upload_id: &upload_id,
+        total_size,
+        part_size: config.calculate_part_size(total_size),
+    };
+
+    let result = match parts.run(config.concurrency, on_part).await {
+        Ok(parts) => {
+            dst_client
+                .complete_multipart_upload(dst, &upload_id, &parts)
+                .await
+        }
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(completed) => {
+            let mut info = ObjectInfo::file(&dst.key, total_size as i64);
+            info.etag = completed.etag;
+            info.last_modified = completed.last_modified;
+            Ok(info)
+        }
+        Err(e) => {
+            if let Err(abort_err) = dst_client.abort_multipart_upload(dst, &upload_id).await {
+                tracing::warn!("Failed to abort multipart upload {upload_id}: {abort_err}");
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Parts of a source object being copied into a destination multipart upload
+struct PartCopy<'a> {
+    source: &'a CopySource<'a>,
+    dst_client: &'a S3Client,
+    dst: &'a RemotePath,
+    upload_id: &'a str,
+    total_size: u64,
+    part_size: u64,
+}
+
+impl PartCopy<'_> {
+    /// Copy every part, `concurrency` at a time
+    async fn run(
+        &self,
+        concurrency: usize,
+        on_part: &(dyn Fn(u64) + Send + Sync),
+    ) -> Result<Vec<CompletedPart>> {
+        let part_count = calculate_parts(self.total_size, self.part_size) as i32;
+
+        let mut copies = futures::stream::iter(1..=part_count)
+            .map(|part_number| self.copy_part(part_number))
+            .buffer_unordered(concurrency);
+
+        let mut parts = Vec::with_capacity(part_count as usize);
+        while let Some((part, len)) = copies.try_next().await? {
+            parts.push(part);
+            on_part(len);
+        }
+
+        Ok(parts)
+    }
+
+    /// Fetch one range of the source and upload it as a part
+    async fn copy_part(&self, part_number: i32) -> Result<(CompletedPart, u64)> {
+        let (start, end) = part_byte_range(part_number, self.part_size, self.total_size);
+        let mut body = self
+            .source
+            .client
+            .get_object_range(
+                self.source.path,
+                self.source.version_id,
+                start,
+                end,
+                self.source.info.etag.as_deref(),
+            )
+            .await?;
+
+        let mut data = Vec::with_capacity((end - start) as usize);
+        body.read_to_end(&mut data).await?;
+        if data.len() as u64 != end - start {
+            return Err(Error::Network(format!(
+                "Short read for part {part_number}: expected {} bytes, got {}",
+                end - start,
+                data.len()
+            )));
+        }
+
+        let etag = self
+            .dst_client
+            .upload_part(self.dst, self.upload_id, part_number, Bytes::from(data))
+            .await?;
+        Ok((CompletedPart { part_number, etag }, end - start))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::multipart::MIN_PART_SIZE;
+    use crate::stub::{StubRequest, StubResponse, StubServer};
+
+    fn object_data(size: usize) -> Arc<Vec<u8>> {
+        Arc::new((0..size).map(|i| (i % 251) as u8).collect())
+    }
+
+    /// Source endpoint serving one tagged object with user metadata
+    async fn source_server(data: Arc<Vec<u8>>) -> StubServer {
+        StubServer::start(move |request: &StubRequest| {
+            let len = data.len();
+            match request.method.as_str() {
+                "HEAD" => StubResponse::ok(Vec::new())
+                    .header("Content-Length", &len.to_string())
+                    .header("Content-Type", "image/png")
+                    .header("ETag", "\"src\"")
+                    .header("x-amz-meta-owner", "alice")
+                    .header("x-amz-tagging-count", "1"),
+                "GET" if request.has_query("tagging") => StubResponse::ok(
+                    "<Tagging><TagSet><Tag><Key>team</Key><Value>data</Value></Tag></TagSet></Tagging>",
+                )
+                .header("Content-Type", "application/xml"),
+                "GET" => match request
+                    .header("range")
+                    .and_then(|r| r.strip_prefix("bytes="))
+                    .and_then(|r| r.split_once('-'))
+                {
+                    Some((start, end)) => {
+                        let (start, end): (usize, usize) =
+                            (start.parse().unwrap(), end.parse().unwrap());
+                        StubResponse::status("206 Partial Content", data[start..=end].to_vec())
+                            .header("Content-Range", &format!("bytes {start}-{end}/{len}"))
+                            .header("ETag", "\"src\"")
+                    }
+                    None => StubResponse::ok(data.to_vec()).header("ETag", "\"src\""),
+                },
+                _ => StubResponse::error("405 Method Not Allowed", "MethodNotAllowed"),
+            }
+        })
+        .await
+    }
+
+    /// Destination endpoint accepting single and multipart uploads
+    async fn destination_server() -> StubServer {
+        StubServer::start(|request: &StubRequest| {
+            match (request.method.as_str(), request.has_query("uploads")) {
+                ("POST", true) => StubResponse::ok(
+                    "<InitiateMultipartUploadResult><Bucket>dst</Bucket><Key>copy.png</Key>\
+                     <UploadId>up-1</UploadId></InitiateMultipartUploadResult>",
+                )
+                .header("Content-Type", "application/xml"),
+                ("POST", false) => StubResponse::ok(
+                    "<CompleteMultipartUploadResult><Bucket>dst</Bucket><Key>copy.png</Key>\
+                     <ETag>\"multi-2\"</ETag></CompleteMultipartUploadResult>",
+                )
+                .header("Content-Type", "application/xml"),
+                ("PUT", _) => {
+                    let etag = match request.query("partNumber") {
+                        Some(n) => format!("\"part-{n}\""),
+                        None => "\"single\"".to_string(),
+                    };
+                    StubResponse::ok(Vec::new()).header("ETag", &etag)
+                }
+                _ => StubResponse::error("405 Method Not Allowed", "MethodNotAllowed"),
+            }
+        })
+        .await
+    }
+
+    async fn copy(
+        src_server: &StubServer,
+        dst_server: &StubServer,
+        config: &MultipartConfig,
+        version_id: Option<&str>,
+    ) -> Result<ObjectInfo> {
+        let src_client = S3Client::new(src_server.alias("src")).await.unwrap();
+        let dst_client = S3Client::new(dst_server.alias("dst")).await.unwrap();
+        let src = RemotePath::new("src", "photos", "cat.png");
+        let dst = RemotePath::new("dst", "backup", "copy.png");
+
+        let (info, attributes) = src_client
+            .head_object_with_attributes(&src, version_id)
+            .await?;
+        let source = CopySource {
+            client: &src_client,
+            path: &src,
+            version_id,
+            info: &info,
+            attributes: &attributes,
+        };
+        copy_object(&source, &dst_client, &dst, config, &|_| {}).await
+    }
+
+    fn count(server: &StubServer, method: &str) -> usize {
+        server
+            .requests()
+            .iter()
+            .filter(|r| r.method == method)
+            .count()
+    }
+
+    #[tokio::test]
+    async fn test_copy_small_object_between_clients() {
+        let data = object_data(1000);
+        let src_server = source_server(Arc::clone(&data)).await;
+        let dst_server = destination_server().await;
+
+        let info = copy(&src_server, &dst_server, &MultipartConfig::default(), None)
+            .await
+            .unwrap();
+        assert_eq!(info.size_bytes, Some(1000));
+
+        // The source is read with one HEAD, one tagging GET and one object GET
+        assert_eq!(count(&src_server, "HEAD"), 1);
+        assert_eq!(count(&src_server, "GET"), 2);
+
+        let requests = dst_server.requests();
+        assert_eq!(requests.len(), 1);
+        let put = &requests[0];
+        assert_eq!(put.method, "PUT");
+        assert_eq!(put.path(), "/backup/copy.png");
+        assert_eq!(put.body, *data);
+        assert_eq!(put.header("content-type"), Some("image/png"));
+        assert_eq!(put.header("x-amz-meta-owner"), Some("alice"));
+        assert_eq!(put.header("x-amz-tagging"), Some("team=data"));
+    }
+
+    #[tokio::test]
+    async fn test_copy_large_object_streams_parts_between_clients() {
+        let size = MIN_PART_SIZE as usize + 4096;
+        let data = object_data(size);
+        let src_server = source_server(Arc::clone(&data)).await;
+        let dst_server = destination_server().await;
+
+        let config = MultipartConfig::new().part_size(MIN_PART_SIZE).threshold(0);
+        let info = copy(&src_server, &dst_server, &config, None).await.unwrap();
+        assert_eq!(info.size_bytes, Some(size as i64));
+        assert_eq!(info.etag.as_deref(), Some("multi-2"));
+        assert_eq!(count(&src_server, "HEAD"), 1);
+
+        // Every range GET is pinned to the source ETag
+        let src_requests = src_server.requests();
+        let ranged: Vec<&StubRequest> = src_requests
+            .iter()
+            .filter(|r| r.header("range").is_some())
+            .collect();
+        assert_eq!(ranged.len(), 2);
+        assert!(
+            ranged
+                .iter()
+                .all(|r| r.header("if-match") == Some("\"src\""))
+        );
+
+        let dst_requests = dst_server.requests();
+        let create = dst_requests
+            .iter()
+            .find(|r| r.has_query("uploads"))
+            .unwrap();
+        assert_eq!(create.header("content-type"), Some("image/png"));
+        assert_eq!(create.header("x-amz-meta-owner"), Some("alice"));
+        assert_eq!(create.header("x-amz-tagging"), Some("team=data"));
+
+        let mut parts: Vec<&StubRequest> =
+            dst_requests.iter().filter(|r| r.method == "PUT").collect();
+        parts.sort_by_key(|r| r.query("partNumber"));
+        let uploaded: Vec<u8> = parts.iter().flat_map(|r| r.body.clone()).collect();
+        assert_eq!(uploaded, *data);
+
+        let complete = dst_requests
+            .iter()
+            .find(|r| r.method == "POST" && r.has_query("uploadId"))
+            .unwrap();
+        let body = String::from_utf8_lossy(&complete.body);
+        assert!(body.contains("part-1") && body.contains("part-2"), "{body}");
+    }
+
+    #[tokio::test]
+    async fn test_copy_object_version_between_clients() {
+        let data = object_data(1000);
+        let src_server = source_server(Arc::clone(&data)).await;
+        let dst_server = destination_server().await;
+
+        copy(
+            &src_server,
+            &dst_server,
+            &MultipartConfig::default(),
+            Some("v1"),
+        )
+        .await
+        .unwrap();
+
+        // HEAD, tagging and object GET all address the requested version
+        let src_requests = src_server.requests();
+        assert_eq!(src_requests.len(), 3);
+        assert!(
+            src_requests
+                .iter()
+                .all(|r| r.query("versionId").as_deref() == Some("v1"))
+        );
+
+        let requests = dst_server.requests();
+        assert_eq!(requests.len(), 1);
+        let put = &requests[0];
+        assert_eq!(put.method, "PUT");
+        assert_eq!(put.body, *data);
+        assert_eq!(put.header("x-amz-meta-owner"), Some("alice"));
+        assert_eq!(put.header("x-amz-tagging"), Some("team=data"));
+    }
+}